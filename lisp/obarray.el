@@ -30,22 +30,10 @@
 (defconst obarray-default-size 59
   "The value 59 is an arbitrary prime number that gives a good hash.")
 
-(defun obarray-make (&optional size)
-  "Return a new obarray of size SIZE or `obarray-default-size'."
-  (let ((size (or size obarray-default-size)))
-    (if (< 0 size)
-        (make-vector size 0)
-      (signal 'wrong-type-argument '(size 0)))))
-
 (defun obarray-size (ob)
   "Return the number of slots of obarray OB."
   (length ob))
 
-(defun obarrayp (object)
-  "Return t if OBJECT is an obarray."
-  (and (vectorp object)
-       (< 0 (length object))))
-
 ;; Don’t use obarray as a variable name to avoid shadowing.
 (defun obarray-get (ob name)
   "Return symbol named NAME if it is contained in obarray OB.
@@ -1309,26 +1309,6 @@ and the greater of them is not at the start of a line."
 		done)))
 	(- (buffer-size) (forward-line (buffer-size)))))))
 
-(defun line-number-at-pos (&optional pos absolute)
-  "Return buffer line number at position POS.
-If POS is nil, use current buffer location.
-
-If ABSOLUTE is nil, the default, counting starts
-at (point-min), so the value refers to the contents of the
-accessible portion of the (potentially narrowed) buffer.  If
-ABSOLUTE is non-nil, ignore any narrowing and return the
-absolute line number."
-  (save-restriction
-    (when absolute
-      (widen))
-    (let ((opoint (or pos (point))) start)
-      (save-excursion
-        (goto-char (point-min))
-        (setq start (point))
-        (goto-char opoint)
-        (forward-line 0)
-        (1+ (count-lines start (point)))))))
-
 (defun what-cursor-position (&optional detail)
   "Print info on cursor position (on screen and within buffer).
 Also describe the character after point, and give its character code
@@ -7081,21 +7061,6 @@ current object."
       (delete-region (point) (+ (point) len2))
       (set-marker boundary nil))))
 
-(defun backward-word (&optional arg)
-  "Move backward until encountering the beginning of a word.
-With argument ARG, do this that many times.
-If ARG is omitted or nil, move point backward one word.
-
-The word boundaries are normally determined by the buffer's
-syntax table and character script (according to
-`char-script-table'), but `find-word-boundary-function-table',
-such as set up by `subword-mode', can change that.  If a Lisp
-program needs to move by words determined strictly by the syntax
-table, it should use `backward-word-strictly' instead.  See Info
-node `(elisp) Word Motion' for details."
-  (interactive "^p")
-  (forward-word (- (or arg 1))))
-
 (defun mark-word (&optional arg allow-extend)
   "Set mark ARG words away from point.
 The place mark goes is the same place \\[forward-word] would
@@ -7133,46 +7098,6 @@ With argument ARG, do this that many times."
   (interactive "p")
   (kill-word (- arg)))
 
-(defun current-word (&optional strict really-word)
-  "Return the word at or near point, as a string.
-The return value includes no text properties.
-
-If optional arg STRICT is non-nil, return nil unless point is
-within or adjacent to a word, otherwise look for a word within
-point's line.  If there is no word anywhere on point's line, the
-value is nil regardless of STRICT.
-
-By default, this function treats as a single word any sequence of
-characters that have either word or symbol syntax.  If optional
-arg REALLY-WORD is non-nil, only characters of word syntax can
-constitute a word."
-  (save-excursion
-    (let* ((oldpoint (point)) (start (point)) (end (point))
-	   (syntaxes (if really-word "w" "w_"))
-	   (not-syntaxes (concat "^" syntaxes)))
-      (skip-syntax-backward syntaxes) (setq start (point))
-      (goto-char oldpoint)
-      (skip-syntax-forward syntaxes) (setq end (point))
-      (when (and (eq start oldpoint) (eq end oldpoint)
-		 ;; Point is neither within nor adjacent to a word.
-		 (not strict))
-	;; Look for preceding word in same line.
-	(skip-syntax-backward not-syntaxes (line-beginning-position))
-	(if (bolp)
-	    ;; No preceding word in same line.
-	    ;; Look for following word in same line.
-	    (progn
-	      (skip-syntax-forward not-syntaxes (line-end-position))
-	      (setq start (point))
-	      (skip-syntax-forward syntaxes)
-	      (setq end (point)))
-	  (setq end (point))
-	  (skip-syntax-backward syntaxes)
-	  (setq start (point))))
-      ;; If we found something nonempty, return it as a string.
-      (unless (= start end)
-	(buffer-substring-no-properties start end)))))
-
 (defcustom fill-prefix nil
   "String for filling to insert at front of new line, or nil for none."
   :type '(choice (const :tag "None" nil)
@@ -321,26 +321,6 @@ Note: (user-error \"%s\" VALUE) makes the message VALUE without
 interpreting format characters like `%', `\\=`', and `\\=''."
   (signal 'user-error (list (apply #'format-message format args))))
 
-(defun define-error (name message &optional parent)
-  "Define NAME as a new error signal.
-MESSAGE is a string that will be output to the echo area if such an error
-is signaled without being caught by a `condition-case'.
-PARENT is either a signal or a list of signals from which it inherits.
-Defaults to `error'."
-  (unless parent (setq parent 'error))
-  (let ((conditions
-         (if (consp parent)
-             (apply #'append
-                    (mapcar (lambda (parent)
-                              (cons parent
-                                    (or (get parent 'error-conditions)
-                                        (error "Unknown signal `%s'" parent))))
-                            parent))
-           (cons parent (get parent 'error-conditions)))))
-    (put name 'error-conditions
-         (delete-dups (copy-sequence (cons name conditions))))
-    (when message (put name 'error-message message))))
-
 ;; We put this here instead of in frame.el so that it's defined even on
 ;; systems where frame.el isn't loaded.
 (defun frame-configuration-p (object)
@@ -379,65 +359,11 @@ was called."
         (setq i (1- i)))
       x)))
 
-(defun caar (x)
-  "Return the car of the car of X."
-  (declare (compiler-macro internal--compiler-macro-cXXr))
-  (car (car x)))
-
-(defun cadr (x)
-  "Return the car of the cdr of X."
-  (declare (compiler-macro internal--compiler-macro-cXXr))
-  (car (cdr x)))
-
-(defun cdar (x)
-  "Return the cdr of the car of X."
-  (declare (compiler-macro internal--compiler-macro-cXXr))
-  (cdr (car x)))
-
-(defun cddr (x)
-  "Return the cdr of the cdr of X."
-  (declare (compiler-macro internal--compiler-macro-cXXr))
-  (cdr (cdr x)))
-
-(defun caaar (x)
-  "Return the `car' of the `car' of the `car' of X."
-  (declare (compiler-macro internal--compiler-macro-cXXr))
-  (car (car (car x))))
-
-(defun caadr (x)
-  "Return the `car' of the `car' of the `cdr' of X."
-  (declare (compiler-macro internal--compiler-macro-cXXr))
-  (car (car (cdr x))))
-
-(defun cadar (x)
-  "Return the `car' of the `cdr' of the `car' of X."
-  (declare (compiler-macro internal--compiler-macro-cXXr))
-  (car (cdr (car x))))
-
-(defun caddr (x)
-  "Return the `car' of the `cdr' of the `cdr' of X."
-  (declare (compiler-macro internal--compiler-macro-cXXr))
-  (car (cdr (cdr x))))
-
-(defun cdaar (x)
-  "Return the `cdr' of the `car' of the `car' of X."
-  (declare (compiler-macro internal--compiler-macro-cXXr))
-  (cdr (car (car x))))
-
-(defun cdadr (x)
-  "Return the `cdr' of the `car' of the `cdr' of X."
-  (declare (compiler-macro internal--compiler-macro-cXXr))
-  (cdr (car (cdr x))))
-
-(defun cddar (x)
-  "Return the `cdr' of the `cdr' of the `car' of X."
-  (declare (compiler-macro internal--compiler-macro-cXXr))
-  (cdr (cdr (car x))))
-
-(defun cdddr (x)
-  "Return the `cdr' of the `cdr' of the `cdr' of X."
-  (declare (compiler-macro internal--compiler-macro-cXXr))
-  (cdr (cdr (cdr x))))
+;; The two- and three-level accessors are defined in Rust; keep
+;; open-coding them in byte-compiled code.
+(dolist (fun '(caar cadr cdar cddr caaar caadr cadar caddr
+               cdaar cdadr cddar cdddr))
+  (function-put fun 'compiler-macro #'internal--compiler-macro-cXXr))
 
 (defun caaaar (x)
   "Return the `car' of the `car' of the `car' of the `car' of X."
@@ -519,18 +445,6 @@ was called."
   (declare (compiler-macro internal--compiler-macro-cXXr))
   (cdr (cdr (cdr (cdr x)))))
 
-(defun last (list &optional n)
-  "Return the last link of LIST.  Its car is the last element.
-If LIST is nil, return nil.
-If N is non-nil, return the Nth-to-last link of LIST.
-If N is bigger than the length of LIST, return LIST."
-  (if n
-      (and (>= n 0)
-           (let ((m (safe-length list)))
-             (if (< n m) (nthcdr (- m n) list) list)))
-    (and list
-         (nthcdr (1- (safe-length list)) list))))
-
 (defun butlast (list &optional n)
   "Return a copy of LIST with the last N elements removed.
 If N is omitted or nil, the last element is removed from the
@@ -594,70 +508,6 @@ non-nil."
 	(setcdr last nil)))
   list)
 
-(defun number-sequence (from &optional to inc)
-  "Return a sequence of numbers from FROM to TO (both inclusive) as a list.
-INC is the increment used between numbers in the sequence and defaults to 1.
-So, the Nth element of the list is (+ FROM (* N INC)) where N counts from
-zero.  TO is only included if there is an N for which TO = FROM + N * INC.
-If TO is nil or numerically equal to FROM, return (FROM).
-If INC is positive and TO is less than FROM, or INC is negative
-and TO is larger than FROM, return nil.
-If INC is zero and TO is neither nil nor numerically equal to
-FROM, signal an error.
-
-This function is primarily designed for integer arguments.
-Nevertheless, FROM, TO and INC can be integer or float.  However,
-floating point arithmetic is inexact.  For instance, depending on
-the machine, it may quite well happen that
-\(number-sequence 0.4 0.6 0.2) returns the one element list (0.4),
-whereas (number-sequence 0.4 0.8 0.2) returns a list with three
-elements.  Thus, if some of the arguments are floats and one wants
-to make sure that TO is included, one may have to explicitly write
-TO as (+ FROM (* N INC)) or use a variable whose value was
-computed with this exact expression.  Alternatively, you can,
-of course, also replace TO with a slightly larger value
-\(or a slightly more negative value if INC is negative)."
-  (if (or (not to) (= from to))
-      (list from)
-    (or inc (setq inc 1))
-    (when (zerop inc) (error "The increment can not be zero"))
-    (let (seq (n 0) (next from) (last from))
-      (if (> inc 0)
-          ;; The (>= next last) condition protects against integer
-          ;; overflow in computing NEXT.
-          (while (and (>= next last) (<= next to))
-            (setq seq (cons next seq)
-                  n (1+ n)
-                  last next
-                  next (+ from (* n inc))))
-        (while (and (<= next last) (>= next to))
-          (setq seq (cons next seq)
-                n (1+ n)
-                next (+ from (* n inc)))))
-      (nreverse seq))))
-
-(defun copy-tree (tree &optional vecp)
-  "Make a copy of TREE.
-If TREE is a cons cell, this recursively copies both its car and its cdr.
-Contrast to `copy-sequence', which copies only along the cdrs.  With second
-argument VECP, this copies vectors as well as conses."
-  (if (consp tree)
-      (let (result)
-	(while (consp tree)
-	  (let ((newcar (car tree)))
-	    (if (or (consp (car tree)) (and vecp (vectorp (car tree))))
-		(setq newcar (copy-tree (car tree) vecp)))
-	    (push newcar result))
-	  (setq tree (cdr tree)))
-	(nconc (nreverse result)
-               (if (and vecp (vectorp tree)) (copy-tree tree vecp) tree)))
-    (if (and vecp (vectorp tree))
-	(let ((i (length (setq tree (copy-sequence tree)))))
-	  (while (>= (setq i (1- i)) 0)
-	    (aset tree i (copy-tree (aref tree i) vecp)))
-	  tree)
-      tree)))
-
 ;;;; Various list-search functions.
 
 (defun assoc-default (key alist &optional test default)
@@ -708,27 +558,6 @@ Elements of ALIST that are not conses are ignored."
 	(setq tail tail-cdr))))
   alist)
 
-(defun assq-delete-all (key alist)
-  "Delete from ALIST all elements whose car is `eq' to KEY.
-Return the modified alist.
-Elements of ALIST that are not conses are ignored."
-  (assoc-delete-all key alist #'eq))
-
-(defun rassq-delete-all (value alist)
-  "Delete from ALIST all elements whose cdr is `eq' to VALUE.
-Return the modified alist.
-Elements of ALIST that are not conses are ignored."
-  (while (and (consp (car alist))
-	      (eq (cdr (car alist)) value))
-    (setq alist (cdr alist)))
-  (let ((tail alist) tail-cdr)
-    (while (setq tail-cdr (cdr tail))
-      (if (and (consp (car tail-cdr))
-	       (eq (cdr (car tail-cdr)) value))
-	  (setcdr tail (cdr tail-cdr))
-	(setq tail tail-cdr))))
-  alist)
-
 (defun alist-get (key alist &optional default remove testfn)
   "Return the value associated with KEY in ALIST.
 If KEY is not found in ALIST, return DEFAULT.
@@ -1509,89 +1338,6 @@ be a list of the form returned by `event-start' and `event-end'."
 
 ;;;; Hook manipulation functions.
 
-(defun add-hook (hook function &optional append local)
-  "Add to the value of HOOK the function FUNCTION.
-FUNCTION is not added if already present.
-FUNCTION is added (if necessary) at the beginning of the hook list
-unless the optional argument APPEND is non-nil, in which case
-FUNCTION is added at the end.
-
-The optional fourth argument, LOCAL, if non-nil, says to modify
-the hook's buffer-local value rather than its global value.
-This makes the hook buffer-local, and it makes t a member of the
-buffer-local value.  That acts as a flag to run the hook
-functions of the global value as well as in the local value.
-
-HOOK should be a symbol, and FUNCTION may be any valid function.  If
-HOOK is void, it is first set to nil.  If HOOK's value is a single
-function, it is changed to a list of functions."
-  (or (boundp hook) (set hook nil))
-  (or (default-boundp hook) (set-default hook nil))
-  (if local (unless (local-variable-if-set-p hook)
-	      (set (make-local-variable hook) (list t)))
-    ;; Detect the case where make-local-variable was used on a hook
-    ;; and do what we used to do.
-    (unless (and (consp (symbol-value hook)) (memq t (symbol-value hook)))
-      (setq local t)))
-  (let ((hook-value (if local (symbol-value hook) (default-value hook))))
-    ;; If the hook value is a single function, turn it into a list.
-    (when (or (not (listp hook-value)) (functionp hook-value))
-      (setq hook-value (list hook-value)))
-    ;; Do the actual addition if necessary
-    (unless (member function hook-value)
-      (when (stringp function)
-	(setq function (purecopy function)))
-      (setq hook-value
-	    (if append
-		(append hook-value (list function))
-	      (cons function hook-value))))
-    ;; Set the actual variable
-    (if local
-	(progn
-	  ;; If HOOK isn't a permanent local,
-	  ;; but FUNCTION wants to survive a change of modes,
-	  ;; mark HOOK as partially permanent.
-	  (and (symbolp function)
-	       (get function 'permanent-local-hook)
-	       (not (get hook 'permanent-local))
-	       (put hook 'permanent-local 'permanent-local-hook))
-	  (set hook hook-value))
-      (set-default hook hook-value))))
-
-(defun remove-hook (hook function &optional local)
-  "Remove from the value of HOOK the function FUNCTION.
-HOOK should be a symbol, and FUNCTION may be any valid function.  If
-FUNCTION isn't the value of HOOK, or, if FUNCTION doesn't appear in the
-list of hooks to run in HOOK, then nothing is done.  See `add-hook'.
-
-The optional third argument, LOCAL, if non-nil, says to modify
-the hook's buffer-local value rather than its default value."
-  (or (boundp hook) (set hook nil))
-  (or (default-boundp hook) (set-default hook nil))
-  ;; Do nothing if LOCAL is t but this hook has no local binding.
-  (unless (and local (not (local-variable-p hook)))
-    ;; Detect the case where make-local-variable was used on a hook
-    ;; and do what we used to do.
-    (when (and (local-variable-p hook)
-	       (not (and (consp (symbol-value hook))
-			 (memq t (symbol-value hook)))))
-      (setq local t))
-    (let ((hook-value (if local (symbol-value hook) (default-value hook))))
-      ;; Remove the function, for both the list and the non-list cases.
-      (if (or (not (listp hook-value)) (eq (car hook-value) 'lambda))
-	  (if (equal hook-value function) (setq hook-value nil))
-	(setq hook-value (delete function (copy-sequence hook-value))))
-      ;; If the function is on the global hook, we need to shadow it locally
-      ;;(when (and local (member function (default-value hook))
-      ;;	       (not (member (cons 'not function) hook-value)))
-      ;;  (push (cons 'not function) hook-value))
-      ;; Set the actual variable
-      (if (not local)
-	  (set-default hook hook-value)
-	(if (equal hook-value '(t))
-	    (kill-local-variable hook)
-	  (set hook hook-value))))))
-
 (defmacro letrec (binders &rest body)
   "Bind variables according to BINDERS then eval BODY.
 The value of the last form in BODY is returned.
@@ -1670,84 +1416,48 @@ FUN is then called once."
                           (default-value ',hook)))
                 (list ,@args)))))
 
-(defun add-to-list (list-var element &optional append compare-fn)
-  "Add ELEMENT to the value of LIST-VAR if it isn't there yet.
-The test for presence of ELEMENT is done with `equal', or with
-COMPARE-FN if that's non-nil.
-If ELEMENT is added, it is added at the beginning of the list,
-unless the optional argument APPEND is non-nil, in which case
-ELEMENT is added at the end.
-
-The return value is the new value of LIST-VAR.
-
-This is handy to add some elements to configuration variables,
-but please do not abuse it in Elisp code, where you are usually
-better off using `push' or `cl-pushnew'.
-
-If you want to use `add-to-list' on a variable that is not
-defined until a certain package is loaded, you should put the
-call to `add-to-list' into a hook function that will be run only
-after loading the package.  `eval-after-load' provides one way to
-do this.  In some cases other hooks, such as major mode hooks,
-can do the job."
-  (declare
-   (compiler-macro
-    (lambda (exp)
-      ;; FIXME: Something like this could be used for `set' as well.
-      (if (or (not (eq 'quote (car-safe list-var)))
-              (special-variable-p (cadr list-var))
-              (not (macroexp-const-p append)))
-          exp
-        (let* ((sym (cadr list-var))
-               (append (eval append))
-               (msg (format-message
-                     "`add-to-list' can't use lexical var `%s'; use `push' or `cl-pushnew'"
-                     sym))
-               ;; Big ugly hack so we only output a warning during
-               ;; byte-compilation, and so we can use
-               ;; byte-compile-not-lexical-var-p to silence the warning
-               ;; when a defvar has been seen but not yet executed.
-               (warnfun (lambda ()
-                          ;; FIXME: We should also emit a warning for let-bound
-                          ;; variables with dynamic binding.
-                          (when (assq sym byte-compile--lexical-environment)
-                            (byte-compile-report-error msg :fill))))
-               (code
-                (macroexp-let2 macroexp-copyable-p x element
-                  `(if ,(if compare-fn
-                            (progn
-                              (require 'cl-lib)
-                              `(cl-member ,x ,sym :test ,compare-fn))
-                          ;; For bootstrapping reasons, don't rely on
-                          ;; cl--compiler-macro-member for the base case.
-                          `(member ,x ,sym))
-                       ,sym
-                     ,(if append
-                          `(setq ,sym (append ,sym (list ,x)))
-                        `(push ,x ,sym))))))
-          (if (not (macroexp--compiling-p))
-              code
-            `(progn
-               (macroexp--funcall-if-compiled ',warnfun)
-               ,code)))))))
-  (if (cond
-       ((null compare-fn)
-	(member element (symbol-value list-var)))
-       ((eq compare-fn 'eq)
-	(memq element (symbol-value list-var)))
-       ((eq compare-fn 'eql)
-	(memql element (symbol-value list-var)))
-       (t
-	(let ((lst (symbol-value list-var)))
-	  (while (and lst
-		      (not (funcall compare-fn element (car lst))))
-	    (setq lst (cdr lst)))
-          lst)))
-      (symbol-value list-var)
-    (set list-var
-	 (if append
-	     (append (symbol-value list-var) (list element))
-	   (cons element (symbol-value list-var))))))
+;; `add-to-list' is defined in Rust; this compiler macro warns about
+;; lexical variables and open-codes the simple cases.
+(function-put
+ 'add-to-list 'compiler-macro
+ #'(lambda (exp list-var element &optional append compare-fn)
+     ;; FIXME: Something like this could be used for `set' as well.
+     (if (or (not (eq 'quote (car-safe list-var)))
+             (special-variable-p (cadr list-var))
+             (not (macroexp-const-p append)))
+         exp
+       (let* ((sym (cadr list-var))
+              (append (eval append))
+              (msg (format-message
+                    "`add-to-list' can't use lexical var `%s'; use `push' or `cl-pushnew'"
+                    sym))
+              ;; Big ugly hack so we only output a warning during
+              ;; byte-compilation, and so we can use
+              ;; byte-compile-not-lexical-var-p to silence the warning
+              ;; when a defvar has been seen but not yet executed.
+              (warnfun (lambda ()
+                         ;; FIXME: We should also emit a warning for let-bound
+                         ;; variables with dynamic binding.
+                         (when (assq sym byte-compile--lexical-environment)
+                           (byte-compile-report-error msg :fill))))
+              (code
+               (macroexp-let2 macroexp-copyable-p x element
+                 `(if ,(if compare-fn
+                           (progn
+                             (require 'cl-lib)
+                             `(cl-member ,x ,sym :test ,compare-fn))
+                         ;; For bootstrapping reasons, don't rely on
+                         ;; cl--compiler-macro-member for the base case.
+                         `(member ,x ,sym))
+                      ,sym
+                    ,(if append
+                         `(setq ,sym (append ,sym (list ,x)))
+                       `(push ,x ,sym))))))
+         (if (not (macroexp--compiling-p))
+             code
+           `(progn
+              (macroexp--funcall-if-compiled ',warnfun)
+              ,code))))))
 
 
 (defun add-to-ordered-list (list-var element &optional order)
@@ -1784,39 +1494,6 @@ The return value is the new value of LIST-VAR."
 				(< oa ob)
 			      oa)))))))
 
-(defun add-to-history (history-var newelt &optional maxelt keep-all)
-  "Add NEWELT to the history list stored in the variable HISTORY-VAR.
-Return the new history list.
-If MAXELT is non-nil, it specifies the maximum length of the history.
-Otherwise, the maximum history length is the value of the `history-length'
-property on symbol HISTORY-VAR, if set, or the value of the `history-length'
-variable.  The possible values of maximum length have the same meaning as
-the values of `history-length'.
-Remove duplicates of NEWELT if `history-delete-duplicates' is non-nil.
-If optional fourth arg KEEP-ALL is non-nil, add NEWELT to history even
-if it is empty or a duplicate."
-  (unless maxelt
-    (setq maxelt (or (get history-var 'history-length)
-		     history-length)))
-  (let ((history (symbol-value history-var))
-	tail)
-    (when (and (listp history)
-	       (or keep-all
-		   (not (stringp newelt))
-		   (> (length newelt) 0))
-	       (or keep-all
-		   (not (equal (car history) newelt))))
-      (if history-delete-duplicates
-	  (setq history (delete newelt history)))
-      (setq history (cons newelt history))
-      (when (integerp maxelt)
-	(if (= 0 maxelt)
-	    (setq history nil)
-	  (setq tail (nthcdr (1- maxelt) history))
-	  (when (consp tail)
-	    (setcdr tail nil)))))
-    (set history-var history)))
-
 
 ;;;; Mode hooks.
 
@@ -3953,24 +3630,6 @@ and replace a sub-expression, e.g.
       (setq matches (cons (substring string start l) matches)) ; leftover
       (apply #'concat (nreverse matches)))))
 
-(defun string-prefix-p (prefix string &optional ignore-case)
-  "Return non-nil if PREFIX is a prefix of STRING.
-If IGNORE-CASE is non-nil, the comparison is done without paying attention
-to case differences."
-  (let ((prefix-length (length prefix)))
-    (if (> prefix-length (length string)) nil
-      (eq t (compare-strings prefix 0 prefix-length string
-			     0 prefix-length ignore-case)))))
-
-(defun string-suffix-p (suffix string  &optional ignore-case)
-  "Return non-nil if SUFFIX is a suffix of STRING.
-If IGNORE-CASE is non-nil, the comparison is done without paying
-attention to case differences."
-  (let ((start-pos (- (length string) (length suffix))))
-    (and (>= start-pos 0)
-         (eq t (compare-strings suffix nil nil
-                                string start-pos nil ignore-case)))))
-
 (defun bidi-string-mark-left-to-right (str)
   "Return a string that can be safely inserted in left-to-right text.
 
@@ -4252,15 +3911,6 @@ from `standard-syntax-table' otherwise."
     (set-char-table-parent table (or oldtable (standard-syntax-table)))
     table))
 
-(defun syntax-after (pos)
-  "Return the raw syntax descriptor for the char after POS.
-If POS is outside the buffer's accessible portion, return nil."
-  (unless (or (< pos (point-min)) (>= pos (point-max)))
-    (let ((st (if parse-sexp-lookup-properties
-		  (get-char-property pos 'syntax-table))))
-      (if (consp st) st
-	(aref (or st (syntax-table)) (char-after pos))))))
-
 (defun syntax-class (syntax)
   "Return the code for the syntax class described by SYNTAX.
 
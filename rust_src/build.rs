@@ -2,6 +2,7 @@
 extern crate lazy_static;
 extern crate libc;
 extern crate regex;
+extern crate remacs_lib;
 
 use std::env;
 use std::ffi::OsStr;
@@ -15,6 +16,8 @@ use std::process;
 
 use regex::Regex;
 
+use remacs_lib::parse_lisp_variable;
+
 static C_NAME: &str = "c_name = \"";
 
 /// Exit with error $code after printing the $fmtstr to stderr
@@ -96,6 +99,7 @@ struct ModuleData {
     pub c_exports: Vec<(Option<String>, String)>,
     pub lisp_fns: Vec<(Option<String>, String)>,
     pub protected_statics: Vec<String>,
+    pub lisp_variables: Vec<(String, String, String)>,
 }
 
 impl ModuleData {
@@ -105,6 +109,7 @@ impl ModuleData {
             c_exports: Vec::new(),
             lisp_fns: Vec::new(),
             protected_statics: Vec::new(),
+            lisp_variables: Vec::new(),
         }
     }
 }
@@ -139,6 +144,9 @@ impl<'a> ModuleParser<'a> {
             if line.starts_with("declare_GC_protected_static!") {
                 let var = self.parse_gc_protected_static(&line)?;
                 mod_data.protected_statics.push(var);
+            } else if line.starts_with("declare_lisp_variable!") {
+                let var = self.parse_lisp_variable(&line)?;
+                mod_data.lisp_variables.push(var);
             } else if line.starts_with("#[no_mangle]") {
                 if let Some(next) = reader.next() {
                     let line = next?;
@@ -215,10 +223,13 @@ impl<'a> ModuleParser<'a> {
             }
         }
 
-        if !(has_include || (mod_data.lisp_fns.is_empty() && mod_data.protected_statics.is_empty()))
+        if !(has_include
+            || (mod_data.lisp_fns.is_empty()
+                && mod_data.protected_statics.is_empty()
+                && mod_data.lisp_variables.is_empty()))
         {
             let msg = format!(
-                "{} is missing the required include for protected statics or lisp_fn exports.",
+                "{} is missing the required include for protected statics, lisp variables or lisp_fn exports.",
                 path_as_str(self.info.path.file_name()).to_string()
             );
 
@@ -264,6 +275,17 @@ impl<'a> ModuleParser<'a> {
         }
     }
 
+    fn parse_lisp_variable(&mut self, line: &str) -> Result<(String, String, String), LintMsg> {
+        match parse_lisp_variable(line) {
+            Some(var) => Ok(var),
+            None => Err(LintMsg::new(
+                &self.info.name,
+                self.lineno,
+                "could not parse lisp variable".to_string(),
+            )),
+        }
+    }
+
     // Determine if a function is exported correctly and return that function's name or None.
     fn validate_exported_function(
         &mut self,
@@ -448,7 +470,7 @@ fn generate_include_files() -> Result<(), BuildError> {
                 .create(true)
                 .write(true)
                 .append(true)
-                .open(exports_path)?;
+                .open(&exports_path)?;
             writeln!(
                 file,
                 "protect_statics_from_GC! {{ {} }}",
@@ -457,6 +479,30 @@ fn generate_include_files() -> Result<(), BuildError> {
 
             writeln!(out_file, "    {}::rust_static_syms();", mod_data.info.name)?;
         }
+
+        if !mod_data.lisp_variables.is_empty() {
+            let mut file = OpenOptions::new()
+                .create(true)
+                .write(true)
+                .append(true)
+                .open(&exports_path)?;
+            writeln!(
+                file,
+                "export_lisp_variables! {{\n    {}\n}}",
+                mod_data
+                    .lisp_variables
+                    .iter()
+                    .map(|(var, lisp_name, value)| format!("{}, \"{}\", {}", var, lisp_name, value))
+                    .collect::<Vec<String>>()
+                    .join(";\n    ")
+            )?;
+
+            writeln!(
+                out_file,
+                "    {}::rust_variable_syms();",
+                mod_data.info.name
+            )?;
+        }
     }
 
     // Add this one by hand.
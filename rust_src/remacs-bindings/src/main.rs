@@ -285,6 +285,8 @@ fn run_bindgen(path: &str) {
                 .rustified_enum("Lisp_Misc_Type")
                 .rustified_enum("Lisp_Type")
                 .rustified_enum("case_action")
+                .rustified_enum("charset_attr_index")
+                .rustified_enum("charset_method")
                 .rustified_enum("face_id")
                 .rustified_enum("output_method")
                 .rustified_enum("pvec_type")
@@ -169,6 +169,15 @@ pub unsafe extern "C" fn scan_rust_file(
             let name = CString::new(&caps[1]).unwrap();
             let value = CString::new(&caps[2]).unwrap();
             add_global(SYMBOL, name.as_ptr(), 0, value.as_ptr());
+        } else if line.starts_with("declare_lisp_variable!(") {
+            // declare_lisp_variable!(Vfoo, "foo", Qnil);
+            // These live in Rust statics rather than in globals, so only
+            // the documentation is emitted.
+            if generate_globals == 0 {
+                if let Some((_, lisp_name, _)) = parse_lisp_variable(line) {
+                    print!("\x1fV{}\n{}", lisp_name, docstring)
+                }
+            }
         } else if line.starts_with("defvar_") {
             // defvar_lisp!(f_Vpost_self_insert_hook, "post-self-insert-hook", Qnil);
             // defvar_kboard!(Vlast_command_, "last-command");
@@ -204,3 +213,61 @@ pub unsafe extern "C" fn scan_rust_file(
     }
     stdout().flush().unwrap();
 }
+
+/// Parse a `declare_lisp_variable!(var, "lisp-name", value);` line into
+/// the name of its static, its Lisp name and its initial value.
+pub fn parse_lisp_variable(line: &str) -> Option<(String, String, String)> {
+    lazy_static! {
+        static ref RE: Regex =
+            Regex::new(r#"^declare_lisp_variable!\((\w+),\s*"(.+?)",\s*(.+)\);"#).unwrap();
+    }
+
+    RE.captures(line).map(|caps| {
+        (
+            caps[1].to_string(),
+            caps[2].to_string(),
+            caps[3].to_string(),
+        )
+    })
+}
+
+#[test]
+fn test_parse_lisp_variable() {
+    let module = r#"
+use crate::lisp::LispObject;
+
+/// Non-nil means frobnicate.
+declare_lisp_variable!(Vfrobnicate, "frobnicate", Qnil);
+
+declare_GC_protected_static!(scratch, Qnil);
+
+/// How many times to frobnicate.
+declare_lisp_variable!(Vfrobnicate_count, "frobnicate-count", LispObject::from(3));
+"#;
+
+    let vars: Vec<_> = module.lines().filter_map(parse_lisp_variable).collect();
+    assert_eq!(
+        vars,
+        vec![
+            (
+                "Vfrobnicate".to_string(),
+                "frobnicate".to_string(),
+                "Qnil".to_string()
+            ),
+            (
+                "Vfrobnicate_count".to_string(),
+                "frobnicate-count".to_string(),
+                "LispObject::from(3)".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_lisp_variable_malformed() {
+    assert_eq!(parse_lisp_variable("declare_lisp_variable!(Vfoo);"), None);
+    assert_eq!(
+        parse_lisp_variable("    declare_lisp_variable!(Vfoo, \"foo\", Qnil);"),
+        None
+    );
+}
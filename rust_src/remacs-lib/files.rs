@@ -69,7 +69,12 @@ fn generate_temporary_filename(name: &mut String) {
     assert!(len >= 6);
     let name_vec = unsafe { &mut name.as_mut_vec() };
 
-    let bytes = &mut name_vec[len - 6..len];
+    fill_random_name(&mut name_vec[len - 6..len]);
+}
+
+/// Overwrite BYTES with random ASCII letters and digits, as used for
+/// the unique part of temporary file names.
+pub fn fill_random_name(bytes: &mut [u8]) {
     {
         let mut rng = OsRng::new().unwrap();
         rng.fill_bytes(bytes);
@@ -19,10 +19,12 @@ mod math;
 mod time;
 
 pub use crate::{
+    // Used by build.rs to register Lisp variables declared in Rust
+    docfile::parse_lisp_variable,
     // Used by make-docfile
     docfile::scan_rust_file,
     // Used for creating temporary files in emacs
-    files::rust_make_temp,
+    files::{fill_random_name, rust_make_temp},
 
     math::{rust_count_one_bits, rust_count_trailing_zero_bits},
 
@@ -28,6 +28,37 @@ pub fn lisp_fn(attr_ts: TokenStream, fn_ts: TokenStream) -> TokenStream {
         Err(e) => panic!("Invalid lisp_fn attribute: {}", e),
     };
 
+    if lisp_fn_args.min < 0 {
+        panic!(
+            "Invalid lisp_fn attribute on `{}`: min = {} is negative",
+            function.name, lisp_fn_args.min
+        );
+    }
+    // Unevalled functions receive their whole argument list as a single
+    // object, so `min` describes the Lisp form rather than the Rust
+    // signature.
+    match function.fntype {
+        function::LispFnType::Normal(nargs) => {
+            if !lisp_fn_args.unevalled && lisp_fn_args.min > nargs {
+                panic!(
+                    "Invalid lisp_fn attribute on `{}`: min = {} but the function only takes {} argument(s)",
+                    function.name, lisp_fn_args.min, nargs
+                );
+            }
+        }
+        // A `&mut [LispObject]` function is called with any number of
+        // arguments, which cannot be squared with the single argument list
+        // an unevalled function receives.
+        function::LispFnType::Many => {
+            if lisp_fn_args.unevalled {
+                panic!(
+                    "Invalid lisp_fn attribute on `{}`: unevalled = \"true\" conflicts with taking `&mut [LispObject]`",
+                    function.name
+                );
+            }
+        }
+    }
+
     let mut cargs = proc_macro2::TokenStream::new();
     let mut rargs = proc_macro2::TokenStream::new();
     let mut body = proc_macro2::TokenStream::new();
@@ -20,7 +20,10 @@ use crate::{
     fns::{copy_sequence, nconc, nreverse},
     frame::LispFrameRef,
     hashtable::LispHashTableRef,
-    lisp::{ExternalPtr, LispMiscRef, LispObject, LispStructuralEqual, LiveBufferIter},
+    lisp::{
+        ExternalPtr, LispMiscRef, LispObject, LispStructuralEqual, LispTypeError, LiveBufferIter,
+        TryFromLisp,
+    },
     lists,
     lists::{car, cdr, list, member, rassq, setcar},
     lists::{CarIter, LispCons, LispConsCircularChecks, LispConsEndChecks, TailsIter},
@@ -47,7 +50,7 @@ use crate::{
     },
     remacs_sys::{
         buffer_defaults, equal_kind, pvec_type, EmacsInt, Lisp_Buffer, Lisp_Buffer_Local_Value,
-        Lisp_Misc_Type, Lisp_Overlay, Lisp_Type, Vbuffer_alist, Vrun_hooks,
+        Lisp_Interval, Lisp_Misc_Type, Lisp_Overlay, Lisp_Type, Vbuffer_alist, Vrun_hooks,
     },
     remacs_sys::{
         buffer_permanent_local_flags, Qafter_string, Qbefore_string, Qbuffer_list_update_hook,
@@ -338,6 +341,33 @@ impl LispBufferRef {
         unsafe { self.beg_addr().offset(offset + n - self.beg_byte()) }
     }
 
+    /// Return the raw bytes between START_BYTE and END_BYTE as two
+    /// slices, the parts before and after the gap.  Either may be empty.
+    pub fn text_slices<'a>(
+        self,
+        start_byte: ptrdiff_t,
+        end_byte: ptrdiff_t,
+    ) -> (&'a [u8], &'a [u8]) {
+        debug_assert!(self.beg_byte() <= start_byte && start_byte <= end_byte);
+        debug_assert!(end_byte <= self.z_byte());
+        let gpt_byte = self.gpt_byte();
+        let before_gap: &[u8] = if start_byte < gpt_byte {
+            let end = end_byte.min(gpt_byte);
+            unsafe {
+                slice::from_raw_parts(self.byte_pos_addr(start_byte), (end - start_byte) as usize)
+            }
+        } else {
+            &[]
+        };
+        let after_gap: &[u8] = if gpt_byte < end_byte {
+            let start = start_byte.max(gpt_byte);
+            unsafe { slice::from_raw_parts(self.byte_pos_addr(start), (end_byte - start) as usize) }
+        } else {
+            &[]
+        };
+        (before_gap, after_gap)
+    }
+
     /// Return the address of character at byte position BYTE_POS.
     pub fn buf_byte_address(self, byte_pos: isize) -> c_uchar {
         let gap = self.pos_within_range(byte_pos);
@@ -390,6 +420,18 @@ impl LispBufferRef {
         }
     }
 
+    /// Iterate over the characters from POS / POS_BYTE to the end of
+    /// the accessible region, yielding `(charpos, codepoint)` pairs.
+    pub fn chars_from(self, pos: isize, pos_byte: isize) -> BufferCharIter {
+        BufferCharIter::new(self, (pos, pos_byte), (self.zv, self.zv_byte))
+    }
+
+    /// Iterate backwards over the characters from the one before
+    /// POS / POS_BYTE to the beginning of the accessible region.
+    pub fn chars_rev_from(self, pos: isize, pos_byte: isize) -> iter::Rev<BufferCharIter> {
+        BufferCharIter::new(self, (self.begv, self.begv_byte), (pos, pos_byte)).rev()
+    }
+
     // Same as the BUF_INC_POS c macro
     /// Increment the buffer byte position POS_BYTE of the the buffer to
     /// the next character boundary.  This macro relies on the fact that
@@ -472,6 +514,10 @@ impl LispBufferRef {
         unsafe { (*self.text).z }
     }
 
+    pub fn intervals(self) -> *mut Lisp_Interval {
+        unsafe { (*self.text).intervals }
+    }
+
     pub fn bytepos_to_charpos(mut self, bytepos: isize) -> isize {
         assert!(self.beg_byte() <= bytepos && bytepos <= self.z_byte());
 
@@ -892,6 +938,63 @@ impl LispBufferRef {
     }
 }
 
+/// Iterator over the characters of a buffer between two positions,
+/// each given as a `(charpos, bytepos)` pair.  Handles the gap and
+/// unibyte buffers.
+pub struct BufferCharIter {
+    buffer: LispBufferRef,
+    charpos: isize,
+    bytepos: isize,
+    end_charpos: isize,
+    end_bytepos: isize,
+}
+
+impl BufferCharIter {
+    fn new(buffer: LispBufferRef, from: (isize, isize), to: (isize, isize)) -> Self {
+        debug_assert!(buffer.begv <= from.0 && from.0 <= to.0 && to.0 <= buffer.zv);
+        Self {
+            buffer,
+            charpos: from.0,
+            bytepos: from.1,
+            end_charpos: to.0,
+            end_bytepos: to.1,
+        }
+    }
+}
+
+impl Iterator for BufferCharIter {
+    type Item = (isize, Codepoint);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.charpos >= self.end_charpos {
+            return None;
+        }
+        let item = (self.charpos, self.buffer.fetch_char(self.bytepos));
+        self.charpos += 1;
+        self.bytepos = if self.buffer.multibyte_characters_enabled() {
+            self.buffer.inc_pos(self.bytepos)
+        } else {
+            self.bytepos + 1
+        };
+        Some(item)
+    }
+}
+
+impl DoubleEndedIterator for BufferCharIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.charpos >= self.end_charpos {
+            return None;
+        }
+        self.end_charpos -= 1;
+        self.end_bytepos = if self.buffer.multibyte_characters_enabled() {
+            self.buffer.dec_pos(self.end_bytepos)
+        } else {
+            self.end_bytepos - 1
+        };
+        Some((self.end_charpos, self.buffer.fetch_char(self.end_bytepos)))
+    }
+}
+
 impl LispObject {
     pub fn is_buffer(self) -> bool {
         self.as_vectorlike()
@@ -913,6 +1016,12 @@ impl From<LispObject> for LispBufferRef {
     }
 }
 
+impl TryFromLisp for LispBufferRef {
+    fn try_from_lisp(o: LispObject) -> Result<Self, LispTypeError> {
+        o.as_buffer().ok_or_else(|| LispTypeError::new(Qbufferp, o))
+    }
+}
+
 impl From<LispBufferRef> for LispObject {
     fn from(b: LispBufferRef) -> Self {
         Self::tag_ptr(b, Lisp_Type::Lisp_Vectorlike)
@@ -5,9 +5,13 @@ use libc::{c_uchar, ptrdiff_t};
 use remacs_macros::lisp_fn;
 
 use crate::{
+    casefiddle::upcase,
+    fns::compare_strings,
+    hashtable::{gethash, LispHashTableRef},
     lisp::LispObject,
-    multibyte::{char_resolve_modifier_mask, Codepoint, MAX_CHAR},
-    remacs_sys::EmacsInt,
+    multibyte::{char_resolve_modifier_mask, Codepoint, LispStringRef, MAX_CHAR},
+    obarray::intern,
+    remacs_sys::{EmacsInt, EmacsUint, Qnil, Qt},
     threads::ThreadState,
 };
 
@@ -92,6 +96,57 @@ pub fn multibyte_char_to_unibyte(ch: Codepoint) -> EmacsInt {
     }
 }
 
+/// Return a byte value of a character at point.
+/// Optional 1st arg POSITION, if non-nil, is a position of a character to get
+/// a byte value.
+/// Optional 2nd arg STRING, if non-nil, is a string of which first
+/// character is a target to get a byte value.  In this case, POSITION, if
+/// non-nil, is an index of a target character in the string.
+///
+/// If the current buffer (or STRING) is multibyte, and the target
+/// character is not ASCII nor 8-bit character, an error is signaled.
+#[lisp_fn(min = "0")]
+pub fn get_byte(position: LispObject, string: LispObject) -> EmacsInt {
+    let c = if string.is_nil() {
+        let buffer = ThreadState::current_buffer_unchecked();
+        let pos_byte = if position.is_nil() {
+            buffer.pt_byte
+        } else {
+            let pos = position.as_fixnum_coerce_marker_or_error() as ptrdiff_t;
+            if pos < buffer.begv || pos >= buffer.zv {
+                args_out_of_range!(position, buffer.begv, buffer.zv);
+            }
+            buffer.charpos_to_bytepos(pos)
+        };
+        if !buffer.multibyte_characters_enabled() {
+            return buffer.fetch_byte(pos_byte).into();
+        }
+        buffer.fetch_multibyte_char(pos_byte)
+    } else {
+        let s: LispStringRef = string.into();
+        let pos = if position.is_nil() {
+            0
+        } else {
+            let pos = position.as_natnum_or_error();
+            if pos >= s.len_chars() as EmacsUint {
+                args_out_of_range!(string, position);
+            }
+            pos as usize
+        };
+        // An empty string has only its terminating NUL byte.
+        let c = s.chars().nth(pos).unwrap_or_else(|| Codepoint::from(0_u8));
+        if !s.is_multibyte() {
+            return c.into();
+        }
+        c
+    };
+
+    c.to_byte8().map_or_else(
+        || error!("Not an ASCII nor an 8-bit character: {}", u32::from(c)),
+        EmacsInt::from,
+    )
+}
+
 /// Resolve modifiers in the character CHAR.
 /// The value is a character with modifiers resolved into the character
 /// code.  Unresolved modifiers are kept in the value.
@@ -101,4 +156,88 @@ pub fn char_resolve_modifiers(character: LispObject) -> EmacsInt {
     char_resolve_modifier_mask(character.into())
 }
 
+/// Return a character as a number from its Unicode name STRING.
+/// If optional IGNORE-CASE is non-nil, ignore case in STRING.
+/// STRING may also have the form "U+XXXX", naming a code point in hex.
+/// Return nil if STRING does not name a character.
+#[lisp_fn(min = "1")]
+pub fn char_from_name(string: LispStringRef, ignore_case: bool) -> Option<EmacsInt> {
+    let key = if ignore_case {
+        upcase(string.into())
+    } else {
+        string.into()
+    };
+    let names: LispHashTableRef = call!(intern("ucs-names").into()).into();
+    if let Some(c) = gethash(key, names, Qnil).as_fixnum() {
+        return Some(c);
+    }
+
+    let bytes = string.as_slice();
+    if bytes.len() > 2
+        && bytes[..2].eq_ignore_ascii_case(b"U+")
+        && bytes[2..].iter().all(u8::is_ascii_hexdigit)
+    {
+        return parse_code_point(&bytes[2..], 16).filter(|&c| c <= EmacsInt::from(MAX_CHAR));
+    }
+
+    char_from_numbered_name(string, ignore_case)
+}
+
+/// Parse the leading digits of BYTES in RADIX, like `string-to-number'.
+fn parse_code_point(bytes: &[u8], radix: u32) -> Option<EmacsInt> {
+    let mut num: EmacsInt = 0;
+    for &b in bytes {
+        match (b as char).to_digit(radix) {
+            Some(d) => {
+                num = num
+                    .checked_mul(EmacsInt::from(radix))?
+                    .checked_add(EmacsInt::from(d))?
+            }
+            None => break,
+        }
+    }
+    Some(num)
+}
+
+/// Parse names like "VARIATION SELECTOR-17" and "CJK COMPATIBILITY
+/// IDEOGRAPH-F900" that are not in `ucs-names'.
+fn char_from_numbered_name(string: LispStringRef, ignore_case: bool) -> Option<EmacsInt> {
+    let bytes = string.as_slice();
+    let minus = bytes.iter().rposition(|&b| b == b'-')?;
+    let digits = &bytes[minus + 1..];
+    if digits.is_empty() || !digits.iter().all(u8::is_ascii_hexdigit) {
+        return None;
+    }
+
+    let prefix = b"VARIATION SELECTOR-";
+    let vs = bytes.len() >= prefix.len()
+        && if ignore_case {
+            bytes[..prefix.len()].eq_ignore_ascii_case(prefix)
+        } else {
+            bytes.starts_with(prefix)
+        };
+    let num = parse_code_point(digits, if vs { 10 } else { 16 })?;
+    let vs_offset = match (vs, num > 16) {
+        (false, _) => 0,
+        (true, true) => 0xE00EF,
+        (true, false) => 0xFDFF,
+    };
+    let code = vs_offset + num;
+    if code > EmacsInt::from(MAX_CHAR) {
+        return None;
+    }
+
+    let name = call!(
+        intern("get-char-code-property").into(),
+        code.into(),
+        intern("name").into()
+    )
+    .as_string()?;
+    if compare_strings(string, None, None, name, None, None, ignore_case).eq(Qt) {
+        Some(code)
+    } else {
+        None
+    }
+}
+
 include!(concat!(env!("OUT_DIR"), "/character_exports.rs"));
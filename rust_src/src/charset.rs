@@ -6,13 +6,98 @@ use remacs_macros::lisp_fn;
 
 use crate::{
     hashtable::{HashLookupResult, LispHashTableRef},
-    lisp::LispObject,
-    remacs_sys::temp_charset_work,
-    remacs_sys::Qnil,
+    lisp::{ExternalPtr, LispError, LispObject},
+    lists::{LispConsCircularChecks, LispConsEndChecks},
+    multibyte::Codepoint,
+    remacs_sys::{char_charset as c_char_charset, coding_system_charset_list},
+    remacs_sys::{charset, charset_table, cons_to_unsigned},
+    remacs_sys::{charset_ascii, temp_charset_work},
+    remacs_sys::{charset_attr_index, EmacsInt},
+    remacs_sys::{decode_char as c_decode_char, encode_char as c_encode_char},
     remacs_sys::{xfree, Foptimize_char_table},
+    remacs_sys::{Qargs_out_of_range, Qcharsetp, Qerror, Qnil},
     remacs_sys::{Vchar_unify_table, Vcharset_hash_table},
 };
 
+pub type LispCharsetRef = ExternalPtr<charset>;
+
+impl LispCharsetRef {
+    /// Equivalent to CHARSET_FROM_ID.
+    pub fn from_id(id: isize) -> Self {
+        unsafe { Self::new(charset_table.offset(id)) }
+    }
+
+    /// Return the charset of highest priority that contains C.
+    /// Equivalent to CHAR_CHARSET.
+    pub fn for_char(c: Codepoint) -> Self {
+        if c.is_ascii() {
+            Self::from_id(unsafe { charset_ascii } as isize)
+        } else {
+            let charset = unsafe { c_char_charset(u32::from(c) as i32, Qnil, ptr::null_mut()) };
+            if charset.is_null() {
+                panic!("No charset contains {:?}", c);
+            }
+            Self::new(charset)
+        }
+    }
+
+    /// Equivalent to CHARSET_ATTRIBUTES.
+    pub fn attributes(self) -> LispObject {
+        let h_ref: LispHashTableRef = unsafe { Vcharset_hash_table }.into();
+        h_ref.get_hash_value(self.hash_index)
+    }
+
+    pub fn attribute(self, idx: charset_attr_index) -> LispObject {
+        self.attributes().force_vector().get(idx as usize)
+    }
+
+    pub fn name(self) -> LispObject {
+        self.attribute(charset_attr_index::charset_name)
+    }
+
+    /// Return the code point of C in this charset, or `None` if C
+    /// does not belong to it.  Equivalent to ENCODE_CHAR.
+    pub fn encode_char(self, c: Codepoint) -> Option<u32> {
+        let code = unsafe { c_encode_char(self.as_ptr() as *mut charset, u32::from(c) as i32) };
+        if code == self.invalid_code {
+            None
+        } else {
+            Some(code)
+        }
+    }
+
+    /// Return the character whose code point in this charset is CODE,
+    /// or `None` if CODE is invalid.  Equivalent to DECODE_CHAR.
+    pub fn decode_char(self, code: u32) -> Option<Codepoint> {
+        let c = unsafe { c_decode_char(self.as_ptr() as *mut charset, code) };
+        if c < 0 {
+            None
+        } else {
+            Some(Codepoint::from(c as u32))
+        }
+    }
+}
+
+impl From<LispObject> for LispCharsetRef {
+    fn from(o: LispObject) -> Self {
+        let h_ref: LispHashTableRef = unsafe { Vcharset_hash_table }.into();
+        match h_ref.lookup(o) {
+            HashLookupResult::Found(idx) if o.is_symbol() => {
+                let attrs = h_ref.get_hash_value(idx).force_vector();
+                let id = attrs.get(charset_attr_index::charset_id as usize);
+                Self::from_id(id.as_fixnum_or_error() as isize)
+            }
+            _ => wrong_type!(Qcharsetp, o),
+        }
+    }
+}
+
+impl From<LispCharsetRef> for LispObject {
+    fn from(c: LispCharsetRef) -> Self {
+        c.name()
+    }
+}
+
 impl LispObject {
     pub fn is_charset(self) -> bool {
         let h_ref: LispHashTableRef = unsafe { Vcharset_hash_table }.into();
@@ -46,4 +131,122 @@ pub fn charsetp(object: LispObject) -> bool {
     object.is_charset()
 }
 
+/// Decode the pair of CHARSET and CODE-POINT into a character.
+/// Return nil if CODE-POINT is not valid in CHARSET.
+///
+/// CODE-POINT may be a cons (HIGHER-16-BIT-VALUE . LOWER-16-BIT-VALUE).
+#[lisp_fn]
+pub fn decode_char(charset: LispCharsetRef, code_point: LispObject) -> Option<Codepoint> {
+    let code = unsafe { cons_to_unsigned(code_point, u32::max_value().into()) };
+    charset.decode_char(code as u32)
+}
+
+/// Encode the character CH into a code-point of CHARSET.
+/// Return nil if CHARSET doesn't include CH.
+#[lisp_fn]
+pub fn encode_char(ch: LispObject, charset: LispCharsetRef) -> Option<u32> {
+    charset.encode_char(ch.into())
+}
+
+/// Return a character of CHARSET whose position codes are CODEn.
+///
+/// CODE1 through CODE4 are optional, but if you don't supply sufficient
+/// position codes, it is assumed that the minimum code in each dimension
+/// is specified.
+#[lisp_fn(min = "1")]
+pub fn make_char(
+    charset: LispCharsetRef,
+    code1: LispObject,
+    code2: LispObject,
+    code3: LispObject,
+    code4: LispObject,
+) -> Result<Codepoint, LispError> {
+    let dimension = charset.dimension as usize;
+
+    let mut code = if code1.is_nil() {
+        if charset.ascii_compatible_p() {
+            0
+        } else {
+            charset.min_code
+        }
+    } else {
+        let mut code = position_code(code1)?;
+        for (i, &code_n) in [code2, code3, code4].iter().enumerate().take(dimension - 1) {
+            code <<= 8;
+            code |= if code_n.is_nil() {
+                charset.code_space[(dimension - i - 2) * 4] as u32
+            } else {
+                position_code(code_n)?
+            };
+        }
+        code
+    };
+
+    if charset.iso_final >= 0 {
+        code &= 0x7F7F_7F7F;
+    }
+    charset
+        .decode_char(code)
+        .ok_or_else(|| LispError::new(Qerror, list!("Invalid code(s)")))
+}
+
+/// Check that CODE is a valid position code, i.e. a single byte.
+fn position_code(code: LispObject) -> Result<u32, LispError> {
+    let n = code.as_natnum_or_error();
+    if n >= 0x100 {
+        return Err(LispError::new(
+            Qargs_out_of_range,
+            list!(LispObject::from(0xFF), code),
+        ));
+    }
+    Ok(n as u32)
+}
+
+/// Return list of charset and one to four position-codes of CH.
+/// The charset is decided by the current priority order of charsets.
+/// A position-code is a byte value of each dimension of the code-point of
+/// CH in the charset.
+#[lisp_fn]
+pub fn split_char(ch: Codepoint) -> LispObject {
+    let charset = LispCharsetRef::for_char(ch);
+    let mut code = charset
+        .encode_char(ch)
+        .unwrap_or_else(|| panic!("split-char: {:?} has no code point", ch));
+
+    let mut val = Qnil;
+    for _ in 0..charset.dimension {
+        val = (EmacsInt::from(code & 0xFF), val).into();
+        code >>= 8;
+    }
+    (charset, val).into()
+}
+
+/// Return the charset of highest priority that contains CH.
+/// ASCII characters are an exception: for them, this function always
+/// returns `ascii'.
+/// If optional 2nd arg RESTRICTION is non-nil, it is a list of charsets
+/// from which to find the charset.  It may also be a coding system.  In
+/// that case, find the charset from what supported by that coding system.
+#[lisp_fn(min = "1")]
+pub fn char_charset(ch: Codepoint, restriction: LispObject) -> LispObject {
+    if restriction.is_nil() {
+        return LispCharsetRef::for_char(ch).into();
+    }
+
+    if restriction.is_cons() {
+        return restriction
+            .iter_cars(LispConsEndChecks::off, LispConsCircularChecks::off)
+            .find(|&elt| LispCharsetRef::from(elt).encode_char(ch).is_some())
+            .unwrap_or(Qnil);
+    }
+
+    let charset_list = unsafe { coding_system_charset_list(restriction) };
+    let charset = unsafe { c_char_charset(u32::from(ch) as i32, charset_list, ptr::null_mut()) };
+    if charset.is_null() {
+        Qnil
+    } else {
+        LispCharsetRef::new(charset).into()
+    }
+}
+
 include!(concat!(env!("OUT_DIR"), "/charset_exports.rs"));
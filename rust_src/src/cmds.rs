@@ -205,6 +205,36 @@ pub fn forward_line(n: Option<EmacsInt>) -> EmacsInt {
     }
 }
 
+/// Move point to the beginning of line LINE, counting from 1 at the
+/// beginning of the accessible portion of the buffer.
+/// If LINE is past the last line, move to the beginning of the last
+/// line instead.  Returns the number of the line actually reached.
+///
+/// This is the primitive underlying the `goto-line' command.
+#[lisp_fn]
+pub fn goto_line_internal(line: EmacsInt) -> EmacsInt {
+    let cur_buf = ThreadState::current_buffer_unchecked();
+    unsafe { set_point_both(cur_buf.begv, cur_buf.begv_byte) };
+    if line <= 1 {
+        return 1;
+    }
+
+    let (mut pos, mut pos_byte) = (0, 0);
+    let shortage = unsafe { scan_newline_from_point(line as isize - 1, &mut pos, &mut pos_byte) };
+    unsafe { set_point_both(pos, pos_byte) };
+
+    if shortage > 0 {
+        // We ran into the end of the buffer partway through the last
+        // line; back up to its beginning.
+        unsafe {
+            scan_newline_from_point(0, &mut pos, &mut pos_byte);
+            set_point_both(pos, pos_byte);
+        }
+    }
+
+    line - shortage as EmacsInt
+}
+
 /// Delete the following N characters (previous if N is negative).
 /// Optional second arg KILLFLAG non-nil means kill instead (save in kill ring).
 /// Interactively, N is the prefix arg, and KILLFLAG is set if
@@ -6,7 +6,8 @@ use crate::{
     fns::validate_subarray_rust,
     lisp::LispObject,
     multibyte::LispStringRef,
-    remacs_sys::{EmacsInt, Fput_text_property, Qcomposition},
+    remacs_sys::{EmacsInt, Qcomposition},
+    textprop::put_text_property,
 };
 
 /// Internal use only.
@@ -47,15 +48,13 @@ pub fn compose_text_rust(
         modification_function,
     );
 
-    unsafe {
-        Fput_text_property(
-            start.into(),
-            end.into(),
-            Qcomposition,
-            prop.into(),
-            string.into(),
-        );
-    }
+    put_text_property(
+        start.into(),
+        end.into(),
+        Qcomposition,
+        prop.into(),
+        string.into(),
+    );
 }
 
 #[no_mangle]
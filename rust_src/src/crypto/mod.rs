@@ -10,7 +10,7 @@ use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
 use remacs_macros::lisp_fn;
 
 use crate::{
-    buffers::{LispBufferOrName, LispBufferRef},
+    buffers::{validate_region_rust, LispBufferOrName, LispBufferRef},
     lisp::LispObject,
     multibyte::LispStringRef,
     remacs_sys::EmacsInt,
@@ -119,6 +119,27 @@ pub fn secure_hash(
     _secure_hash(hash_alg(algorithm), object, start, end, Qnil, Qnil, binary)
 }
 
+/// Return a list of all the supported `secure_hash' algorithms.
+#[lisp_fn]
+pub fn secure_hash_algorithms() -> LispObject {
+    list!(Qmd5, Qsha1, Qsha224, Qsha256, Qsha384, Qsha512)
+}
+
+/// Return the secure hash of BYTES, a unibyte string, as a hex string.
+/// ALGORITHM is a symbol specifying the hash to use, as for
+/// `secure-hash'.
+///
+/// Unlike `secure-hash', no coding system conversion is done: the
+/// bytes of the string are hashed directly.  It is an error to pass a
+/// multibyte string.
+#[lisp_fn]
+pub fn secure_hash_bytes(algorithm: LispSymbolRef, bytes: LispStringRef) -> LispObject {
+    if bytes.is_multibyte() {
+        error!("Multibyte string given to secure-hash-bytes");
+    }
+    make_digest(hash_alg(algorithm), &[bytes.as_slice()], false)
+}
+
 fn _secure_hash(
     algorithm: HashAlg,
     object: LispObject,
@@ -128,8 +149,6 @@ fn _secure_hash(
     noerror: LispObject,
     binary: LispObject,
 ) -> LispObject {
-    type HashFn = fn(&[u8], &mut [u8]);
-
     let spec = list!(object, start, end, coding_system, noerror);
     let mut start_byte: ptrdiff_t = 0;
     let mut end_byte: ptrdiff_t = 0;
@@ -146,24 +165,40 @@ fn _secure_hash(
         )
     };
 
-    let (digest_size, hash_func) = match algorithm {
+    make_digest(algorithm, &[input_slice], binary.is_not_nil())
+}
+
+type HashFn = fn(&[&[u8]], &mut [u8]) -> usize;
+
+fn hash_fn(algorithm: HashAlg) -> (usize, HashFn) {
+    match algorithm {
         HashAlg::MD5 => (MD5_DIGEST_LEN, md5_buffer as HashFn),
         HashAlg::SHA1 => (SHA1_DIGEST_LEN, sha1_buffer as HashFn),
         HashAlg::SHA224 => (SHA224_DIGEST_LEN, sha224_buffer as HashFn),
         HashAlg::SHA256 => (SHA256_DIGEST_LEN, sha256_buffer as HashFn),
         HashAlg::SHA384 => (SHA384_DIGEST_LEN, sha384_buffer as HashFn),
         HashAlg::SHA512 => (SHA512_DIGEST_LEN, sha512_buffer as HashFn),
-    };
+    }
+}
 
-    let buffer_size = if binary.is_nil() {
-        (digest_size * 2) as EmacsInt
-    } else {
+/// Hash the concatenation of INPUT with ALGORITHM and return the
+/// digest as a new Lisp string, in hex form unless BINARY is true.
+fn make_digest(algorithm: HashAlg, input: &[&[u8]], binary: bool) -> LispObject {
+    let (digest_size, hash_func) = hash_fn(algorithm);
+
+    let buffer_size = if binary {
         digest_size as EmacsInt
+    } else {
+        (digest_size * 2) as EmacsInt
     };
     let digest = unsafe { make_uninit_string(buffer_size as EmacsInt) };
     let mut digest_str: LispStringRef = digest.into();
-    hash_func(input_slice, digest_str.as_mut_slice());
-    if binary.is_nil() {
+    let written = hash_func(input, digest_str.as_mut_slice());
+    debug_assert_eq!(
+        written, digest_size,
+        "hash function produced a digest of unexpected length"
+    );
+    if !binary {
         hexify_digest_string(digest_str.as_mut_slice(), digest_size);
     }
     digest
@@ -190,42 +225,56 @@ fn hexify_digest_string(buffer: &mut [u8], len: usize) {
 // destination buffer is at least long enough to hold the
 // digest. Additionally, the caller may have been asked to return a
 // hex string, in which case dest_buf will be twice as long as the
-// digest.
+// digest. The input is given as a list of chunks which are fed to
+// the hasher in order, so that buffer text split by the gap does not
+// need to be copied. Each function returns the number of digest
+// bytes written.
 
-fn md5_buffer(buffer: &[u8], dest_buf: &mut [u8]) {
-    let output = md5_crate::compute(buffer);
-    dest_buf[..output.len()].copy_from_slice(&*output)
+fn md5_buffer(chunks: &[&[u8]], dest_buf: &mut [u8]) -> usize {
+    let mut ctx = md5_crate::Context::new();
+    for chunk in chunks {
+        ctx.consume(chunk);
+    }
+    let output = ctx.compute();
+    dest_buf[..output.len()].copy_from_slice(&*output);
+    output.len()
 }
 
-fn sha1_buffer(buffer: &[u8], dest_buf: &mut [u8]) {
+fn sha1_buffer(chunks: &[&[u8]], dest_buf: &mut [u8]) -> usize {
     let mut hasher = sha1::Sha1::new();
-    hasher.update(buffer);
+    for chunk in chunks {
+        hasher.update(chunk);
+    }
     let output = hasher.digest().bytes();
-    dest_buf[..output.len()].copy_from_slice(&output)
+    dest_buf[..output.len()].copy_from_slice(&output);
+    output.len()
 }
 
-/// Given an instance of `Digest`, and `buffer` write its hash to `dest_buf`.
-fn sha2_hash_buffer(hasher: impl Digest, buffer: &[u8], dest_buf: &mut [u8]) {
+/// Given an instance of `Digest`, and `chunks` write its hash to `dest_buf`.
+fn sha2_hash_buffer(hasher: impl Digest, chunks: &[&[u8]], dest_buf: &mut [u8]) -> usize {
     let mut hasher = hasher;
-    hasher.input(buffer);
+    for chunk in chunks {
+        hasher.input(chunk);
+    }
     let output = hasher.result();
-    dest_buf[..output.len()].copy_from_slice(&output)
+    dest_buf[..output.len()].copy_from_slice(&output);
+    output.len()
 }
 
-fn sha224_buffer(buffer: &[u8], dest_buf: &mut [u8]) {
-    sha2_hash_buffer(Sha224::new(), buffer, dest_buf);
+fn sha224_buffer(chunks: &[&[u8]], dest_buf: &mut [u8]) -> usize {
+    sha2_hash_buffer(Sha224::new(), chunks, dest_buf)
 }
 
-fn sha256_buffer(buffer: &[u8], dest_buf: &mut [u8]) {
-    sha2_hash_buffer(Sha256::new(), buffer, dest_buf);
+fn sha256_buffer(chunks: &[&[u8]], dest_buf: &mut [u8]) -> usize {
+    sha2_hash_buffer(Sha256::new(), chunks, dest_buf)
 }
 
-fn sha384_buffer(buffer: &[u8], dest_buf: &mut [u8]) {
-    sha2_hash_buffer(Sha384::new(), buffer, dest_buf);
+fn sha384_buffer(chunks: &[&[u8]], dest_buf: &mut [u8]) -> usize {
+    sha2_hash_buffer(Sha384::new(), chunks, dest_buf)
 }
 
-fn sha512_buffer(buffer: &[u8], dest_buf: &mut [u8]) {
-    sha2_hash_buffer(Sha512::new(), buffer, dest_buf);
+fn sha512_buffer(chunks: &[&[u8]], dest_buf: &mut [u8]) -> usize {
+    sha2_hash_buffer(Sha512::new(), chunks, dest_buf)
 }
 
 /// Return a hash of the contents of BUFFER-OR-NAME.
@@ -236,17 +285,9 @@ pub fn buffer_hash(buffer_or_name: Option<LispBufferOrName>) -> LispObject {
     let b = buffer_or_name.map_or_else(ThreadState::current_buffer_unchecked, LispBufferRef::from);
     let mut ctx = sha1::Sha1::new();
 
-    ctx.update(unsafe {
-        slice::from_raw_parts(b.beg_addr(), (b.gpt_byte() - b.beg_byte()) as usize)
-    });
-    if b.gpt_byte() < b.z_byte() {
-        ctx.update(unsafe {
-            slice::from_raw_parts(
-                b.gap_end_addr(),
-                b.z_addr() as usize - b.gap_end_addr() as usize,
-            )
-        });
-    }
+    let (before_gap, after_gap) = b.text_slices(b.beg_byte(), b.z_byte());
+    ctx.update(before_gap);
+    ctx.update(after_gap);
 
     let formatted = ctx.digest().to_string();
     let digest = unsafe { make_uninit_string(formatted.len() as EmacsInt) };
@@ -258,4 +299,23 @@ pub fn buffer_hash(buffer_or_name: Option<LispBufferOrName>) -> LispObject {
     digest
 }
 
+/// Return a hash of the text of the current buffer between START and END.
+/// Like `buffer-hash', the hash is performed on the raw internal format
+/// of the buffer, disregarding any coding systems.  START and END
+/// default to the accessible portion of the buffer.  ALGORITHM is one
+/// of the symbols listed by `secure-hash-algorithms' and defaults to
+/// sha1.  The result is a hex string.
+#[lisp_fn(min = "0")]
+pub fn buffer_hash_region(start: LispObject, end: LispObject, algorithm: LispObject) -> LispObject {
+    let b = ThreadState::current_buffer_unchecked();
+    let start = start.map_or_else(|| LispObject::from(b.begv), |s| s);
+    let end = end.map_or_else(|| LispObject::from(b.zv), |e| e);
+    let (start, end) = validate_region_rust(start, end);
+    let algorithm = algorithm.map_or(HashAlg::SHA1, |a| hash_alg(a.into()));
+
+    let (before_gap, after_gap) =
+        b.text_slices(b.charpos_to_bytepos(start), b.charpos_to_bytepos(end));
+    make_digest(algorithm, &[before_gap, after_gap], false)
+}
+
 include!(concat!(env!("OUT_DIR"), "/crypto_exports.rs"));
@@ -1,18 +1,20 @@
 //! data helpers
 
+use std::{mem, ptr};
+
 use field_offset::FieldOffset;
-use libc::{c_char, c_int};
+use libc::{c_char, c_int, ptrdiff_t};
 
 use remacs_macros::lisp_fn;
 
 use crate::{
     alloc::purecopy,
-    buffers::{per_buffer_idx, per_buffer_idx_from_field_offset},
+    buffers::{per_buffer_idx, per_buffer_idx_from_field_offset, LispBufferLocalValueRef},
     frame::selected_frame,
     keymap::get_keymap,
     lisp::is_autoload,
     lisp::{LispObject, LispSubrRef, LiveBufferIter},
-    lists::{get, member, memq, put},
+    lists::{assq, get, member, memq, put, LispCons},
     math::leq,
     multibyte::{Codepoint, LispStringRef},
     obarray::{loadhist_attach, map_obarray},
@@ -20,10 +22,11 @@ use crate::{
     remacs_sys::Fdelete,
     remacs_sys::Vautoload_queue,
     remacs_sys::{
-        aset_multibyte_string, bool_vector_binop_driver, buffer_defaults, build_string, globals,
-        rust_count_one_bits, set_default_internal, set_internal, string_to_number,
-        symbol_trapped_write, valid_lisp_object_p, wrong_choice, wrong_range, CHAR_TABLE_SET,
-        CHECK_IMPURE,
+        aset_multibyte_string, bool_vector_binop_driver, buffer_defaults, build_string,
+        float_to_string, globals, let_shadows_buffer_binding_p, make_unibyte_string,
+        notify_variable_watchers, rust_count_one_bits, set_internal, string_to_number,
+        swap_in_symval_forwarding, symbol_trapped_write, valid_lisp_object_p, wrong_choice,
+        wrong_range, xmalloc, Lisp_Buffer_Local_Value, CHAR_TABLE_SET, CHECK_IMPURE,
     },
     remacs_sys::{per_buffer_default, symbol_redirect},
     remacs_sys::{pvec_type, BoolVectorOp, EmacsInt, Lisp_Misc_Type, Lisp_Type, Set_Internal_Bind},
@@ -33,11 +36,14 @@ use crate::{
         Qcondition_variable, Qcons, Qcyclic_function_indirection, Qdefalias_fset_function, Qdefun,
         Qfinalizer, Qfloat, Qfont, Qfont_entity, Qfont_object, Qfont_spec, Qframe,
         Qfunction_documentation, Qhash_table, Qinteger, Qmany, Qmarker, Qmodule_function, Qmutex,
-        Qnil, Qnone, Qoverlay, Qprocess, Qrange, Qstring, Qsubr, Qsymbol, Qterminal, Qthread,
-        Qunbound, Qunevalled, Quser_ptr, Qvector, Qwatchers, Qwindow, Qwindow_configuration,
+        Qnil, Qnone, Qnumberp, Qoverlay, Qprocess, Qrange, Qset_default, Qstring, Qsubr, Qsymbol,
+        Qsymbol_with_pos, Qterminal, Qthread, Qunbound, Qunevalled, Quser_ptr, Qvector, Qwatchers,
+        Qwindow, Qwindow_configuration, Qwrong_length_argument,
     },
-    symbols::LispSymbolRef,
+    remacs_sys::{BITS_PER_BITS_WORD, FLOAT_TO_STRING_BUFSIZE},
+    symbols::{boundp, keywordp, symbol_value, LispSymbolRef},
     threads::ThreadState,
+    vectors::LispBoolVecRef,
 };
 
 // Lisp_Fwd predicates which can go away as the callers are ported to Rust
@@ -143,6 +149,7 @@ pub fn type_of(object: LispObject) -> LispObject {
                 pvec_type::PVEC_CONDVAR => Qcondition_variable,
                 pvec_type::PVEC_TERMINAL => Qterminal,
                 pvec_type::PVEC_MODULE_FUNCTION => Qmodule_function,
+                pvec_type::PVEC_SYMBOL_WITH_POS => Qsymbol_with_pos,
                 pvec_type::PVEC_FONT => {
                     if object.is_font_spec() {
                         Qfont_spec
@@ -675,8 +682,29 @@ pub fn bool_vector_set_difference(a: LispObject, b: LispObject, c: LispObject) -
 /// Return t if every t value in A is also t in B, nil otherwise.
 /// A and B must be bool vectors of the same length.
 #[lisp_fn]
-pub fn bool_vector_subsetp(a: LispObject, b: LispObject) -> LispObject {
-    unsafe { bool_vector_binop_driver(a, b, b, BoolVectorOp::BoolVectorSubsetp) }
+pub fn bool_vector_subsetp(a: LispBoolVecRef, b: LispBoolVecRef) -> bool {
+    let nr_bits = a.len();
+    if b.len() != nr_bits {
+        xsignal!(Qwrong_length_argument, nr_bits, b.len());
+    }
+
+    let bits_per_word = BITS_PER_BITS_WORD as usize;
+    let tail_bits = nr_bits % bits_per_word;
+    let last = a.len_words().wrapping_sub(1);
+
+    a.as_slice()
+        .iter()
+        .zip(b.as_slice())
+        .enumerate()
+        .all(|(i, (&a_word, &b_word))| {
+            // Only the low bits of the final word belong to the vector.
+            let mask = if i == last && tail_bits != 0 {
+                (1 << tail_bits) - 1
+            } else {
+                !0
+            };
+            (a_word & !b_word) & mask == 0
+        })
 }
 
 /// Set SYMBOL's value to NEWVAL, and return NEWVAL.
@@ -693,15 +721,291 @@ pub fn set(symbol: LispSymbolRef, newval: LispObject) -> LispObject {
     newval
 }
 
+/// Set the default value of SYMBOL to VALUE.  BINDFLAG tells whether
+/// this is a plain assignment, a `let' binding or unbinding, or part of
+/// a thread switch.
+///
+/// Only the default binding is touched: a buffer-local binding in the
+/// current buffer keeps its value, while buffers without one see VALUE.
+#[no_mangle]
+pub extern "C" fn set_default_internal(
+    symbol: LispObject,
+    value: LispObject,
+    bindflag: Set_Internal_Bind::Type,
+) {
+    let sym: LispSymbolRef = symbol.into();
+
+    match sym.get_trapped_write() {
+        symbol_trapped_write::SYMBOL_NOWRITE => {
+            if !keywordp(symbol) || !value.eq(symbol_value(sym)) {
+                setting_constant!(symbol);
+            }
+            // Allow setting keywords to their own value.
+            return;
+        }
+        symbol_trapped_write::SYMBOL_TRAPPED_WRITE => {
+            // Don't notify here if we're going to call `set' anyway.
+            // Setting due to thread switching doesn't count.
+            if sym.get_redirect() != symbol_redirect::SYMBOL_PLAINVAL
+                && bindflag != Set_Internal_Bind::SET_INTERNAL_THREAD_SWITCH
+            {
+                unsafe { notify_variable_watchers(symbol, value, Qset_default, Qnil) };
+            }
+        }
+        _ => {}
+    }
+
+    let sym = sym.get_indirect_variable();
+    match sym.get_redirect() {
+        symbol_redirect::SYMBOL_PLAINVAL => unsafe { set_internal(symbol, value, Qnil, bindflag) },
+        symbol_redirect::SYMBOL_LOCALIZED => {
+            let blv = unsafe { sym.get_blv() };
+
+            // Store the new value into the DEFAULT-VALUE slot.
+            LispCons::from(blv.defcell).set_cdr(value);
+
+            // If the default binding is now loaded, set the REALVALUE slot too.
+            if !blv.fwd.is_null() && blv.defcell.eq(blv.valcell) {
+                unsafe { store_symval_forwarding(blv.fwd, value, ptr::null_mut()) };
+            }
+        }
+        symbol_redirect::SYMBOL_FORWARDED => unsafe {
+            let valcontents = sym.get_fwd();
+
+            // Handle variables like case-fold-search that have special slots
+            // in the buffer.  Make them work apparently like buffer-local
+            // variables.
+            if let Some(buffer_objfwd) = as_buffer_objfwd(valcontents) {
+                let offset = buffer_objfwd.offset;
+                let idx = per_buffer_idx_from_field_offset(offset);
+
+                *offset.apply_ptr_mut(&mut buffer_defaults) = value;
+
+                // If this variable is not always local in all buffers,
+                // set it in the buffers that don't nominally have a local value.
+                if idx > 0 {
+                    LiveBufferIter::new().for_each(|mut buf| {
+                        if !buf.value_p(idx) {
+                            *offset.apply_ptr_mut(buf.as_mut()) = value;
+                        }
+                    });
+                }
+            } else {
+                set_internal(symbol, value, Qnil, bindflag);
+            }
+        },
+        _ => unreachable!(),
+    }
+}
+
 /// Set SYMBOL's default value to VALUE.  SYMBOL and VALUE are evaluated.
 /// The default value is seen in buffers that do not have their own
 /// values for this variable.
 #[lisp_fn]
 pub fn set_default(symbol: LispSymbolRef, value: LispObject) -> LispObject {
-    unsafe { set_default_internal(symbol.into(), value, Set_Internal_Bind::SET_INTERNAL_SET) };
+    set_default_internal(symbol.into(), value, Set_Internal_Bind::SET_INTERNAL_SET);
     value
 }
 
+/// Allocate the buffer-local value structure for SYMBOL.  Its default
+/// cell starts out holding the current value of the variable, which is
+/// read through FWD if that is non-null and is VALUE otherwise.
+fn make_blv(
+    symbol: LispSymbolRef,
+    fwd: *mut Lisp_Fwd,
+    value: LispObject,
+) -> LispBufferLocalValueRef {
+    // Buffer-local values cannot have as realval a buffer-local or
+    // keyboard-local forwarding.
+    debug_assert!(fwd.is_null() || unsafe { !is_buffer_objfwd(fwd) && !is_kboard_objfwd(fwd) });
+
+    let value = if fwd.is_null() {
+        value
+    } else {
+        unsafe { do_symval_forwarding(fwd) }
+    };
+    let tem = LispObject::cons(symbol, value);
+
+    let mut blv =
+        LispBufferLocalValueRef::new(
+            unsafe { xmalloc(mem::size_of::<Lisp_Buffer_Local_Value>()) }
+                as *mut Lisp_Buffer_Local_Value,
+        );
+    blv.fwd = fwd;
+    blv.where_ = Qnil;
+    blv.set_local_if_set(false);
+    blv.defcell = tem;
+    blv.valcell = tem;
+    blv.set_found(false);
+    blv
+}
+
+/// Make VARIABLE become buffer-local whenever it is set.
+/// At any time, the value for the current buffer is in effect,
+/// unless the variable has never been set in this buffer,
+/// in which case the default value is in effect.
+/// Note that binding the variable with `let', or setting it while
+/// a `let'-style binding made in this buffer is in effect,
+/// does not make the variable buffer-local.  Return VARIABLE.
+///
+/// This globally affects all uses of this variable, so it belongs together with
+/// the variable declaration, rather than with its uses (if you just want to make
+/// a variable local to the current buffer for one particular use, use
+/// `make-local-variable').  Buffer-local bindings are normally cleared
+/// while setting up a new major mode, unless they have a `permanent-local'
+/// property.
+///
+/// The function `default-value' gets the default value and `set-default' sets it.
+#[lisp_fn(intspec = "vMake Variable Buffer Local: ")]
+pub fn make_variable_buffer_local(variable: LispSymbolRef) -> LispSymbolRef {
+    let sym = variable.get_indirect_variable();
+    let mut fwd: *mut Lisp_Fwd = ptr::null_mut();
+    let mut value = Qnil;
+    let mut blv = None;
+
+    match sym.get_redirect() {
+        symbol_redirect::SYMBOL_PLAINVAL => {
+            value = unsafe { sym.get_value() };
+            if value.eq(Qunbound) {
+                value = Qnil;
+            }
+        }
+        symbol_redirect::SYMBOL_LOCALIZED => blv = Some(unsafe { sym.get_blv() }),
+        symbol_redirect::SYMBOL_FORWARDED => {
+            fwd = unsafe { sym.get_fwd() };
+            if unsafe { is_kboard_objfwd(fwd) } {
+                error!(
+                    "Symbol {} may not be buffer-local",
+                    LispStringRef::from(variable.symbol_name())
+                );
+            } else if unsafe { is_buffer_objfwd(fwd) } {
+                return variable;
+            }
+        }
+        _ => unreachable!(),
+    }
+
+    if variable.is_constant() {
+        setting_constant!(variable);
+    }
+
+    let mut blv = blv.unwrap_or_else(|| {
+        let blv = make_blv(sym, fwd, value);
+        sym.set_redirect(symbol_redirect::SYMBOL_LOCALIZED);
+        sym.set_blv(blv);
+        blv
+    });
+
+    blv.set_local_if_set(true);
+    variable
+}
+
+/// Make VARIABLE have a separate value in the current buffer.
+/// Other buffers will continue to share a common default value.
+/// \(The buffer-local value of VARIABLE starts out as the same value
+/// VARIABLE previously had.  If VARIABLE was void, it remains void.)
+/// Return VARIABLE.
+///
+/// If the variable is already arranged to become local when set,
+/// this function causes a local value to exist for this buffer,
+/// just as setting the variable would do.
+///
+/// This function returns VARIABLE, and therefore
+///   (set (make-local-variable \='VARIABLE) VALUE-EXP)
+/// works.
+///
+/// See also `make-variable-buffer-local'.
+///
+/// Do not use `make-local-variable' to make a hook variable buffer-local.
+/// Instead, use `add-hook' and specify t for the LOCAL argument.
+#[lisp_fn(intspec = "vMake Local Variable: ")]
+pub fn make_local_variable(variable: LispSymbolRef) -> LispSymbolRef {
+    let mut sym = variable.get_indirect_variable();
+    let mut fwd: *mut Lisp_Fwd = ptr::null_mut();
+    let mut value = Qnil;
+    let mut blv = None;
+
+    match sym.get_redirect() {
+        symbol_redirect::SYMBOL_PLAINVAL => value = unsafe { sym.get_value() },
+        symbol_redirect::SYMBOL_LOCALIZED => blv = Some(unsafe { sym.get_blv() }),
+        symbol_redirect::SYMBOL_FORWARDED => {
+            fwd = unsafe { sym.get_fwd() };
+            if unsafe { is_kboard_objfwd(fwd) } {
+                error!(
+                    "Symbol {} may not be buffer-local",
+                    LispStringRef::from(variable.symbol_name())
+                );
+            }
+        }
+        _ => unreachable!(),
+    }
+
+    if sym.is_constant() {
+        setting_constant!(variable);
+    }
+
+    let local_if_set = match blv {
+        Some(blv) => blv.local_if_set(),
+        None => !fwd.is_null() && unsafe { is_buffer_objfwd(fwd) },
+    };
+    if local_if_set {
+        // Make sure the symbol has a local value in this particular buffer,
+        // by setting it to the same value it already has.
+        let value = if boundp(variable) {
+            symbol_value(variable)
+        } else {
+            Qunbound
+        };
+        set(variable, value);
+        return variable;
+    }
+
+    let mut blv = blv.unwrap_or_else(|| {
+        let blv = make_blv(sym, fwd, value);
+        sym.set_redirect(symbol_redirect::SYMBOL_LOCALIZED);
+        sym.set_blv(blv);
+        blv
+    });
+
+    // Make sure this buffer has its own value of symbol.
+    let mut buffer = ThreadState::current_buffer_unchecked();
+    if assq(sym.into(), buffer.local_var_alist_).is_nil() {
+        if unsafe { let_shadows_buffer_binding_p(sym.as_mut()) } {
+            message_with_string!(
+                "Making %s buffer-local while locally let-bound!\0",
+                sym.symbol_name(),
+                false
+            );
+        }
+
+        // Swap out any local binding for some other buffer, and make
+        // sure the current value is permanently recorded, if it's the
+        // default value.
+        unsafe { sym.find_value() };
+
+        let (_, default) = blv.defcell.into();
+        buffer.local_var_alist_ =
+            LispObject::cons(LispObject::cons(sym, default), buffer.local_var_alist_);
+
+        // Make sure symbol does not think it is set up for this buffer;
+        // force it to look once again for this buffer's value.
+        if blv.where_.eq(buffer) {
+            blv.where_ = Qnil;
+        }
+        blv.set_found(false);
+    }
+
+    // If the symbol forwards into a C variable, then load the binding
+    // for this buffer now.  If C code modifies the variable before we
+    // load the binding in, then that new value will clobber the default
+    // binding the next time we unload it.
+    if !blv.get_fwd().is_null() {
+        unsafe { swap_in_symval_forwarding(sym.as_mut(), blv.as_mut()) };
+    }
+
+    sym
+}
+
 extern "C" fn harmonize_variable_watchers(alias: LispObject, base_variable: LispObject) {
     let alias_sym: LispSymbolRef = alias.into();
     let base_variable_sym: LispSymbolRef = base_variable.into();
@@ -853,4 +1157,24 @@ pub fn string_to_number_lisp(mut string: LispStringRef, base: Option<EmacsInt>)
     }
 }
 
+/// Return the decimal representation of NUMBER as a string.
+/// Uses a minus sign if negative.
+/// NUMBER may be an integer or a floating point number.
+#[lisp_fn]
+pub fn number_to_string(number: LispObject) -> LispObject {
+    if let Some(f) = number.as_float() {
+        let mut buffer = [0 as c_char; FLOAT_TO_STRING_BUFSIZE as usize];
+        unsafe {
+            let len = float_to_string(buffer.as_mut_ptr(), f);
+            make_unibyte_string(buffer.as_ptr(), len as ptrdiff_t)
+        }
+    } else if let Some(n) = number.as_fixnum() {
+        LispObject::from(n.to_string().as_str())
+    } else {
+        wrong_type!(Qnumberp, number)
+    }
+}
+
+def_lisp_sym!(Qsymbol_with_pos, "symbol-with-pos");
+
 include!(concat!(env!("OUT_DIR"), "/data_exports.rs"));
@@ -11,14 +11,14 @@ use libc::{c_char, c_int, c_uchar, ptrdiff_t};
 use remacs_macros::lisp_fn;
 
 use crate::{
-    buffers::{current_buffer, validate_region_rust},
+    buffers::{current_buffer, overlay_get, validate_region_rust, LispOverlayRef, BEG},
     buffers::{LispBufferOrCurrent, LispBufferOrName, LispBufferRef, BUF_BYTES_MAX},
     character::{char_head_p, dec_pos},
     eval::{progn, record_unwind_protect, unbind_to},
     fns::copy_sequence,
     indent::invalidate_current_column,
     lisp::LispObject,
-    marker::{marker_position_lisp, point_marker, set_point_from_marker},
+    marker::{marker_position_lisp, point_marker, set_point_from_marker, LispMarkerRef},
     multibyte::MAX_MULTIBYTE_LENGTH,
     multibyte::{multibyte_char_at, Codepoint, LispStringRef},
     numbers::{check_range, LispNumber},
@@ -30,17 +30,18 @@ use crate::{
         make_buffer_string_both, make_save_obj_obj_obj_obj, make_string_from_bytes, maybe_quit,
         message1, message3, record_unwind_current_buffer, save_excursion_restore,
         save_restriction_restore, save_restriction_save, scan_newline_from_point,
-        set_buffer_internal_1, set_point, set_point_both, signal_after_change, styled_format,
-        update_buffer_properties, update_compositions, CHECK_BORDER, STRING_BYTES,
+        set_buffer_internal_1, set_point, set_point_both, signal_after_change, sort_overlays,
+        styled_format, text_property_stickiness, update_buffer_properties, update_compositions,
+        CHECK_BORDER, STRING_BYTES,
     },
     remacs_sys::{
-        Fadd_text_properties, Fget_pos_property, Fnext_single_char_property_change,
+        Fadd_text_properties, Fnext_single_char_property_change,
         Fprevious_single_char_property_change, Fsystem_name, Fx_popup_dialog,
     },
     remacs_sys::{
         Qboundary, Qchar_or_string_p, Qfield, Qinteger_or_marker_p, Qmark_inactive, Qnil, Qt,
     },
-    textprop::get_char_property,
+    textprop::{get_char_property, get_text_property},
     threads::{c_specpdl_index, ThreadState},
     time::{lisp_time_struct, time_overflow, LispTime},
     util::clip_to_bounds,
@@ -309,11 +310,10 @@ pub fn insert_char(character: Codepoint, count: Option<EmacsInt>, inherit: bool)
 pub fn following_char() -> EmacsInt {
     let buffer_ref = ThreadState::current_buffer_unchecked();
 
-    if buffer_ref.pt >= buffer_ref.zv {
-        0
-    } else {
-        EmacsInt::from(buffer_ref.fetch_char(buffer_ref.pt_byte))
-    }
+    buffer_ref
+        .chars_from(buffer_ref.pt, buffer_ref.pt_byte)
+        .next()
+        .map_or(0, |(_, c)| EmacsInt::from(c))
 }
 
 /// Return the character preceding point, as a number. At the
@@ -322,16 +322,10 @@ pub fn following_char() -> EmacsInt {
 pub fn preceding_char() -> EmacsInt {
     let buffer_ref = ThreadState::current_buffer_unchecked();
 
-    if buffer_ref.pt <= buffer_ref.begv {
-        return EmacsInt::from(0);
-    }
-
-    let pos = if buffer_ref.multibyte_characters_enabled() {
-        unsafe { dec_pos(buffer_ref.pt_byte) }
-    } else {
-        buffer_ref.pt_byte - 1
-    };
-    EmacsInt::from(buffer_ref.fetch_char(pos))
+    buffer_ref
+        .chars_rev_from(buffer_ref.pt, buffer_ref.pt_byte)
+        .next()
+        .map_or(0, |(_, c)| EmacsInt::from(c))
 }
 
 /// Return character in current buffer preceding position POS.
@@ -482,25 +476,45 @@ pub fn string_to_char(string: LispStringRef) -> EmacsInt {
 /// starts at point, the scan stops as soon as it starts.  To ignore field
 /// boundaries, bind `inhibit-field-text-motion' to t.
 ///
-/// This function does not move point.
+/// This function does not move point.  Also see `pos-bol'.
 #[lisp_fn(min = "0")]
 pub fn line_beginning_position(n: Option<EmacsInt>) -> EmacsInt {
-    let mut charpos: isize = 0;
-
-    let n = n.unwrap_or(1) as isize;
-
-    unsafe { scan_newline_from_point(n - 1, &mut charpos, ptr::null_mut()) };
+    let charpos = pos_bol(n);
 
     // Return END constrained to the current input field.
     constrain_to_field(
-        Some(LispNumber::Fixnum(charpos as EmacsInt)),
+        Some(LispNumber::Fixnum(charpos)),
         LispNumber::Fixnum(point() as EmacsInt),
-        n != 1,
+        n.map_or(false, |n| n != 1),
         true,
         Qnil,
     )
 }
 
+/// Return the position of the first character on the current line.
+/// With optional argument N, scan forward N - 1 lines first.
+/// If the scan reaches the end of the buffer, return that position.
+///
+/// This function ignores text display directionality; it returns the
+/// position of the first character in logical order, i.e. the smallest
+/// character position on the logical line.  See `vertical-motion' for
+/// movement by screen lines.
+///
+/// Unlike `line-beginning-position', this function does not take
+/// fields into account.
+///
+/// This function does not move point.  Also see `line-beginning-position'.
+#[lisp_fn(min = "0")]
+pub fn pos_bol(n: Option<EmacsInt>) -> EmacsInt {
+    let mut charpos: isize = 0;
+
+    let n = n.unwrap_or(1) as isize;
+
+    unsafe { scan_newline_from_point(n - 1, &mut charpos, ptr::null_mut()) };
+
+    charpos as EmacsInt
+}
+
 /// Return the character position of the last character on the current line.
 /// With argument N not nil or 1, move forward N - 1 lines first.
 /// If scan reaches end of buffer, return that position.
@@ -515,31 +529,178 @@ pub fn line_beginning_position(n: Option<EmacsInt>) -> EmacsInt {
 /// at point, the scan stops as soon as it starts.  To ignore field
 /// boundaries bind `inhibit-field-text-motion' to t.
 ///
-/// This function does not move point.
+/// This function does not move point.  Also see `pos-eol'.
 #[lisp_fn(min = "0")]
 pub fn line_end_position(n: Option<EmacsInt>) -> EmacsInt {
-    let orig = point();
+    let end_pos = pos_eol(n);
+
+    // Return END constrained to the current input field.
+    constrain_to_field(
+        Some(LispNumber::Fixnum(end_pos)),
+        LispNumber::Fixnum(point()),
+        n.map_or(false, |n| n != 1),
+        true,
+        Qnil,
+    )
+}
 
+/// Return the position of the last character on the current line.
+/// With argument N not nil or 1, move forward N - 1 lines first.
+/// If scan reaches end of buffer, return that position.
+///
+/// This function ignores text display directionality; it returns the
+/// position of the last character in logical order, i.e. the largest
+/// character position on the line.
+///
+/// Unlike `line-end-position', this function does not take fields
+/// into account.
+///
+/// This function does not move point.  Also see `line-end-position'.
+#[lisp_fn(min = "0")]
+pub fn pos_eol(n: Option<EmacsInt>) -> EmacsInt {
     let n = n.unwrap_or(1);
 
     let clipped_n = clip_to_bounds(ptrdiff_t::min_value() + 1, n, ptrdiff_t::max_value());
     let end_pos = unsafe {
         find_before_next_newline(
-            orig as isize,
+            point() as isize,
             0,
             clipped_n - (if clipped_n <= 0 { 1 } else { 0 }),
             ptr::null_mut(),
         )
     };
 
-    // Return END constrained to the current input field.
-    constrain_to_field(
-        Some(LispNumber::Fixnum(end_pos as EmacsInt)),
-        LispNumber::Fixnum(orig),
-        n != 1,
-        true,
-        Qnil,
-    )
+    end_pos as EmacsInt
+}
+
+/// Return buffer line number at position POS.
+/// If POS is nil, use current buffer location.
+///
+/// If ABSOLUTE is nil, the default, counting starts
+/// at (point-min), so the value refers to the contents of the
+/// accessible portion of the (potentially narrowed) buffer.  If
+/// ABSOLUTE is non-nil, ignore any narrowing and return the
+/// absolute line number.
+#[lisp_fn(min = "0")]
+pub fn line_number_at_pos(pos: LispObject, absolute: bool) -> EmacsInt {
+    let cur_buf = ThreadState::current_buffer_unchecked();
+    let (start, end) = if absolute {
+        (BEG, cur_buf.z())
+    } else {
+        (cur_buf.begv, cur_buf.zv)
+    };
+
+    let pos = if pos.is_nil() {
+        cur_buf.pt as EmacsInt
+    } else {
+        pos.as_fixnum_coerce_marker_or_error()
+    };
+    let pos = clip_to_bounds(start, pos, end);
+    if pos == start {
+        return 1;
+    }
+
+    // Ask for more newlines than can possibly be there; whatever is
+    // left over is the number we did not find.
+    let wanted = pos - start;
+    let mut shortage = 0;
+    unsafe {
+        find_newline(
+            start,
+            -1,
+            pos,
+            -1,
+            wanted,
+            &mut shortage,
+            ptr::null_mut(),
+            true,
+        )
+    };
+
+    (wanted - shortage + 1) as EmacsInt
+}
+
+/// Find all the overlays in BUFFER that touch position POS.
+fn overlays_around(buffer: LispBufferRef, pos: EmacsInt) -> Vec<LispObject> {
+    let position = |marker: LispObject| LispMarkerRef::from(marker).charpos_or_error() as EmacsInt;
+
+    let before = buffer
+        .overlays_before()
+        .into_iter()
+        .flat_map(LispOverlayRef::iter)
+        .take_while(|ol| position(ol.end) >= pos)
+        .filter(|ol| position(ol.start) <= pos);
+    let after = buffer
+        .overlays_after()
+        .into_iter()
+        .flat_map(LispOverlayRef::iter)
+        .take_while(|ol| position(ol.start) <= pos)
+        .filter(|ol| pos <= position(ol.end));
+
+    before.chain(after).map(LispObject::from).collect()
+}
+
+/// Return the value of POSITION's property PROP, in OBJECT.
+/// Almost identical to `get-char-property' except for the following difference:
+/// Whereas `get-char-property' returns the property of the char at (i.e. right
+/// after) POSITION, this pays attention to properties's stickiness and overlays's
+/// advancement settings, in order to find the property of POSITION itself,
+/// i.e. the property that a char would inherit if it were inserted
+/// at POSITION.
+#[lisp_fn(min = "2")]
+pub fn get_pos_property(position: LispObject, prop: LispObject, object: LispObject) -> LispObject {
+    let posn = position.as_fixnum_coerce_marker_or_error();
+
+    let object = match object.as_window() {
+        Some(w) => w.contents,
+        None if object.is_nil() => current_buffer(),
+        None => object,
+    };
+
+    let buffer = match object.as_buffer() {
+        Some(buffer) => buffer,
+        // pos-property only makes sense in buffers right now, since strings
+        // have no overlays and no notion of insertion for which stickiness
+        // could be obeyed.
+        None => return get_text_property(posn.into(), prop, object),
+    };
+
+    let mut overlays = overlays_around(buffer, posn);
+    let noverlays = unsafe {
+        sort_overlays(
+            overlays.as_mut_ptr(),
+            overlays.len() as ptrdiff_t,
+            ptr::null_mut(),
+        )
+    };
+    overlays.truncate(noverlays as usize);
+
+    // Now check the overlays in order of decreasing priority.
+    for ol in overlays.into_iter().rev().map(LispOverlayRef::from) {
+        let tem = overlay_get(ol, prop);
+        if tem.is_nil() {
+            continue;
+        }
+        // Check the overlay is indeed active at point.
+        let start = LispMarkerRef::from(ol.start);
+        let finish = LispMarkerRef::from(ol.end);
+        let excluded = (start.charpos_or_error() as EmacsInt == posn && start.insertion_type())
+            || (finish.charpos_or_error() as EmacsInt == posn && !finish.insertion_type());
+        // Otherwise the overlay will not cover a char inserted at point.
+        if !excluded {
+            return tem;
+        }
+    }
+
+    // Now check the text properties.
+    let stickiness = unsafe { text_property_stickiness(prop, posn.into(), object) };
+    if stickiness > 0 {
+        get_text_property(posn.into(), prop, object)
+    } else if stickiness < 0 && posn > buffer.begv as EmacsInt {
+        get_text_property((posn - 1).into(), prop, object)
+    } else {
+        Qnil
+    }
 }
 
 /// Return the beginning of the field surrounding POS.
@@ -578,6 +739,10 @@ pub fn field_end(
     end as EmacsInt
 }
 
+/// Non-nil means text motion commands don't notice fields.
+#[rustfmt::skip]
+declare_lisp_variable!(Vinhibit_field_text_motion, "inhibit-field-text-motion", Qnil);
+
 /// Return the position closest to NEW-POS that is in the same field as OLD-POS.
 /// A field is a region of text with the same `field' property.
 ///
@@ -629,7 +794,7 @@ pub fn constrain_to_field(
     let prev_new = new_pos - 1;
     let begv = ThreadState::current_buffer_unchecked().begv as EmacsInt;
 
-    if unsafe { globals.Vinhibit_field_text_motion.is_nil() }
+    if unsafe { Vinhibit_field_text_motion.is_nil() }
         && new_pos != old_pos
         && (get_char_property(
             new_pos.into (),
@@ -654,12 +819,10 @@ pub fn constrain_to_field(
             // Field boundaries are again a problem; but now we must
             // decide the case exactly, so we need to call
             // `get_pos_property' as well.
-            || (unsafe {
-                Fget_pos_property(
+            || (get_pos_property(
                     LispObject::from(old_pos),
                     inhibit_capture_property,
                     Qnil).is_nil()
-            }
                 && (old_pos <= begv
                     || get_char_property(
                         old_pos.into (),
@@ -1,6 +1,6 @@
 //! Generic Lisp eval functions
 
-use std::{ptr, unreachable};
+use std::{cmp::Ordering, ptr, unreachable};
 
 use libc::c_void;
 
@@ -8,12 +8,15 @@ use remacs_macros::lisp_fn;
 
 use crate::{
     alloc::purecopy,
+    buffers::LispBufferOrCurrent,
     data::{
-        defalias, default_boundp, fset, indirect_function, indirect_function_lisp, set, set_default,
+        defalias, default_boundp, fset, indirect_function, indirect_function_lisp,
+        make_local_variable, set, set_default,
     },
+    fns::{append, copy_sequence},
     lisp::is_autoload,
     lisp::{LispObject, LispSubrRef, SpecbindingRef},
-    lists::{assq, car, cdr, get, memq, nth, put},
+    lists::{assoc, assq, car, cdr, delq, get, list, member, memq, nth, put},
     lists::{LispCons, LispConsCircularChecks, LispConsEndChecks},
     multibyte::LispStringRef,
     obarray::loadhist_attach,
@@ -22,19 +25,24 @@ use crate::{
     remacs_sys::{
         backtrace_debug_on_exit, build_string, call_debugger, check_cons_list, do_debug_on_call,
         do_one_unbind, eval_sub, funcall_lambda, funcall_subr, globals, grow_specpdl,
-        internal_catch, internal_lisp_condition_case, list2, maybe_gc, maybe_quit,
-        record_in_backtrace, record_unwind_save_match_data, signal_or_quit, specbind, COMPILEDP,
-        MODULE_FUNCTIONP,
+        internal_catch, internal_condition_case_n, internal_lisp_condition_case, list2, maybe_gc,
+        maybe_quit, record_in_backtrace, record_unwind_save_match_data, signal_or_quit, specbind,
+        COMPILEDP, MODULE_FUNCTIONP,
     },
-    remacs_sys::{pvec_type, EmacsInt, Lisp_Compiled, Set_Internal_Bind},
-    remacs_sys::{Fapply, Fdefault_value, Fload},
+    remacs_sys::{pvec_type, EmacsDouble, EmacsInt, Lisp_Compiled, Set_Internal_Bind},
+    remacs_sys::{Fapply, Fdefault_value, Fdelete, Fkill_local_variable, Fload},
     remacs_sys::{
-        QCdocumentation, Qautoload, Qclosure, Qerror, Qexit, Qfunction, Qinteractive,
-        Qinteractive_form, Qinternal_interpreter_environment, Qinvalid_function, Qlambda, Qmacro,
-        Qnil, Qrisky_local_variable, Qsetq, Qt, Qunbound, Qvariable_documentation, Qvoid_function,
+        QCdocumentation, Qautoload, Qclosure, Qerror, Qerror_conditions, Qerror_message, Qexit,
+        Qfunction, Qhook__depth_alist, Qinteractive, Qinteractive_form,
+        Qinternal_interpreter_environment, Qinvalid_function, Qlambda, Qmacro, Qnil,
+        Qpermanent_local, Qpermanent_local_hook, Qrisky_local_variable, Qsetq, Qt, Qunbound,
+        Qvariable_documentation, Qvoid_function,
     },
     remacs_sys::{Vautoload_queue, Vrun_hooks},
-    symbols::{fboundp, symbol_function, LispSymbolRef},
+    symbols::{
+        boundp, fboundp, local_variable_if_set_p, local_variable_p, symbol_function, symbol_value,
+        LispSymbolRef,
+    },
     threads::{c_specpdl_index, ThreadState},
     vectors::length,
 };
@@ -1079,6 +1087,209 @@ fn run_hook_with_args_internal(
     }
 }
 
+/// Make sure HOOK has both a value and a default value, setting
+/// either to nil if void.
+fn init_hook(hook: LispSymbolRef) {
+    if !boundp(hook) {
+        set(hook, Qnil);
+    }
+    if !default_boundp(hook) {
+        set_default(hook, Qnil);
+    }
+}
+
+/// Whether the hook value VALUE is a list containing the `t' that
+/// flags a local hook.
+fn hook_value_has_t(value: LispObject) -> bool {
+    value.is_cons() && memq(Qt, value).is_not_nil()
+}
+
+/// Record DEPTH as the depth of FUNCTION in the depth alist of HOOK.
+/// A depth of 0, the default, removes FUNCTION's entry.
+fn set_hook_depth(hook: LispSymbolRef, function: LispObject, depth: LispObject) {
+    let depth_alist = get(hook, Qhook__depth_alist);
+    let entry = assoc(function, depth_alist, Qnil);
+    if depth.any_to_float_or_error() == 0.0 {
+        if entry.is_not_nil() {
+            put(hook, Qhook__depth_alist, delq(entry, depth_alist));
+        }
+    } else if let Some(entry) = entry.as_cons() {
+        entry.set_cdr(depth);
+    } else {
+        put(
+            hook,
+            Qhook__depth_alist,
+            LispObject::cons(LispObject::cons(function, depth), depth_alist),
+        );
+    }
+}
+
+/// Stably sort the functions of HOOK-VALUE by their depth in
+/// DEPTH-ALIST, where functions without an entry have depth 0.
+fn sort_hook_by_depth(hook_value: LispObject, depth_alist: LispObject) -> LispObject {
+    let depth_of = |function| {
+        assq(function, depth_alist)
+            .as_cons()
+            .map_or(0.0, |entry| entry.cdr().any_to_float_or_error())
+    };
+    let mut functions: Vec<(EmacsDouble, LispObject)> = hook_value
+        .iter_cars(LispConsEndChecks::off, LispConsCircularChecks::off)
+        .map(|function| (depth_of(function), function))
+        .collect();
+    functions.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+    let functions: Vec<LispObject> = functions.into_iter().map(|(_, f)| f).collect();
+    list(&functions)
+}
+
+/// Add to the value of HOOK the function FUNCTION.
+/// FUNCTION is not added if already present.
+///
+/// The place where the function is added depends on the DEPTH
+/// parameter.  DEPTH defaults to 0.  By convention, it should be
+/// a number between -100 and 100 where 100 means that the function
+/// should be at the very end of the list, whereas -100 means that
+/// the function should always come first.
+/// Since nothing is \"always\" true, don't use 100 nor -100.
+/// When two functions have the same depth, the new one gets added after the
+/// old one if depth is strictly positive and before otherwise.
+///
+/// For backward compatibility reasons, a symbol other than nil is
+/// interpreted as a DEPTH of 90.
+///
+/// The optional fourth argument, LOCAL, if non-nil, says to modify
+/// the hook's buffer-local value rather than its global value.
+/// This makes the hook buffer-local, and it makes t a member of the
+/// buffer-local value.  That acts as a flag to run the hook
+/// functions of the global value as well as in the local value.
+///
+/// HOOK should be a symbol, and FUNCTION may be any valid function.  If
+/// HOOK is void, it is first set to nil.  If HOOK's value is a single
+/// function, it is changed to a list of functions.
+#[lisp_fn(min = "2")]
+pub fn add_hook(hook: LispSymbolRef, function: LispObject, depth: LispObject, local: bool) {
+    init_hook(hook);
+    let depth = if depth.is_number() {
+        depth
+    } else if depth.is_nil() {
+        LispObject::from(0)
+    } else {
+        LispObject::from(90)
+    };
+    let append = depth.any_to_float_or_error() > 0.0;
+
+    let mut local = local;
+    if local {
+        if !local_variable_if_set_p(hook, LispBufferOrCurrent::Current) {
+            set(make_local_variable(hook), list!(Qt));
+        }
+    } else if !hook_value_has_t(symbol_value(hook)) {
+        // Detect the case where make-local-variable was used on a hook
+        // and do what we used to do.
+        local = true;
+    }
+
+    let mut hook_value = if local {
+        symbol_value(hook)
+    } else {
+        unsafe { Fdefault_value(hook.into()) }
+    };
+    // If the hook value is a single function, turn it into a list.
+    if !hook_value.is_list() || FUNCTIONP(hook_value) {
+        hook_value = list!(hook_value);
+    }
+
+    // Do the actual addition if necessary.
+    let mut function = function;
+    if member(function, hook_value).is_nil() {
+        if function.is_string() {
+            function = purecopy(function);
+        }
+        if get(hook, Qhook__depth_alist).is_not_nil() || depth.any_to_float_or_error() != 0.0 {
+            set_hook_depth(hook, function, depth);
+        }
+        hook_value = if append {
+            append(&mut [hook_value, list!(function)])
+        } else {
+            LispObject::cons(function, hook_value)
+        };
+        let depth_alist = get(hook, Qhook__depth_alist);
+        if depth_alist.is_not_nil() {
+            hook_value = sort_hook_by_depth(hook_value, depth_alist);
+        }
+    }
+
+    if local {
+        // If HOOK isn't a permanent local, but FUNCTION wants to
+        // survive a change of modes, mark HOOK as partially permanent.
+        if let Some(function) = function.as_symbol() {
+            if get(function, Qpermanent_local_hook).is_not_nil()
+                && get(hook, Qpermanent_local).is_nil()
+            {
+                put(hook, Qpermanent_local, Qpermanent_local_hook);
+            }
+        }
+        set(hook, hook_value);
+    } else {
+        set_default(hook, hook_value);
+    }
+}
+
+/// Remove from the value of HOOK the function FUNCTION.
+/// HOOK should be a symbol, and FUNCTION may be any valid function.  If
+/// FUNCTION isn't the value of HOOK, or, if FUNCTION doesn't appear in the
+/// list of hooks to run in HOOK, then nothing is done.  See `add-hook'.
+///
+/// The optional third argument, LOCAL, if non-nil, says to modify
+/// the hook's buffer-local value rather than its default value.
+#[lisp_fn(min = "2")]
+pub fn remove_hook(hook: LispSymbolRef, function: LispObject, local: bool) {
+    init_hook(hook);
+    let has_local = local_variable_p(hook, LispBufferOrCurrent::Current);
+    // Do nothing if LOCAL is t but this hook has no local binding.
+    if local && !has_local {
+        return;
+    }
+    // Detect the case where make-local-variable was used on a hook
+    // and do what we used to do.
+    let local = local || (has_local && !hook_value_has_t(symbol_value(hook)));
+
+    let mut hook_value = if local {
+        symbol_value(hook)
+    } else {
+        unsafe { Fdefault_value(hook.into()) }
+    };
+    // Remove the function, for both the list and the non-list cases.
+    let removed = if !hook_value.is_list() || car(hook_value).eq(Qlambda) {
+        let removed = equal(hook_value, function);
+        if removed {
+            hook_value = Qnil;
+        }
+        removed
+    } else {
+        let removed = member(function, hook_value).is_not_nil();
+        if removed {
+            hook_value = unsafe { Fdelete(function, copy_sequence(hook_value)) };
+        }
+        removed
+    };
+    if removed {
+        // Drop FUNCTION's depth so the depth alist doesn't keep growing.
+        let depth_alist = get(hook, Qhook__depth_alist);
+        let entry = assoc(function, depth_alist, Qnil);
+        if entry.is_not_nil() {
+            put(hook, Qhook__depth_alist, delq(entry, depth_alist));
+        }
+    }
+
+    if !local {
+        set_default(hook, hook_value);
+    } else if equal(hook_value, list!(Qt)) {
+        unsafe { Fkill_local_variable(hook.into()) };
+    } else {
+        set(hook, hook_value);
+    }
+}
+
 enum LispFun {
     SubrFun(LispSubrRef),
     LambdaFun(LispObject),
@@ -1216,6 +1427,56 @@ pub fn funcall(args: &mut [LispObject]) -> LispObject {
     val
 }
 
+/// Call FUNCTION with ARGS, catching any error it signals.  Return the
+/// value of the call, or `Err' with the error data (ERROR-SYMBOL . DATA)
+/// if it signaled instead of returning.
+pub fn safe_call(function: LispObject, args: &[LispObject]) -> Result<LispObject, LispObject> {
+    let mut call_args = Vec::with_capacity(args.len() + 1);
+    call_args.push(function);
+    call_args.extend_from_slice(args);
+
+    let val = unsafe {
+        internal_condition_case_n(
+            Some(Ffuncall),
+            call_args.len() as libc::ptrdiff_t,
+            call_args.as_mut_ptr(),
+            Qerror,
+            Some(stash_error_data),
+        )
+    };
+
+    if val.eq(Qunbound) {
+        Err(call_args[0])
+    } else {
+        Ok(val)
+    }
+}
+
+/// Call FUNCTION with the single argument ARG, catching any error it
+/// signals.  See `safe_call'.
+pub fn safe_call1(function: LispObject, arg: LispObject) -> Result<LispObject, LispObject> {
+    safe_call(function, &[arg])
+}
+
+/// Error handler for `safe_call'.  No call can return `Qunbound', so
+/// return that to flag the error, stashing the error data in the slot
+/// of the called function.
+extern "C" fn stash_error_data(
+    err: LispObject,
+    _nargs: libc::ptrdiff_t,
+    args: *mut LispObject,
+) -> LispObject {
+    unsafe { *args = err };
+    Qunbound
+}
+
+/// Call FUNCTION with no arguments, returning its value.
+/// If FUNCTION signals an error, return nil instead.
+#[lisp_fn(name = "internal--ignore-errors")]
+pub fn internal_ignore_errors(function: LispObject) -> LispObject {
+    safe_call(function, &[]).unwrap_or(Qnil)
+}
+
 /// Pop and execute entries from the unwind-protect stack until the
 /// depth COUNT is reached. Return VALUE.
 #[no_mangle]
@@ -1306,6 +1567,50 @@ pub fn signal(error_symbol: LispObject, data: LispObject) -> ! {
     }
 }
 
+/// Define NAME as a new error signal.
+/// MESSAGE is a string that will be output to the echo area if such an error
+/// is signaled without being caught by a `condition-case'.
+/// PARENT is either a signal or a list of signals from which it inherits.
+/// Defaults to `error'.
+#[lisp_fn(min = "2")]
+pub fn define_error(name: LispSymbolRef, message: LispObject, parent: LispObject) {
+    let parent = if parent.is_nil() { Qerror } else { parent };
+    let mut conditions = vec![name.into()];
+    let mut add_condition = |condition| {
+        if !conditions.iter().any(|&c| equal(c, condition)) {
+            conditions.push(condition);
+        }
+    };
+
+    if parent.is_cons() {
+        for parent in parent.iter_cars(LispConsEndChecks::on, LispConsCircularChecks::on) {
+            let parent_symbol = LispSymbolRef::from(parent);
+            let parent_conditions = get(parent_symbol, Qerror_conditions);
+            if parent_conditions.is_nil() {
+                error!("Unknown signal `{}'", parent_symbol.symbol_name());
+            }
+            add_condition(parent);
+            for condition in
+                parent_conditions.iter_cars(LispConsEndChecks::off, LispConsCircularChecks::off)
+            {
+                add_condition(condition);
+            }
+        }
+    } else {
+        add_condition(parent);
+        for condition in get(parent.into(), Qerror_conditions)
+            .iter_cars(LispConsEndChecks::off, LispConsCircularChecks::off)
+        {
+            add_condition(condition);
+        }
+    }
+
+    put(name, Qerror_conditions, list(&conditions));
+    if message.is_not_nil() {
+        put(name, Qerror_message, message);
+    }
+}
+
 /// Regain control when an error is signaled.
 /// Executes BODYFORM and returns its value if no error happens.
 /// Each element of HANDLERS looks like (CONDITION-NAME BODY...)
@@ -1440,3 +1745,14 @@ pub fn defvar(args: LispCons) -> LispObject {
 }
 
 include!(concat!(env!("OUT_DIR"), "/eval_exports.rs"));
+
+#[test]
+fn test_safe_call() {
+    // The mocked internal_condition_case_n signals for `error' and
+    // returns t for any other function.
+    assert!(safe_call(Qt, &[]).map_or(false, |val| val.eq(Qt)));
+    assert!(safe_call1(Qt, Qnil).map_or(false, |val| val.eq(Qt)));
+
+    let err = safe_call1(Qerror, Qnil).unwrap_err();
+    assert!(err.eq(Qerror));
+}
@@ -287,6 +287,17 @@ macro_rules! declare_GC_protected_static {
     };
 }
 
+/// Declare a module-level Lisp variable backed by the static `$var`.
+/// Like `declare_GC_protected_static!`, build.rs looks for this marker
+/// and arranges for `rust_init_syms` to register the variable with
+/// `defvar_lisp` and set it to `$value`.  A doc comment placed before
+/// the marker becomes the variable's documentation.
+macro_rules! declare_lisp_variable {
+    ($var: ident, $lisp_name: expr, $value: expr) => {
+        static mut $var: LispObject = crate::remacs_sys::Qnil;
+    };
+}
+
 macro_rules! verify_lisp_type {
     ($obj:expr, Qarrayp) => {
         if !$obj.is_array() {
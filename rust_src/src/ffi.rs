@@ -1,5 +1,15 @@
 //! Module that is used for FFI exports.These calls should NOT be used in Rust directly.
-use crate::{data, keyboard, lisp::LispObject, lists, math, remacs_sys::Lisp_Window, windows};
+use std::ptr;
+
+use libc::c_char;
+
+use crate::{
+    data, keyboard, keymap,
+    lisp::LispObject,
+    lists, math,
+    remacs_sys::{EmacsInt, Lisp_Window},
+    windows,
+};
 
 #[no_mangle]
 pub extern "C" fn circular_list(obj: LispObject) -> ! {
@@ -39,3 +49,11 @@ pub extern "C" fn window_wants_mode_line(window: *mut Lisp_Window) -> bool {
 pub extern "C" fn window_wants_header_line(window: *mut Lisp_Window) -> bool {
     windows::window_wants_header_line(windows::LispWindowRef::new(window))
 }
+
+#[no_mangle]
+pub unsafe extern "C" fn push_key_description(ch: EmacsInt, p: *mut c_char) -> *mut c_char {
+    let mut buf = Vec::new();
+    keymap::push_key_description(ch, &mut buf);
+    ptr::copy_nonoverlapping(buf.as_ptr() as *const c_char, p, buf.len());
+    p.add(buf.len())
+}
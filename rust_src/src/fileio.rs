@@ -1,28 +1,57 @@
 //! Functions to deal with files
 use errno::{set_errno, Errno};
 
-use std::path;
+use std::fs::{self, DirBuilder, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{self, PathBuf};
 
+#[cfg(unix)]
+use std::{
+    ffi::OsStr,
+    os::unix::ffi::OsStrExt,
+    os::unix::fs::{DirBuilderExt, OpenOptionsExt},
+};
+
+use remacs_lib::fill_random_name;
 use remacs_macros::lisp_fn;
 
 use crate::{
-    coding::encode_file_name,
+    buffers::validate_region_rust,
+    coding::{decode_coding_string, encode_file_name},
+    data::set,
+    editfns::insert_slice,
     errno::errno,
+    eval::unbind_to,
+    fns::concat,
     lisp::LispObject,
-    lists::LispCons,
+    lists::{get, put, LispCons, LispConsCircularChecks, LispConsEndChecks},
     math::{arithcompare, ArithComparison},
-    multibyte::LispStringRef,
+    multibyte::{Codepoint, LispStringRef, MAX_MULTIBYTE_LENGTH},
+    remacs_sys::{
+        check_executable, check_existing, expand_and_dir_to_file, file_directory_p,
+        file_name_case_insensitive_p, globals, make_specified_string, make_unibyte_string,
+        maybe_quit, record_unwind_save_match_data, report_file_errno, set_point_both, specbind,
+        EmacsInt,
+    },
     remacs_sys::{
-        check_executable, check_existing, empty_unibyte_string, expand_and_dir_to_file,
-        file_directory_p, file_name_absolute_p, file_name_case_insensitive_p, report_file_errno,
+        Fexpand_file_name, Ffile_name_directory, Ffind_file_name_handler, Finsert_file_contents,
+        Fregexp_quote, Fsubstring, Fwrite_region,
     },
-    remacs_sys::{Fexpand_file_name, Ffind_file_name_handler, Fmake_temp_file_internal},
     remacs_sys::{
-        Qfile_directory_p, Qfile_executable_p, Qfile_exists_p, Qfile_name_case_insensitive_p, Qnil,
+        Qcase_fold_search, Qdirectory_file_name, Qexcl, Qfile_directory_p, Qfile_executable_p,
+        Qfile_exists_p, Qfile_name_as_directory, Qfile_name_case_insensitive_p, Qfile_readable_p,
+        Qfile_writable_p, Qinhibit_file_name_operation, Qinsert_file_contents, Qnil,
+        Qno_conversion, Qunbound, Qutf_8, Qwrite_region, Qwrite_region_annotate_functions,
     },
-    threads::ThreadState,
+    search::{match_beginning, match_end, string_match},
+    strings::multibyte_string_p,
+    symbols::LispSymbolRef,
+    threads::{c_specpdl_index, ThreadState},
 };
 
+#[cfg(windows)]
+use crate::casefiddle::downcase;
+
 /// Signal a file-access failure that set errno.  STRING describes the
 /// failure, NAME the file involved.  When invoking this function, take
 /// care to not use arguments such as build_string ("foo") that involve
@@ -47,8 +76,7 @@ pub fn directory_name_p(name: LispStringRef) -> bool {
         return false;
     }
 
-    let b = name.byte_at(name.len_bytes() - 1);
-    b as char == path::MAIN_SEPARATOR
+    is_directory_sep(name.byte_at(name.len_bytes() - 1))
 }
 
 /// Clear any record of a recent auto-save failure in the current buffer.
@@ -93,11 +121,318 @@ pub fn file_name_case_insensitive_p_lisp(filename: LispStringRef) -> bool {
     }
 }
 
+/// Return true if B separates directories in a file name.
+#[cfg(not(windows))]
+fn is_directory_sep(b: u8) -> bool {
+    b == b'/'
+}
+
+#[cfg(windows)]
+fn is_directory_sep(b: u8) -> bool {
+    b == b'/' || b == b'\\'
+}
+
+/// Return true if B can end the directory part of a file name: a
+/// directory separator, or the colon after a drive letter on Windows.
+#[cfg(not(windows))]
+fn is_any_sep(b: u8) -> bool {
+    is_directory_sep(b)
+}
+
+#[cfg(windows)]
+fn is_any_sep(b: u8) -> bool {
+    is_directory_sep(b) || b == b':'
+}
+
+/// Return true if NAME is a drive root such as "c:/", whose final
+/// separator must be kept.
+#[cfg(not(windows))]
+fn is_drive_root(_name: &[u8]) -> bool {
+    false
+}
+
+#[cfg(windows)]
+fn is_drive_root(name: &[u8]) -> bool {
+    name.len() > 2 && name[name.len() - 2] == b':'
+}
+
+/// Turn the backslashes in NAME into the slashes Emacs uses internally,
+/// like `dostounix_filename'.
+#[cfg(not(windows))]
+fn dos_to_unix_filename(_name: &mut [u8]) {}
+
+#[cfg(windows)]
+fn dos_to_unix_filename(name: &mut [u8]) {
+    for b in name.iter_mut().filter(|b| **b == b'\\') {
+        *b = b'/';
+    }
+}
+
+/// Downcase NAME if `w32-downcase-file-names' is non-nil.
+#[cfg(not(windows))]
+fn w32_downcase_file_name(name: LispStringRef) -> LispStringRef {
+    name
+}
+
+#[cfg(windows)]
+fn w32_downcase_file_name(name: LispStringRef) -> LispStringRef {
+    if unsafe { globals.Vw32_downcase_file_names }.is_nil() {
+        name
+    } else {
+        downcase(name.into()).into()
+    }
+}
+
+/// Call HANDLER for OPERATION on NAME, which must give back a string.
+fn call_file_name_handler(
+    handler: LispObject,
+    operation: LispObject,
+    name: LispStringRef,
+) -> LispObject {
+    let handled_name = call!(handler, operation, name.into());
+    if !handled_name.is_string() {
+        error!("Invalid handler in `file-name-handler-alist'");
+    }
+    handled_name
+}
+
+/// Return a string representing the file name FILE interpreted as a directory.
+/// This operation exists because a directory is also a file, but its name as
+/// a directory is different from its name as a file.
+/// The result can be used as the value of `default-directory'
+/// or passed as second argument to `expand-file-name'.
+/// For a Unix-syntax file name, just appends a slash unless a trailing slash
+/// is already present.
+#[lisp_fn]
+pub fn file_name_as_directory(file: LispStringRef) -> LispObject {
+    // If the file name has special constructs in it,
+    // call the corresponding file handler.
+    let handler = find_file_name_handler(file, Qfile_name_as_directory);
+    if handler.is_not_nil() {
+        return call_file_name_handler(handler, Qfile_name_as_directory, file);
+    }
+
+    let file = w32_downcase_file_name(file);
+
+    let name = file.as_slice();
+    let mut dir = if name.is_empty() {
+        b".".to_vec()
+    } else {
+        name.to_vec()
+    };
+    if !dir.last().map_or(false, |&b| is_any_sep(b)) {
+        dir.push(b'/');
+    }
+    dos_to_unix_filename(&mut dir);
+
+    if dir == name {
+        return file.into();
+    }
+
+    unsafe {
+        make_specified_string(
+            dir.as_ptr() as *const libc::c_char,
+            -1,
+            dir.len() as isize,
+            file.is_multibyte(),
+        )
+    }
+}
+
+/// Returns the file name of the directory named DIRECTORY.
+/// This is the name of the file that holds the data for the directory DIRECTORY.
+/// This operation exists because a directory is also a file, but its name as
+/// a directory is different from its name as a file.
+/// In Unix-syntax, this function just removes the final slash.
+#[lisp_fn]
+pub fn directory_file_name(directory: LispStringRef) -> LispObject {
+    // If the file name has special constructs in it,
+    // call the corresponding file handler.
+    let handler = find_file_name_handler(directory, Qdirectory_file_name);
+    if handler.is_not_nil() {
+        return call_file_name_handler(handler, Qdirectory_file_name, directory);
+    }
+
+    let directory = w32_downcase_file_name(directory);
+
+    // Just remove any final slashes.  However, if they are all slashes,
+    // leave "/" and "//" alone, and treat "///" and longer as if they
+    // were "/".
+    let name = directory.as_slice();
+    let mut len = name.len();
+    if !(len == 2 && is_directory_sep(name[0])) {
+        while len > 1 && !is_drive_root(&name[..len]) && is_directory_sep(name[len - 1]) {
+            len -= 1;
+        }
+    }
+    let mut dir = name[..len].to_vec();
+    dos_to_unix_filename(&mut dir);
+
+    if dir == name {
+        return directory.into();
+    }
+
+    unsafe {
+        make_specified_string(
+            dir.as_ptr() as *const libc::c_char,
+            -1,
+            dir.len() as isize,
+            directory.is_multibyte(),
+        )
+    }
+}
+
+/// Return the value of the variable SYMBOL, or nil while files.el,
+/// which defines it, has not been loaded yet.
+fn lisp_variable_value(symbol: LispObject) -> LispObject {
+    let value = unsafe { LispSymbolRef::from(symbol).find_value() };
+    if value == Qunbound {
+        Qnil
+    } else {
+        value
+    }
+}
+
+/// Return FILENAME with the abbreviations in `directory-abbrev-alist'
+/// applied to it.
+fn apply_directory_abbrevs(mut filename: LispObject) -> LispObject {
+    let abbrevs = lisp_variable_value(Qdirectory_abbrev_alist);
+    for elt in abbrevs.iter_cars(LispConsEndChecks::on, LispConsCircularChecks::on) {
+        let (regexp, abbrev): (LispObject, LispObject) = elt.into();
+        if string_match(regexp, filename, Qnil).is_not_nil() {
+            let rest = unsafe { Fsubstring(filename, match_end(LispObject::from(0)), Qnil) };
+            filename = concat(&mut [abbrev, rest]);
+        }
+    }
+    filename
+}
+
+/// Return the regexp in `abbreviated-home-dir', computing and caching
+/// it first if the variable is nil.
+fn abbreviated_home_dir() -> LispObject {
+    let cached = lisp_variable_value(Qabbreviated_home_dir);
+    if cached.is_not_nil() {
+        return cached;
+    }
+
+    let home: LispObject = expand_file_name(LispObject::from("~").into(), None).into();
+    put(Qabbreviated_home_dir.into(), Qhome, home);
+
+    // Abbreviate the home directory itself with a regexp that matches
+    // nothing, so that only `directory-abbrev-alist' applies.
+    let count = c_specpdl_index();
+    unsafe { specbind(Qabbreviated_home_dir, LispObject::from("$foo")) };
+    let abbreviated = unbind_to(count, abbreviate_file_name(home.into()));
+
+    let mut regexp = concat(&mut [
+        LispObject::from("\\`"),
+        unsafe { Fregexp_quote(abbreviated) },
+        LispObject::from("\\(/\\|\\'\\)"),
+    ]);
+    // Depending on whether the home directory includes non-ASCII
+    // characters, the regexp could be unibyte, in which case it
+    // needs decoding.
+    if !multibyte_string_p(regexp) {
+        let coding_system = if cfg!(windows) {
+            Qutf_8
+        } else {
+            unsafe { globals.Vlocale_coding_system }
+        };
+        regexp = decode_coding_string(regexp, coding_system, Qnil, Qnil);
+    }
+    set(Qabbreviated_home_dir.into(), regexp)
+}
+
+/// Return a version of FILENAME shortened using `directory-abbrev-alist'.
+/// This also substitutes "~" for the user's home directory (unless the
+/// home directory is a root directory) and removes automounter prefixes
+/// (see the variable `automount-dir-prefix').
+///
+/// When this function is first called, it caches the user's home
+/// directory as a regexp in `abbreviated-home-dir', and reuses it
+/// afterwards (so long as the home directory does not change;
+/// if you want to permanently change your home directory after having
+/// started Emacs, set `abbreviated-home-dir' to nil so it will be recalculated).
+#[lisp_fn]
+pub fn abbreviate_file_name(filename: LispStringRef) -> LispObject {
+    let count = c_specpdl_index();
+    unsafe { record_unwind_save_match_data() };
+
+    let mut filename: LispObject = filename.into();
+
+    // Get rid of the prefixes added by the automounter.
+    let automount_prefix = lisp_variable_value(Qautomount_dir_prefix);
+    if automount_prefix.is_not_nil() && string_match(automount_prefix, filename, Qnil).is_not_nil()
+    {
+        let start = match_end(LispObject::from(0)).as_fixnum_or_error() - 1;
+        let unmounted = unsafe { Fsubstring(filename, start.into(), Qnil) };
+        let dir = unsafe { Ffile_name_directory(unmounted) };
+        if dir.is_not_nil() && file_exists_p(dir.into()) {
+            filename = unmounted;
+        }
+    }
+
+    // Avoid treating /home/foo as /home/Foo during `~' substitution.
+    let ignore_case = file_name_case_insensitive_p_lisp(filename.into());
+    unsafe { specbind(Qcase_fold_search, ignore_case.into()) };
+
+    filename = apply_directory_abbrevs(filename);
+
+    // If FILENAME starts with the abbreviated homedir, and ~ hasn't
+    // changed since `abbreviated-home-dir' was set, make it start with
+    // `~' instead.  If ~ has changed, ignore the cached regexp rather
+    // than invalidating it, as a change in HOME is likely temporary.
+    let home_regexp = abbreviated_home_dir();
+    if string_match(home_regexp, filename, Qnil).is_not_nil() {
+        let end = match_end(LispObject::from(0));
+        let name: LispStringRef = filename.into();
+        let name = name.as_slice();
+
+        // If the home dir is just /, don't change it.  Windows root
+        // directories can come with a drive letter.
+        let is_root = (end.as_fixnum_or_error() == 1 && is_directory_sep(name[0]))
+            || match name {
+                [drive, b':', b'/'] => {
+                    cfg!(windows) && (drive.is_ascii_lowercase() || (b'A'..=b'`').contains(drive))
+                }
+                _ => false,
+            };
+        let home: LispObject = expand_file_name(LispObject::from("~").into(), None).into();
+        let home_unchanged = get(Qabbreviated_home_dir.into(), Qhome).equal(home);
+
+        if !is_root && home_unchanged {
+            let separator = match_beginning(LispObject::from(1));
+            let separator = if separator.is_nil() {
+                Qnil
+            } else {
+                unsafe { Fsubstring(filename, separator, match_end(LispObject::from(1))) }
+            };
+            let rest = unsafe { Fsubstring(filename, end, Qnil) };
+            filename = concat(&mut [LispObject::from("~"), separator, rest]);
+        }
+    }
+
+    unbind_to(count, filename)
+}
+
+def_lisp_sym!(Qdirectory_abbrev_alist, "directory-abbrev-alist");
+def_lisp_sym!(Qautomount_dir_prefix, "automount-dir-prefix");
+def_lisp_sym!(Qabbreviated_home_dir, "abbreviated-home-dir");
+def_lisp_sym!(Qhome, "home");
+
 /// Return t if FILENAME is an absolute file name or starts with `~'.
 /// On Unix, absolute file names start with `/'.
-#[lisp_fn(name = "file-name-absolute-p", c_name = "file_name_absolute_p")]
-pub fn file_name_absolute_p_lisp(filename: LispStringRef) -> bool {
-    unsafe { file_name_absolute_p(filename.const_data_ptr() as *const libc::c_char) }
+#[lisp_fn]
+pub fn file_name_absolute_p(filename: LispStringRef) -> bool {
+    let name = filename.as_slice();
+    match name.first() {
+        Some(b'~') => true,
+        Some(&b) if is_directory_sep(b) => true,
+        Some(drive) if cfg!(windows) && name.len() > 2 => {
+            drive.is_ascii_alphabetic() && name[1] == b':' && is_directory_sep(name[2])
+        }
+        _ => false,
+    }
 }
 
 /// Return t if file FILENAME exists (whether or not you can read it.)
@@ -121,6 +456,94 @@ pub fn file_exists_p(filename: LispStringRef) -> bool {
     }
 }
 
+/// Return true if the file FILENAME, already encoded, can be accessed
+/// according to AMODE.  On failure, return false and set errno.
+fn check_access(filename: LispStringRef, amode: libc::c_int) -> bool {
+    unsafe {
+        libc::faccessat(
+            libc::AT_FDCWD,
+            filename.const_sdata_ptr(),
+            amode,
+            libc::AT_EACCESS,
+        ) == 0
+    }
+}
+
+/// Return true if file FILENAME, already encoded, exists and can be
+/// accessed according to AMODE, which should include W_OK.  On failure,
+/// return false and set errno.
+#[cfg(not(target_os = "cygwin"))]
+fn check_writable(filename: LispStringRef, amode: libc::c_int) -> bool {
+    check_access(filename, amode)
+}
+
+#[cfg(target_os = "cygwin")]
+fn check_writable(filename: LispStringRef, amode: libc::c_int) -> bool {
+    if check_access(filename, amode) {
+        return true;
+    }
+
+    // faccessat may have failed because Cygwin couldn't determine the
+    // file's UID or GID; if so, report success.
+    let faccessat_errno = errno();
+    let mut st: libc::stat = unsafe { std::mem::zeroed() };
+    if unsafe { libc::stat(filename.const_sdata_ptr(), &mut st) } < 0 {
+        return false;
+    }
+    let res = st.st_uid == !0 || st.st_gid == !0;
+    set_errno(faccessat_errno);
+    res
+}
+
+/// Return t if file FILENAME exists and you can read it.
+/// See also `file-exists-p' and `file-attributes'.
+#[lisp_fn]
+pub fn file_readable_p(filename: LispStringRef) -> bool {
+    let absname = expand_file_name(filename, None);
+
+    // If the file name has special constructs in it,
+    // call the corresponding file handler.
+    let handler = find_file_name_handler(absname, Qfile_readable_p);
+
+    if handler.is_not_nil() {
+        call!(handler, Qfile_readable_p, absname.into()).into()
+    } else {
+        check_access(encode_file_name(absname), libc::R_OK)
+    }
+}
+
+/// Return t if file FILENAME can be written or created by you.
+#[lisp_fn]
+pub fn file_writable_p(filename: LispStringRef) -> bool {
+    let absname = expand_file_name(filename, None);
+
+    // If the file name has special constructs in it,
+    // call the corresponding file handler.
+    let handler = find_file_name_handler(absname, Qfile_writable_p);
+
+    if handler.is_not_nil() {
+        return call!(handler, Qfile_writable_p, absname.into()).into();
+    }
+
+    if check_writable(encode_file_name(absname), libc::W_OK) {
+        return true;
+    }
+    if errno().0 != libc::ENOENT {
+        return false;
+    }
+
+    // A file that does not exist yet can be created in a directory we
+    // can write to.
+    let dir: LispStringRef = unsafe { Ffile_name_directory(absname.into()) }.into();
+    if cfg!(windows) {
+        // The read-only attribute of the parent directory doesn't affect
+        // whether a file or directory can be created within it.
+        unsafe { file_directory_p(encode_file_name(dir).into()) }
+    } else {
+        check_writable(encode_file_name(dir), libc::W_OK | libc::X_OK)
+    }
+}
+
 /// Return t if FILENAME names an existing directory.
 /// Symbolic links to directories count as directories.
 /// See `file-symlink-p' to distinguish symlinks.
@@ -169,6 +592,33 @@ pub fn find_file_name_handler(filename: LispStringRef, operation: LispObject) ->
     unsafe { Ffind_file_name_handler(filename.into(), operation) }
 }
 
+/// Convert the Lisp file name PATH to a `PathBuf`, encoding it for the
+/// file system.
+#[cfg(unix)]
+pub fn to_path_buf(path: LispStringRef) -> PathBuf {
+    let path = encode_file_name(path);
+    PathBuf::from(OsStr::from_bytes(path.as_slice()))
+}
+
+#[cfg(windows)]
+pub fn to_path_buf(path: LispStringRef) -> PathBuf {} // TODO (may have to return a Result)
+
+/// Signal a `file-error' for the I/O error ERR.  STRING describes the
+/// failure, NAME the file involved.
+fn report_io_error(string: &'static [u8], name: LispStringRef, err: &io::Error) -> ! {
+    unsafe {
+        report_file_errno(
+            string.as_ptr() as *const libc::c_char,
+            name.into(),
+            err.raw_os_error().unwrap_or(0),
+        )
+    };
+    unreachable!()
+}
+
+/// How many random names `make-temp-name' tries before giving up.
+const TEMP_NAME_ATTEMPTS: usize = 62 * 62 * 62;
+
 /// Generate temporary file name (string) starting with PREFIX (a string).
 ///
 /// This function tries to choose a name that has no existing file.
@@ -180,14 +630,356 @@ pub fn find_file_name_handler(filename: LispStringRef, operation: LispObject) ->
 /// For that reason, you should normally use `make-temp-file' instead.
 #[lisp_fn]
 pub fn make_temp_name(prefix: LispStringRef) -> LispObject {
+    let mut name = prefix.as_slice().to_vec();
+    name.extend_from_slice(b"XXXXXX");
+    let unique = name.len() - 6;
+
+    for _ in 0..TEMP_NAME_ATTEMPTS {
+        fill_random_name(&mut name[unique..]);
+        let candidate: LispStringRef = unsafe {
+            make_specified_string(
+                name.as_ptr() as *const libc::c_char,
+                -1,
+                name.len() as isize,
+                prefix.is_multibyte(),
+            )
+        }
+        .into();
+
+        match fs::symlink_metadata(to_path_buf(candidate)) {
+            Ok(_) => continue,
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => return candidate.into(),
+            Err(err) => report_io_error(b"Creating file name with prefix\0", prefix, &err),
+        }
+    }
+
+    let err = io::Error::from_raw_os_error(libc::EEXIST);
+    report_io_error(b"Creating file name with prefix\0", prefix, &err)
+}
+
+/// Create the directory PATH, accessible only by its owner where the
+/// platform supports permission bits.
+fn create_private_dir(path: &path::Path) -> io::Result<()> {
+    let mut builder = DirBuilder::new();
+    #[cfg(unix)]
+    builder.mode(0o700);
+    builder.create(path)
+}
+
+/// Create the file PATH, failing if it already exists.  The file is
+/// accessible only by its owner where the platform supports permission
+/// bits.
+fn create_private_file(path: &path::Path) -> io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    options.open(path).map(drop)
+}
+
+/// Create a temporary file.
+/// The returned file name (created by appending some random characters at the end
+/// of PREFIX, and expanding against `temporary-file-directory' if necessary),
+/// is guaranteed to point to a newly created file.
+/// You can then use `write-region' to write new data into the file.
+///
+/// If DIR-FLAG is non-nil, create a new empty directory instead of a file.
+///
+/// If SUFFIX is non-nil, add that at the end of the file name.
+///
+/// If TEXT is a string, insert it into the new file; DIR-FLAG should be nil.
+/// Otherwise the file will be empty.
+#[lisp_fn(min = "1")]
+pub fn make_temp_file(
+    prefix: LispStringRef,
+    dir_flag: bool,
+    suffix: Option<LispStringRef>,
+    text: LispObject,
+) -> LispObject {
+    let tmpdir = unsafe { globals.Vtemporary_file_directory };
+    let name = prefix.as_slice();
+    let absolute_prefix: LispStringRef = if name.is_empty() || name == b"." || name == b".." {
+        concat(&mut [file_name_as_directory(tmpdir.into()), prefix.into()]).into()
+    } else {
+        expand_file_name(prefix, Some(tmpdir.into()))
+    };
+
+    if find_file_name_handler(absolute_prefix, Qwrite_region).is_not_nil() {
+        return call!(
+            Qfiles__make_magic_temp_file,
+            absolute_prefix.into(),
+            dir_flag.into(),
+            suffix.into(),
+            text
+        );
+    }
+
+    let message: &'static [u8] = if dir_flag {
+        b"Creating directory with prefix\0"
+    } else {
+        b"Creating file with prefix\0"
+    };
+    let suffix = suffix.map_or_else(|| LispObject::from(""), LispObject::from);
+
+    for _ in 0..TEMP_NAME_ATTEMPTS {
+        let mut unique = [0u8; 6];
+        fill_random_name(&mut unique);
+        let unique = unsafe { make_unibyte_string(unique.as_ptr() as *const libc::c_char, 6) };
+        let file: LispStringRef = concat(&mut [absolute_prefix.into(), unique, suffix]).into();
+
+        // Both creations fail if the name is already taken, so no one
+        // else can slip in between choosing the name and using it.
+        let created = if dir_flag {
+            create_private_dir(&to_path_buf(file))
+        } else {
+            create_private_file(&to_path_buf(file))
+        };
+
+        match created {
+            Ok(()) => {
+                if let Some(text) = text.as_string().filter(|t| t.len_bytes() != 0) {
+                    unsafe {
+                        Fwrite_region(text.into(), Qnil, file.into(), Qnil, Qnil, Qnil, Qnil)
+                    };
+                }
+                return file.into();
+            }
+            Err(ref err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => report_io_error(message, prefix, &err),
+        }
+    }
+
+    let err = io::Error::from_raw_os_error(libc::EEXIST);
+    report_io_error(message, prefix, &err)
+}
+
+def_lisp_sym!(Qfiles__make_magic_temp_file, "files--make-magic-temp-file");
+
+/// Open the file FILENAME for reading the bytes from BEG up to END.
+fn open_file_range(
+    filename: LispStringRef,
+    beg: Option<EmacsInt>,
+    end: Option<EmacsInt>,
+) -> io::Result<io::Take<File>> {
+    let mut file = File::open(to_path_buf(filename))?;
+    let beg = beg.unwrap_or(0) as u64;
+    file.seek(SeekFrom::Start(beg))?;
+    Ok(file.take(end.map_or(u64::MAX, |end| end as u64 - beg)))
+}
+
+/// Insert everything read from INPUT before point, each byte as a raw
+/// byte.  Return the number of characters inserted.
+fn insert_raw_bytes(mut input: impl Read) -> io::Result<isize> {
+    let multibyte = ThreadState::current_buffer_unchecked().multibyte_characters_enabled();
+    let mut chunk = [0; 16 * 1024];
+    let mut text = Vec::new();
+    let mut inserted = 0;
+
+    loop {
+        let nread = match input.read(&mut chunk) {
+            Ok(0) => return Ok(inserted),
+            Ok(nread) => nread,
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        let bytes = &chunk[..nread];
+
+        if multibyte {
+            text.clear();
+            let mut buf = [0; MAX_MULTIBYTE_LENGTH];
+            for &b in bytes {
+                let len = Codepoint::from_raw(b).write_to(&mut buf);
+                text.extend_from_slice(&buf[..len]);
+            }
+            insert_slice(&text);
+        } else {
+            insert_slice(bytes);
+        }
+
+        inserted += nread as isize;
+        unsafe { maybe_quit() };
+    }
+}
+
+/// Like `insert-file-contents', but only reads in the file literally.
+/// See `insert-file-contents' for an explanation of the parameters.
+/// A buffer may be modified in several ways after reading into the buffer,
+/// due to Emacs features such as format decoding, character code
+/// conversion, `find-file-hook', automatic uncompression, etc.
+///
+/// This function ensures that none of these modifications will take place.
+#[lisp_fn(min = "1")]
+pub fn insert_file_contents_literally(
+    filename: LispStringRef,
+    visit: LispObject,
+    beg: Option<EmacsInt>,
+    end: Option<EmacsInt>,
+    replace: LispObject,
+) -> LispObject {
+    if beg.map_or(false, |b| b < 0) || end.map_or(false, |e| e < beg.unwrap_or(0)) {
+        args_out_of_range!(beg, end);
+    }
+
+    let count = c_specpdl_index();
     unsafe {
-        Fmake_temp_file_internal(
-            prefix.into(),
-            LispObject::from(0),
-            empty_unibyte_string,
-            Qnil,
-        )
+        specbind(Qformat_alist, Qnil);
+        specbind(Qafter_insert_file_functions, Qnil);
+        specbind(Qcoding_system_for_read, Qno_conversion);
+        specbind(Qcoding_system_for_write, Qno_conversion);
+        specbind(
+            Qinhibit_file_name_handlers,
+            LispObject::cons(
+                Qjka_compr_handler,
+                LispObject::cons(
+                    Qimage_file_handler,
+                    LispObject::cons(Qepa_file_handler, globals.Vinhibit_file_name_handlers),
+                ),
+            ),
+        );
+        specbind(Qinhibit_file_name_operation, Qinsert_file_contents);
+    }
+
+    let absname = expand_file_name(filename, None);
+
+    // Visiting, replacing and magic file names all need the full
+    // machinery of `insert-file-contents'; the bindings above keep it
+    // from converting anything.
+    let result = if visit.is_not_nil()
+        || replace.is_not_nil()
+        || find_file_name_handler(absname, Qinsert_file_contents).is_not_nil()
+    {
+        unsafe { Finsert_file_contents(filename.into(), visit, beg.into(), end.into(), replace) }
+    } else {
+        let input = open_file_range(absname, beg, end)
+            .unwrap_or_else(|err| report_io_error(b"Opening input file\0", absname, &err));
+
+        let buffer = ThreadState::current_buffer_unchecked();
+        let (opoint, opoint_byte) = (buffer.pt, buffer.pt_byte);
+        let inserted = insert_raw_bytes(input);
+        unsafe { set_point_both(opoint, opoint_byte) };
+        let inserted =
+            inserted.unwrap_or_else(|err| report_io_error(b"Read error\0", absname, &err));
+        unsafe { globals.Vlast_coding_system_used = Qno_conversion };
+
+        list!(absname, inserted)
+    };
+
+    unbind_to(count, result)
+}
+
+/// Write the byte slices in PARTS to FILE, starting at OFFSET if that
+/// is given.
+fn write_file_bytes(file: &mut File, offset: Option<EmacsInt>, parts: &[&[u8]]) -> io::Result<()> {
+    if let Some(offset) = offset {
+        file.seek(SeekFrom::Start(offset as u64))?;
+    }
+    for part in parts {
+        file.write_all(part)?;
+    }
+    Ok(())
+}
+
+/// Like `write-region', but writes the region's bytes literally.
+/// START, END, FILENAME, APPEND, VISIT, LOCKNAME and MUSTBENEW are as
+/// for `write-region'.
+///
+/// The text is written exactly as it is represented in the buffer,
+/// without code conversion, annotations or a "Wrote file" message.
+#[lisp_fn(min = "3")]
+pub fn write_region_literally(
+    start: LispObject,
+    end: LispObject,
+    filename: LispStringRef,
+    append: LispObject,
+    visit: LispObject,
+    lockname: LispObject,
+    mustbenew: LispObject,
+) {
+    let offset = append.as_fixnum();
+    if offset.map_or(false, |o| o < 0) {
+        args_out_of_range!(append);
+    }
+
+    let count = c_specpdl_index();
+    unsafe {
+        specbind(Qcoding_system_for_write, Qno_conversion);
+        specbind(Qwrite_region_annotate_functions, Qnil);
+    }
+
+    let absname = expand_file_name(filename, None);
+
+    // Visiting, locking, asking before overwriting and magic file
+    // names are left to `write-region'; the bindings above keep it
+    // from converting anything.
+    if visit.is_not_nil()
+        || lockname.is_not_nil()
+        || (mustbenew.is_not_nil() && !mustbenew.eq(Qexcl))
+        || find_file_name_handler(absname, Qwrite_region).is_not_nil()
+    {
+        unsafe {
+            Fwrite_region(
+                start,
+                end,
+                filename.into(),
+                append,
+                visit,
+                lockname,
+                mustbenew,
+            )
+        };
+        unbind_to(count, Qnil);
+        return;
+    }
+
+    let buffer = ThreadState::current_buffer_unchecked();
+    let string = start.as_string();
+    let (before_gap, after_gap) = match string {
+        Some(ref string) => (string.as_slice(), &[][..]),
+        None => {
+            let (start_byte, end_byte) = if start.is_nil() {
+                (buffer.beg_byte(), buffer.z_byte())
+            } else {
+                let (start, end) = validate_region_rust(start, end);
+                (
+                    buffer.charpos_to_bytepos(start),
+                    buffer.charpos_to_bytepos(end),
+                )
+            };
+            buffer.text_slices(start_byte, end_byte)
+        }
+    };
+
+    let mut options = OpenOptions::new();
+    options.write(true);
+    if mustbenew.eq(Qexcl) {
+        options.create_new(true);
+    } else {
+        options.create(true);
     }
+    if append.is_nil() {
+        options.truncate(true);
+    } else if offset.is_none() {
+        options.append(true);
+    }
+
+    let mut file = options
+        .open(to_path_buf(absname))
+        .unwrap_or_else(|err| report_io_error(b"Opening output file\0", absname, &err));
+    if let Err(err) = write_file_bytes(&mut file, offset, &[before_gap, after_gap]) {
+        drop(file);
+        report_io_error(b"Write error\0", absname, &err);
+    }
+
+    unbind_to(count, Qnil);
 }
 
+def_lisp_sym!(Qformat_alist, "format-alist");
+def_lisp_sym!(Qafter_insert_file_functions, "after-insert-file-functions");
+def_lisp_sym!(Qcoding_system_for_read, "coding-system-for-read");
+def_lisp_sym!(Qcoding_system_for_write, "coding-system-for-write");
+def_lisp_sym!(Qinhibit_file_name_handlers, "inhibit-file-name-handlers");
+def_lisp_sym!(Qjka_compr_handler, "jka-compr-handler");
+def_lisp_sym!(Qimage_file_handler, "image-file-handler");
+def_lisp_sym!(Qepa_file_handler, "epa-file-handler");
+
 include!(concat!(env!("OUT_DIR"), "/fileio_exports.rs"));
@@ -13,12 +13,11 @@ use systemstat::Platform;
 #[cfg(unix)]
 use libc::{ELOOP, O_NOFOLLOW, O_RDONLY};
 #[cfg(unix)]
-use std::{ffi::CString, ffi::OsStr, os::unix::ffi::OsStrExt, os::unix::io::FromRawFd};
+use std::{ffi::CString, os::unix::ffi::OsStrExt, os::unix::io::FromRawFd};
 
 use crate::{
-    coding::encode_file_name,
     editfns::system_name,
-    fileio::expand_file_name,
+    fileio::{expand_file_name, to_path_buf},
     lisp::LispObject,
     multibyte::LispStringRef,
     remacs_sys::{emacs_open, lock_file, maybe_quit, unlock_file},
@@ -264,15 +263,6 @@ fn current_lock_owner(path: &Path) -> Result<LockState> {
     Ok(result)
 }
 
-#[cfg(unix)]
-fn to_path_buf(path: LispStringRef) -> PathBuf {
-    let path = encode_file_name(path);
-    PathBuf::from(OsStr::from_bytes(path.as_slice()))
-}
-
-#[cfg(windows)]
-fn to_path_buf(path: LispStringRef) -> PathBuf {} // TODO (may have to return a Result)
-
 /// Generates a path to a lock file corresponding to the given
 /// file name in `path`.
 fn make_lock_name(path: LispStringRef) -> PathBuf {
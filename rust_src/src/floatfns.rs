@@ -3,6 +3,7 @@
 
 use libc;
 
+use std::convert::TryFrom;
 use std::mem;
 
 use remacs_macros::lisp_fn;
@@ -277,12 +278,9 @@ pub fn ftruncate(arg: LispObject) -> EmacsDouble {
 /// Return the floating point number equal to ARG.
 #[lisp_fn]
 pub fn float(arg: LispObject) -> LispObject {
-    if arg.is_float() {
-        arg
-    } else if let Some(n) = arg.as_fixnum() {
-        LispObject::from_float(n as EmacsDouble)
-    } else {
-        wrong_type!(Qnumberp, arg);
+    match arg.as_number_coerce_marker_or_error() {
+        LispNumber::Float(_) => arg,
+        LispNumber::Fixnum(n) => LispObject::from_float(n as EmacsDouble),
     }
 }
 
@@ -322,9 +320,21 @@ pub fn ldexp(significand: EmacsDouble, exponent: EmacsInt) -> EmacsDouble {
 /// Return the exponential ARG1 ** ARG2.
 #[lisp_fn]
 pub fn expt(arg1: LispObject, arg2: LispObject) -> LispObject {
+    // An integer raised to a non-negative integer power stays an
+    // integer, unless the result doesn't fit in a fixnum.
     if let (Some(x), Some(y)) = (arg1.as_fixnum(), arg2.as_fixnum()) {
-        if y >= 0 && y <= EmacsInt::from(u32::max_value()) {
-            return x.pow(y as u32).into();
+        if y >= 0 {
+            let n = match x {
+                0 | 1 => Some(if y == 0 { 1 } else { x }),
+                -1 => Some(if y % 2 == 0 { 1 } else { -1 }),
+                _ => u32::try_from(y)
+                    .ok()
+                    .and_then(|y| x.checked_pow(y))
+                    .filter(|&n| !LispObject::fixnum_overflow(n)),
+            };
+            if let Some(n) = n {
+                return n.into();
+            }
         }
     }
     let b = arg1.any_to_float_or_error();
@@ -407,26 +417,23 @@ fn rounding_driver(
     int_round2: fn(EmacsInt, EmacsInt) -> EmacsInt,
     name: &str,
 ) -> EmacsInt {
-    let d;
-    if divisor.is_nil() {
-        if arg.is_fixnum() {
-            return arg.as_fixnum().unwrap();
-        } else if let Some(f) = arg.as_float() {
-            d = f;
-        } else {
-            wrong_type!(Qnumberp, arg)
+    let num = arg.as_number_coerce_marker_or_error();
+    let d = if divisor.is_nil() {
+        match num {
+            LispNumber::Fixnum(n) => return n,
+            LispNumber::Float(f) => f,
         }
     } else {
-        if let (Some(arg), Some(div)) = (arg.as_fixnum(), divisor.as_fixnum()) {
-            if div == 0 {
-                arith_error!();
+        match (num, divisor.as_number_coerce_marker_or_error()) {
+            (LispNumber::Fixnum(n), LispNumber::Fixnum(div)) => {
+                if div == 0 {
+                    arith_error!();
+                }
+                return int_round2(n, div);
             }
-            return int_round2(arg, div);
+            (num, div) => num.to_float() / div.to_float(),
         }
-        let arg = arg.any_to_float_or_error();
-        let div = divisor.any_to_float_or_error();
-        d = arg / div;
-    }
+    };
 
     // Round, coarsely test for fixnum overflow before converting to
     // EmacsInt (to avoid undefined behavior), and then exactly test
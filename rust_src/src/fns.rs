@@ -8,7 +8,7 @@ use remacs_macros::lisp_fn;
 
 use crate::{
     alloc::record,
-    casefiddle::downcase,
+    casefiddle::{downcase, upcase},
     dispnew::{ding, sleep_for},
     eval::{record_unwind_protect, un_autoload, unbind_to},
     lisp::LispObject,
@@ -21,14 +21,14 @@ use crate::{
     objects::equal,
     remacs_sys::Vautoload_queue,
     remacs_sys::{
-        concat as lisp_concat, copy_char_table, globals, make_uninit_bool_vector,
-        make_uninit_multibyte_string, make_uninit_string, make_uninit_vector, message1,
-        redisplay_preserve_echo_area,
+        concat as lisp_concat, copy_char_table, copy_text_properties, globals,
+        make_uninit_bool_vector, make_uninit_multibyte_string, make_uninit_string,
+        make_uninit_vector, message1, redisplay_preserve_echo_area,
     },
     remacs_sys::{EmacsInt, Lisp_Type},
     remacs_sys::{Fdiscard_input, Fload, Fx_popup_dialog},
     remacs_sys::{
-        Qarrayp, Qfuncall, Qlistp, Qnil, Qprovide, Qquote, Qrequire, Qsequencep, Qsubfeatures, Qt,
+        Qfuncall, Qlistp, Qnil, Qprovide, Qquote, Qrequire, Qsequencep, Qsubfeatures, Qt,
         Qyes_or_no_p_history,
     },
     symbols::LispSymbolRef,
@@ -254,14 +254,36 @@ def_lisp_sym!(Qrequire, "require");
 /// usage: (append &rest SEQUENCES)
 #[lisp_fn]
 pub fn append(args: &mut [LispObject]) -> LispObject {
-    unsafe {
-        lisp_concat(
-            args.len() as isize,
-            args.as_mut_ptr() as *mut LispObject,
-            Lisp_Type::Lisp_Cons,
-            true,
-        )
+    let (last, init) = match args.split_last() {
+        Some(split) => split,
+        None => return Qnil,
+    };
+
+    let mut elements: Vec<LispObject> = Vec::new();
+    for &seq in init {
+        if seq.is_nil() {
+            continue;
+        } else if let Some(cons) = seq.as_cons() {
+            elements.extend(cons.iter_cars(LispConsEndChecks::on, LispConsCircularChecks::on));
+        } else if let Some(string) = seq.as_string() {
+            elements.extend(string.chars().map(LispObject::from));
+        } else if let Some(vector) = seq.as_vector() {
+            elements.extend_from_slice(vector.as_slice());
+        } else if let Some(boolvec) = seq.as_bool_vector() {
+            elements.extend(boolvec.iter());
+        } else if let Some(compiled) = seq.as_vectorlike().and_then(|v| v.as_compiled()) {
+            elements.extend_from_slice(compiled.as_slice());
+        } else {
+            wrong_type!(Qsequencep, seq);
+        }
     }
+
+    // When everything before the last argument is empty, it is
+    // returned as is.
+    elements
+        .into_iter()
+        .rev()
+        .fold(*last, |tail, elt| LispObject::cons(elt, tail))
 }
 
 /// Concatenate all the arguments and make the result a string.
@@ -390,7 +412,7 @@ pub fn nreverse(mut seq: LispObject) -> LispObject {
             }
         }
     } else {
-        wrong_type!(Qarrayp, seq);
+        wrong_type!(Qsequencep, seq);
     }
     seq
 }
@@ -556,7 +578,7 @@ pub fn nconc(args: &mut [LispObject]) -> LispObject {
             break;
         }
 
-        let cons: LispCons = elt.into();
+        let cons = elt.as_cons().unwrap_or_else(|| wrong_type!(Qlistp, elt));
 
         let tail = cons
             .iter_tails(LispConsEndChecks::off, LispConsCircularChecks::on)
@@ -579,7 +601,7 @@ pub fn nconc(args: &mut [LispObject]) -> LispObject {
 /// If the original sequence is empty, this function may return
 /// the same empty object instead of its copy.
 #[lisp_fn]
-pub fn copy_sequence(mut arg: LispObject) -> LispObject {
+pub fn copy_sequence(arg: LispObject) -> LispObject {
     if arg.is_nil() {
         arg
     } else if arg.is_record() {
@@ -591,13 +613,120 @@ pub fn copy_sequence(mut arg: LispObject) -> LispObject {
         let mut new = unsafe { make_uninit_bool_vector(nbits).force_bool_vector() };
         new.as_mut_slice().copy_from_slice(boolvec.as_slice());
         new.into()
-    } else if arg.is_cons() || arg.is_vector() || arg.is_string() {
-        unsafe { lisp_concat(1, &mut arg, arg.get_type(), false) }
+    } else if let Some(cons) = arg.as_cons() {
+        // Copy cell by cell, keeping any non-nil final cdr.
+        let mut tail = Qnil;
+        let mut elements = Vec::new();
+        for cell in cons.iter_tails(LispConsEndChecks::off, LispConsCircularChecks::on) {
+            elements.push(cell.car());
+            tail = cell.cdr();
+        }
+        elements
+            .into_iter()
+            .rev()
+            .fold(tail, |tail, elt| LispObject::cons(elt, tail))
+    } else if let Some(vector) = arg.as_vector() {
+        let mut new = unsafe { make_uninit_vector(vector.len() as isize) }.force_vector();
+        new.as_mut_slice().copy_from_slice(vector.as_slice());
+        new.into()
+    } else if let Some(string) = arg.as_string() {
+        let nchars = string.len_chars();
+        let nbytes = string.len_bytes();
+        let mut new = unsafe {
+            if string.is_multibyte() {
+                make_uninit_multibyte_string(nchars as i64, nbytes as i64)
+            } else {
+                make_uninit_string(nbytes as i64)
+            }
+        }
+        .force_string();
+        new.as_mut_slice().copy_from_slice(string.as_slice());
+        unsafe { copy_text_properties(0.into(), nchars.into(), arg, 0.into(), new.into(), Qnil) };
+        new.into()
     } else {
         wrong_type!(Qsequencep, arg);
     }
 }
 
+/// Compare the contents of two strings, converting to multibyte if needed.
+/// The arguments START1, END1, START2, and END2, if non-nil, are
+/// positions specifying which parts of STR1 or STR2 to compare.  In
+/// string STR1, compare the part between START1 (inclusive) and END1
+/// (exclusive).  If START1 is nil, it defaults to 0, the beginning of
+/// the string; if END1 is nil, it defaults to the length of the string.
+/// Likewise, in string STR2, compare the part between START2 and END2.
+/// Like in `substring', negative values are counted from the end.
+///
+/// The strings are compared by the numeric values of their characters.
+/// For instance, STR1 is "less than" STR2 if its first differing
+/// character has a smaller numeric value.  If IGNORE-CASE is non-nil,
+/// characters are converted to upper-case before comparing them.  Unibyte
+/// strings are converted to multibyte for comparison.
+///
+/// The value is t if the strings (or specified portions) match.
+/// If string STR1 is less, the value is a negative number N;
+///   - 1 - N is the number of characters that match at the beginning.
+/// If string STR1 is greater, the value is a positive number N;
+///   N - 1 is the number of characters that match at the beginning.
+#[lisp_fn(min = "6")]
+pub fn compare_strings(
+    str1: LispStringRef,
+    start1: Option<EmacsInt>,
+    end1: Option<EmacsInt>,
+    str2: LispStringRef,
+    start2: Option<EmacsInt>,
+    end2: Option<EmacsInt>,
+    ignore_case: bool,
+) -> LispObject {
+    let len1 = str1.len_chars();
+    let len2 = str2.len_chars();
+
+    // For backward compatibility, silently bring too-large positive end
+    // values into range.
+    let end1 = end1.map(|e| e.min(len1 as EmacsInt));
+    let end2 = end2.map(|e| e.min(len2 as EmacsInt));
+
+    let (from1, to1) = validate_subarray_rust(str1.into(), start1, end1, len1);
+    let (from2, to2) = validate_subarray_rust(str2.into(), start2, end2, len2);
+
+    let mut chars1 = str1
+        .chars_as_multibyte()
+        .skip(from1 as usize)
+        .take((to1 - from1) as usize);
+    let mut chars2 = str2
+        .chars_as_multibyte()
+        .skip(from2 as usize)
+        .take((to2 - from2) as usize);
+
+    let mut matched: EmacsInt = 0;
+    loop {
+        match (chars1.next(), chars2.next()) {
+            (Some(mut c1), Some(mut c2)) => {
+                matched += 1;
+                if c1 == c2 {
+                    continue;
+                }
+
+                if ignore_case {
+                    c1 = upcase(c1.into()).into();
+                    c2 = upcase(c2.into()).into();
+                }
+
+                if c1 == c2 {
+                    continue;
+                }
+
+                // MATCHED already counts the character being compared,
+                // so no adjustment is needed here.
+                return LispObject::from(if c1 < c2 { -matched } else { matched });
+            }
+            (Some(_), None) => return (matched + 1).into(),
+            (None, Some(_)) => return (-matched - 1).into(),
+            (None, None) => return Qt,
+        }
+    }
+}
+
 /// Check that ARRAY can have a valid subarray [FROM..TO),
 /// given that its size is SIZE.
 /// If FROM is nil, use 0; if TO is nil, use SIZE.
@@ -107,6 +107,26 @@ pub extern "C" fn Fcons(car: LispObject, cdr: LispObject) -> LispObject {
     return Qnil;
 }
 
+// Pretend that calling `error' signals, with the error data `error',
+// and that any other function returns t.
+#[cfg(test)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+#[no_mangle]
+pub extern "C" fn internal_condition_case_n(
+    bfun: Option<unsafe extern "C" fn(ptrdiff_t, *mut LispObject) -> LispObject>,
+    nargs: ptrdiff_t,
+    args: *mut LispObject,
+    handlers: LispObject,
+    hfun: Option<unsafe extern "C" fn(LispObject, ptrdiff_t, *mut LispObject) -> LispObject>,
+) -> LispObject {
+    if unsafe { *args }.eq(Qerror) {
+        unsafe { hfun.unwrap()(Qerror, nargs, args) }
+    } else {
+        Qt
+    }
+}
+
 #[cfg(test)]
 #[allow(unused_variables)]
 #[allow(dead_code)]
@@ -120,5 +140,40 @@ pub extern "C" fn make_string(s: *const c_char, length: isize) -> LispObject {
 #[allow(dead_code)]
 #[no_mangle]
 pub extern "C" fn make_unibyte_string(s: *const c_char, length: isize) -> LispObject {
-    mock_unibyte_string!()
+    mock_raw_string(s, length, -1)
+}
+
+#[cfg(test)]
+#[allow(unused_variables)]
+#[allow(dead_code)]
+#[no_mangle]
+pub extern "C" fn make_multibyte_string(
+    s: *const c_char,
+    nchars: isize,
+    nbytes: isize,
+) -> LispObject {
+    mock_raw_string(s, nchars, nbytes)
+}
+
+// Fake a Lisp string holding a copy of the bytes at S.  Unlike
+// the mock_*_string! macros this keeps embedded NUL bytes.
+#[cfg(test)]
+fn mock_raw_string(s: *const c_char, size: isize, size_byte: isize) -> LispObject {
+    let nbytes = if size_byte < 0 { size } else { size_byte };
+    let mut bytes =
+        unsafe { ::std::slice::from_raw_parts(s as *const u8, nbytes as usize) }.to_vec();
+    bytes.push(0);
+    let boxed = Box::new(crate::remacs_sys::Lisp_String {
+        u: crate::remacs_sys::Lisp_String__bindgen_ty_1 {
+            s: crate::remacs_sys::Lisp_String__bindgen_ty_1__bindgen_ty_1 {
+                size,
+                size_byte,
+                intervals: ::std::ptr::null_mut(),
+                data: Box::into_raw(bytes.into_boxed_slice()) as *mut u8,
+            },
+        },
+    });
+
+    let ptr = crate::lisp::ExternalPtr::new(Box::into_raw(boxed));
+    LispObject::tag_ptr(ptr, crate::remacs_sys::Lisp_Type::Lisp_String)
 }
@@ -10,9 +10,10 @@ use crate::{
     fns::copy_sequence,
     lisp::{ExternalPtr, LispObject},
     lists::{list, put},
-    remacs_sys::{gc_aset, hash_clear, hash_lookup, hash_put, hash_remove_from_table},
+    remacs_sys::{gc_aset, hash_clear, hash_lookup, hash_put, hash_remove_from_table, sxhash},
     remacs_sys::{
-        pvec_type, EmacsDouble, EmacsInt, EmacsUint, Lisp_Hash_Table, Lisp_Type, CHECK_IMPURE,
+        pvec_type, EmacsDouble, EmacsInt, EmacsUint, Lisp_Bits, Lisp_Hash_Table, Lisp_Type,
+        CHECK_IMPURE, INTMASK, USE_LSB_TAG,
     },
     remacs_sys::{Qhash_table_p, Qhash_table_test},
     symbols::LispSymbolRef,
@@ -365,4 +366,48 @@ pub fn define_hash_table_test(
     put(name, sym, list(&[test, hash]))
 }
 
+/// Hash OBJ by identity, as the `eq' hash table test does.
+fn hash_eq(obj: LispObject) -> EmacsUint {
+    let raw = obj.to_C_unsigned();
+    let bits = if USE_LSB_TAG {
+        raw >> Lisp_Bits::INTTYPEBITS
+    } else {
+        raw & INTMASK as EmacsUint
+    };
+    bits ^ obj.get_type() as EmacsUint
+}
+
+/// Hash OBJ structurally, as the `equal' hash table test does.
+/// Nesting deeper than a fixed bound is ignored, so circular objects
+/// can be hashed.
+fn hash_equal(obj: LispObject) -> EmacsUint {
+    unsafe { sxhash(obj, 0) }
+}
+
+/// Return an integer hash code for OBJ suitable for `eq'.
+/// If (eq A B), then (= (sxhash-eq A) (sxhash-eq B)).
+#[lisp_fn]
+pub fn sxhash_eq(obj: LispObject) -> LispObject {
+    LispObject::from_fixnum_truncated(hash_eq(obj) as EmacsInt)
+}
+
+/// Return an integer hash code for OBJ suitable for `eql'.
+/// If (eql A B), then (= (sxhash-eql A) (sxhash-eql B)).
+#[lisp_fn]
+pub fn sxhash_eql(obj: LispObject) -> LispObject {
+    let hash = if obj.is_float() {
+        hash_equal(obj)
+    } else {
+        hash_eq(obj)
+    };
+    LispObject::from_fixnum_truncated(hash as EmacsInt)
+}
+
+/// Return an integer hash code for OBJ suitable for `equal'.
+/// If (equal A B), then (= (sxhash-equal A) (sxhash-equal B)).
+#[lisp_fn]
+pub fn sxhash_equal(obj: LispObject) -> LispObject {
+    LispObject::from_fixnum_truncated(hash_equal(obj) as EmacsInt)
+}
+
 include!(concat!(env!("OUT_DIR"), "/hashtable_exports.rs"));
@@ -3,7 +3,7 @@
 use std;
 use std::ptr;
 
-use libc::c_void;
+use libc::{c_char, c_void, ptrdiff_t};
 
 use remacs_macros::lisp_fn;
 
@@ -12,29 +12,30 @@ use crate::{
     buffers::current_buffer,
     data::{aref, fset, indirect_function, set},
     eval::{autoload_do_load, unbind_to},
-    fns::copy_sequence,
+    fns::{concat, copy_sequence},
     indent::indent_to,
     keyboard,
-    keyboard::lucid_event_type_list_p,
+    keyboard::{lucid_event_type_list_p, Event},
     lisp::LispObject,
     lists::{nth, setcdr, Flist},
     lists::{LispCons, LispConsCircularChecks, LispConsEndChecks},
-    multibyte::LispStringRef,
+    multibyte::{Codepoint, LispStringRef, MAX_CHAR, MAX_MULTIBYTE_LENGTH},
     obarray::intern,
     remacs_sys::{
         access_keymap, apropos_accum, apropos_accumulate, apropos_predicate, copy_keymap_item,
-        current_minor_maps, describe_vector, make_save_funcptr_ptr_obj, map_char_table,
-        map_keymap_call, map_keymap_char_table_item, map_keymap_function_t, map_keymap_item,
-        map_obarray, maybe_quit, specbind,
+        current_minor_maps, describe_vector, make_save_funcptr_ptr_obj, make_specified_string,
+        make_string, map_char_table, map_keymap_call, map_keymap_char_table_item,
+        map_keymap_function_t, map_keymap_item, map_obarray, maybe_quit, silly_event_symbol_error,
+        specbind, store_in_keymap,
     },
     remacs_sys::{char_bits, current_global_map as _current_global_map, globals, EmacsInt},
     remacs_sys::{
-        Fcommand_remapping, Fcurrent_active_maps, Fevent_convert_list, Fmake_char_table,
-        Fset_char_table_range, Fterpri,
+        Fcurrent_active_maps, Fevent_convert_list, Fmake_char_table, Fset_char_table_range,
+        Fsubstring, Fterpri, Fvector,
     },
     remacs_sys::{
-        Qautoload, Qkeymap, Qkeymapp, Qmouse_click, Qnil, Qstandard_output, Qstring_lessp, Qt,
-        Qvector_or_char_table_p,
+        Qarrayp, Qautoload, Qcharacterp, Qkeymap, Qkeymapp, Qmouse_click, Qnil, Qremap,
+        Qstandard_output, Qstring_lessp, Qt, Qvector_or_char_table_p,
     },
     symbols::LispSymbolRef,
     threads::{c_specpdl_index, ThreadState},
@@ -514,7 +515,7 @@ pub fn lookup_key(keymap: LispObject, key: LispObject, accept_default: LispObjec
         // Allow string since binding for `menu-bar-select-buffer'
         // includes the buffer name in the key sequence.
         if !(c.is_fixnum() || c.is_symbol() || c.is_cons() || c.is_string()) {
-            message_with_string!("Key sequence contains invalid event %s", c, true);
+            message_with_string!("Key sequence contains invalid event %s\0", c, true);
         }
 
         let cmd = unsafe { access_keymap(keymap, c, ok, false, true) };
@@ -533,6 +534,452 @@ pub fn lookup_key(keymap: LispObject, key: LispObject, accept_default: LispObjec
     }
 }
 
+/// In KEYMAP, define key sequence KEY as DEF.
+/// KEYMAP is a keymap.
+///
+/// KEY is a string or a vector of symbols and characters, representing a
+/// sequence of keystrokes and events.  Non-ASCII characters with codes
+/// above 127 (such as ISO Latin-1) can be represented by vectors.
+/// Two types of vector have special meanings:
+///  [remap COMMAND] remaps any key binding for COMMAND.
+///  [t] creates a default definition, which applies to any event with no
+///     other definition in KEYMAP.
+///
+/// DEF is anything that can be a key's definition:
+///  nil (means key is undefined in this keymap),
+///  a command (a Lisp function suitable for interactive calling),
+///  a string (treated as a keyboard macro),
+///  a keymap (to define a prefix key),
+///  a symbol (when the key is looked up, the symbol will stand for its
+///     function definition, which should at that time be one of the above,
+///     or another symbol whose function definition is used, etc.),
+///  a cons (STRING . DEFN), meaning that DEFN is the definition
+///     (DEFN should be a valid definition in its own right),
+///  or a cons (MAP . CHAR), meaning use definition of CHAR in keymap MAP,
+///  or an extended menu item definition.
+///  (See info node `(elisp)Extended Menu Items'.)
+///
+/// If REMOVE is non-nil, the definition will be removed.  This is almost
+/// the same as setting the definition to nil, but makes a difference if
+/// the KEYMAP has a parent, and KEY is shadowing the same binding in the
+/// parent.  With REMOVE, subsequent lookups will return the binding in
+/// the parent, and with a nil DEF, the lookups will return nil.
+///
+/// If KEYMAP is a sparse keymap with a binding for KEY, the existing
+/// binding is altered.  If there is no binding for KEY, the new pair
+/// binding KEY to DEF is added at the front of KEYMAP.
+#[lisp_fn(min = "3")]
+pub fn define_key(
+    keymap: LispObject,
+    key: LispObject,
+    mut def: LispObject,
+    remove: bool,
+) -> LispObject {
+    let mut keymap = get_keymap(keymap, true, true);
+    let length = key.as_vector_or_string_length() as EmacsInt;
+    if length == 0 {
+        return Qnil;
+    }
+
+    unsafe {
+        if def.is_symbol() && globals.Vdefine_key_rebound_commands != Qt {
+            globals.Vdefine_key_rebound_commands =
+                (def, globals.Vdefine_key_rebound_commands).into();
+        }
+    }
+
+    let meta_bit = match key.as_string() {
+        Some(s) if !s.is_multibyte() => 0x80,
+        _ => char_bits::CHAR_META as EmacsInt,
+    };
+
+    if let Some(v) = def.as_vector() {
+        // DEF is apparently an XEmacs-style keyboard macro.
+        if v.len() > 0 && v.get(0).is_cons() {
+            let mut converted: Vec<LispObject> = v
+                .iter()
+                .map(|defi| {
+                    if defi.is_cons() && lucid_event_type_list_p(defi.into()) {
+                        unsafe { Fevent_convert_list(defi) }
+                    } else {
+                        defi
+                    }
+                })
+                .collect();
+            def = unsafe { Fvector(converted.len() as ptrdiff_t, converted.as_mut_ptr()) };
+        }
+    }
+
+    let meta_prefix_char = unsafe { globals.meta_prefix_char };
+    let mut metized = false;
+    let mut idx = 0;
+    loop {
+        let mut c = aref(key, idx);
+
+        if let Some(cons) = c.as_cons() {
+            // C may be a Lucid style event type list or a cons (FROM .
+            // TO) specifying a range of characters.
+            if lucid_event_type_list_p(Some(cons)) {
+                c = unsafe { Fevent_convert_list(c) };
+            } else if cons.car().is_character() && !cons.cdr().is_character() {
+                wrong_type!(Qcharacterp, cons.cdr());
+            }
+        }
+
+        if c.is_symbol() {
+            unsafe { silly_event_symbol_error(c) };
+        }
+
+        match c.as_fixnum() {
+            Some(n) if n & meta_bit != 0 && !metized => {
+                c = meta_prefix_char;
+                metized = true;
+            }
+            n => {
+                if let Some(n) = n {
+                    c = (n & !meta_bit).into();
+                }
+                metized = false;
+                idx += 1;
+            }
+        }
+
+        if !(c.is_fixnum()
+            || c.is_symbol()
+            || c.as_cons()
+                // If C is a range, it must be a leaf.
+                .map_or(false, |r| !(r.car().is_fixnum() && idx != length)))
+        {
+            message_with_string!("Key sequence contains invalid event %s\0", c, true);
+        }
+
+        if idx == length {
+            return unsafe { store_in_keymap(keymap, c, def, remove) };
+        }
+
+        let mut cmd = unsafe { access_keymap(keymap, c, false, true, true) };
+
+        // If this key is undefined, make it a prefix.
+        if cmd.is_nil() {
+            cmd = make_sparse_keymap(Qnil);
+            unsafe { store_in_keymap(keymap, c, cmd, false) };
+        }
+
+        keymap = get_keymap(cmd, false, true);
+        if !keymap.is_cons() {
+            let trailing_esc = if c == meta_prefix_char && metized {
+                if idx == 0 {
+                    "ESC"
+                } else {
+                    " ESC"
+                }
+            } else {
+                ""
+            };
+
+            // We must use key_description rather than just passing key to
+            // error; key might be a vector, not a string.
+            let description = key_description(key, Qnil);
+            let prefix = key_description(unsafe { Fsubstring(key, 0.into(), idx.into()) }, Qnil);
+            error!(
+                "Key sequence {} starts with non-prefix key {}{}",
+                description, prefix, trailing_esc
+            );
+        }
+    }
+}
+
+/// Append a description of the character CH to BUF, in the form
+/// produced by `single-key-description'.
+pub fn push_key_description(ch: EmacsInt, buf: &mut Vec<u8>) {
+    let meta = EmacsInt::from(char_bits::CHAR_META);
+    let ctrl = EmacsInt::from(char_bits::CHAR_CTL);
+
+    // Clear all the meaningless bits above the meta bit.
+    let mut c = ch & (meta | !-meta);
+    let c2 = c & !EmacsInt::from(char_bits::CHAR_MODIFIER_MASK);
+
+    if c2 > EmacsInt::from(MAX_CHAR) {
+        buf.extend_from_slice(format!("[{}]", c).as_bytes());
+        return;
+    }
+
+    let tab_as_ci = c2 == EmacsInt::from(b'\t') && (c & meta) != 0;
+
+    if c & EmacsInt::from(char_bits::CHAR_ALT) != 0 {
+        buf.extend_from_slice(b"A-");
+        c -= EmacsInt::from(char_bits::CHAR_ALT);
+    }
+    if (c & ctrl) != 0
+        || (c2 < EmacsInt::from(b' ')
+            && c2 != 27
+            && c2 != EmacsInt::from(b'\t')
+            && c2 != EmacsInt::from(Ctl('M')))
+        || tab_as_ci
+    {
+        buf.extend_from_slice(b"C-");
+        c &= !ctrl;
+    }
+    if c & EmacsInt::from(char_bits::CHAR_HYPER) != 0 {
+        buf.extend_from_slice(b"H-");
+        c -= EmacsInt::from(char_bits::CHAR_HYPER);
+    }
+    if c & meta != 0 {
+        buf.extend_from_slice(b"M-");
+        c -= meta;
+    }
+    if c & EmacsInt::from(char_bits::CHAR_SHIFT) != 0 {
+        buf.extend_from_slice(b"S-");
+        c -= EmacsInt::from(char_bits::CHAR_SHIFT);
+    }
+    if c & EmacsInt::from(char_bits::CHAR_SUPER) != 0 {
+        buf.extend_from_slice(b"s-");
+        c -= EmacsInt::from(char_bits::CHAR_SUPER);
+    }
+
+    match c {
+        0o33 => buf.extend_from_slice(b"ESC"),
+        _ if tab_as_ci => buf.push(b'i'),
+        0o11 => buf.extend_from_slice(b"TAB"),
+        0o15 => buf.extend_from_slice(b"RET"),
+        // `C-' already added above.
+        1..=0o10 | 0o12..=0o14 | 0o16..=0o32 => buf.push(c as u8 + 0o140),
+        0 | 0o34..=0o37 => buf.push(c as u8 + 0o100),
+        0o40 => buf.extend_from_slice(b"SPC"),
+        0o41..=0o176 => buf.push(c as u8),
+        0o177 => buf.extend_from_slice(b"DEL"),
+        _ => {
+            // Now we are sure that C is a valid character code.
+            let mut bytes = [0; MAX_MULTIBYTE_LENGTH];
+            let len = Codepoint::from(c as u32).write_to(&mut bytes);
+            buf.extend_from_slice(&bytes[..len]);
+        }
+    }
+}
+
+/// Return a pretty description of command character KEY.
+/// Control characters turn into C-whatever, etc.
+/// Optional argument NO-ANGLES non-nil means don't put angle brackets
+/// around function keys and event symbols.
+#[lisp_fn(min = "1")]
+pub fn single_key_description(mut key: LispObject, no_angles: bool) -> LispObject {
+    if key.is_cons() && lucid_event_type_list_p(key.into()) {
+        key = unsafe { Fevent_convert_list(key) };
+    }
+
+    if let Some((from, to)) = key.as_cons().map(<(LispObject, LispObject)>::from) {
+        // An interval from a map-char-table.
+        if from.is_fixnum() && to.is_fixnum() {
+            return concat(&mut [
+                single_key_description(from, no_angles),
+                LispObject::from(".."),
+                single_key_description(to, no_angles),
+            ]);
+        }
+    }
+
+    let key = Event::from(key).head();
+
+    if let Some(c) = key.as_fixnum() {
+        // Normal character.
+        let mut buf = Vec::new();
+        push_key_description(c, &mut buf);
+        unsafe {
+            make_specified_string(
+                buf.as_ptr() as *const c_char,
+                -1,
+                buf.len() as ptrdiff_t,
+                true,
+            )
+        }
+    } else if let Some(symbol) = key.as_symbol() {
+        // Function key or event-symbol.
+        let name = symbol.symbol_name();
+        if no_angles {
+            return name;
+        }
+
+        let name = LispStringRef::from(name);
+        let mut buf = Vec::with_capacity(name.len_bytes() as usize + 2);
+        buf.push(b'<');
+        buf.extend_from_slice(name.as_slice());
+        buf.push(b'>');
+        unsafe { make_string(buf.as_ptr() as *const c_char, buf.len() as ptrdiff_t) }
+    } else if key.is_string() {
+        // Buffer names in the menubar.
+        copy_sequence(key)
+    } else {
+        error!("KEY must be an integer, cons, symbol, or string");
+    }
+}
+
+/// Return a pretty description of key-sequence KEYS.
+/// Optional arg PREFIX is the sequence of keys leading up to KEYS.
+/// For example, [?\C-x ?l] is converted into the string \"C-x l\".
+///
+/// For an approximate inverse of this, see `kbd'.
+#[lisp_fn(min = "1")]
+pub fn key_description(keys: LispObject, prefix: LispObject) -> LispObject {
+    let meta = EmacsInt::from(char_bits::CHAR_META);
+    let meta_prefix_char = unsafe { globals.meta_prefix_char };
+    let mut add_meta = false;
+    let mut args = Vec::new();
+
+    let mut describe = |key| {
+        if !args.is_empty() {
+            args.push(LispObject::from(" "));
+        }
+        args.push(single_key_description(key, false));
+    };
+
+    for list in &[prefix, keys] {
+        let events: Vec<LispObject> = if list.is_nil() {
+            continue;
+        } else if let Some(s) = list.as_string() {
+            s.chars()
+                .map(|cp| {
+                    let mut c = EmacsInt::from(u32::from(cp));
+                    if cp.is_single_byte() && (c & 0o200) != 0 {
+                        c ^= 0o200 | meta;
+                    }
+                    LispObject::from(c)
+                })
+                .collect()
+        } else if let Some(v) = list.as_vector() {
+            v.iter().collect()
+        } else if list.is_cons() {
+            list.iter_cars(LispConsEndChecks::on, LispConsCircularChecks::on)
+                .collect()
+        } else {
+            wrong_type!(Qarrayp, *list);
+        };
+
+        for mut key in events {
+            if add_meta {
+                match key.as_fixnum() {
+                    Some(c) if key != meta_prefix_char && c & meta == 0 => {
+                        key = LispObject::from(c | meta);
+                    }
+                    _ => {
+                        describe(meta_prefix_char);
+                        if key == meta_prefix_char {
+                            continue;
+                        }
+                    }
+                }
+                add_meta = false;
+            } else if key == meta_prefix_char {
+                add_meta = true;
+                continue;
+            }
+            describe(key);
+        }
+    }
+
+    if add_meta {
+        describe(meta_prefix_char);
+    }
+
+    if args.is_empty() {
+        LispObject::empty_unibyte_string().into()
+    } else {
+        concat(&mut args)
+    }
+}
+
+/// Modifier prefixes accepted by `key-valid-p', in the order they must
+/// appear in a key.
+const KEY_MODIFIER_PREFIXES: [&str; 6] = ["A-", "C-", "H-", "M-", "S-", "s-"];
+
+/// Names of special keys that `key-valid-p' accepts on their own.
+const KEY_NAMES: [&str; 7] = ["NUL", "RET", "LFD", "TAB", "ESC", "SPC", "DEL"];
+
+fn codepoints_eq(key: &[Codepoint], s: &str) -> bool {
+    key.len() == s.len() && key.iter().zip(s.chars()).all(|(&c, s)| c == s)
+}
+
+/// Strip the modifier prefixes from the front of KEY, accepting each
+/// modifier at most once and only in canonical order.
+fn strip_key_modifiers(mut key: &[Codepoint]) -> &[Codepoint] {
+    for prefix in KEY_MODIFIER_PREFIXES.iter() {
+        if key.len() >= prefix.len() && codepoints_eq(&key[..prefix.len()], prefix) {
+            key = &key[prefix.len()..];
+        }
+    }
+    key
+}
+
+fn is_function_key_name(c: Codepoint) -> bool {
+    c.is_ascii() && {
+        let c = u32::from(c) as u8;
+        c.is_ascii_alphanumeric() || c == b'-' || c == b'_'
+    }
+}
+
+/// Whether KEY, one space-separated token of a `kbd'-style string, is
+/// valid.  MULTIBYTE says whether the string it came from is multibyte.
+fn key_token_valid_p(key: &[Codepoint], multibyte: bool) -> bool {
+    let key = strip_key_modifiers(key);
+
+    match key {
+        // Don't accept control characters or Meta'd characters as keys.
+        [c] => {
+            let c = u32::from(*c);
+            c >= u32::from(b' ') && (multibyte || !(127..=255).contains(&c))
+        }
+        // Don't allow <M-C-down>.
+        [open, name @ .., close] if *open == '<' && *close == '>' => {
+            !name.is_empty()
+                && name.iter().all(|&c| is_function_key_name(c))
+                && strip_key_modifiers(name).len() == name.len()
+        }
+        _ => KEY_NAMES.iter().any(|name| codepoints_eq(key, name)),
+    }
+}
+
+/// Say whether KEYS is a valid key.
+/// A key is a string consisting of one or more key strokes.
+/// The key strokes are separated by single space characters.
+///
+/// Each key stroke is either a single character, or the name of an
+/// event, surrounded by angle brackets.  In addition, any key stroke
+/// may be preceded by one or more modifier keys.  Finally, a limited
+/// number of characters have a special shorthand syntax.
+///
+/// Here's some example key sequences.
+///
+///     "f"           (the key 'f')
+///     "S o m"       (a three key sequence of the keys 'S', 'o' and 'm')
+///     "C-c o"       (a two key sequence of the keys 'c' with the control modifier
+///                    and then the key 'o')
+///     "H-<left>"    (the key named "left" with the hyper modifier)
+///     "M-RET"       (the "return" key with a meta modifier)
+///     "C-M-<space>" (the "space" key with both the control and meta modifiers)
+///
+/// These are the characters that have shorthand syntax:
+/// NUL, RET, TAB, LFD, ESC, SPC, DEL.
+///
+/// Modifiers have to be specified in this order:
+///
+///    A-C-H-M-S-s
+///
+/// which is
+///
+///    Alt-Control-Hyper-Meta-Shift-super
+#[lisp_fn]
+pub fn key_valid_p(keys: LispObject) -> bool {
+    let keys = match keys.as_string() {
+        Some(s) => s,
+        None => return false,
+    };
+    let chars: Vec<Codepoint> = keys.chars().collect();
+
+    !chars.is_empty()
+        && chars
+            .split(|&c| c == ' ')
+            .all(|key| !key.is_empty() && key_token_valid_p(key, keys.is_multibyte()))
+}
+
 /// Define COMMAND as a prefix command.  COMMAND should be a symbol.
 /// A new sparse keymap is stored as COMMAND's function definition and its
 /// value.
@@ -748,7 +1195,7 @@ pub fn key_binding(
     // If the result of the ordinary keymap lookup is an interactive
     // command, look for a key binding (i.e. remapping) for that command.
     if !no_remap && value.is_symbol() {
-        let remap = unsafe { Fcommand_remapping(value, position, Qnil) };
+        let remap = command_remapping(value, position, Qnil);
         if remap.is_not_nil() {
             return remap;
         }
@@ -756,6 +1203,44 @@ pub fn key_binding(
     value
 }
 
+/// Return the remapping for command COMMAND.
+/// Returns nil if COMMAND is not remapped (or not a symbol).
+///
+/// If the optional argument POSITION is non-nil, it specifies a mouse
+/// position as returned by `event-start' and `event-end', and the
+/// remapping occurs in the keymaps associated with it.  It can also be a
+/// number or marker, in which case the keymap properties at the specified
+/// buffer position instead of point are used.  The KEYMAPS argument is
+/// ignored if POSITION is non-nil.
+///
+/// If the optional argument KEYMAPS is non-nil, it should be a list of
+/// keymaps to search for command remapping.  Otherwise, search for the
+/// remapping in all currently active keymaps.
+#[lisp_fn(min = "1")]
+pub fn command_remapping(
+    command: LispObject,
+    position: LispObject,
+    keymaps: LispObject,
+) -> LispObject {
+    if !command.is_symbol() {
+        return Qnil;
+    }
+
+    let mut remap = [Qremap, command];
+    let key = unsafe { Fvector(remap.len() as ptrdiff_t, remap.as_mut_ptr()) };
+
+    let command = if keymaps.is_nil() {
+        key_binding(key, false, true, position)
+    } else {
+        lookup_key((Qkeymap, keymaps).into(), key, Qnil)
+    };
+    if command.is_integer() {
+        Qnil
+    } else {
+        command
+    }
+}
+
 /// Show all symbols whose names contain match for REGEXP.
 /// If optional 2nd arg PREDICATE is non-nil, (funcall PREDICATE SYMBOL) is done
 /// for each symbol and a symbol is mentioned only if that returns non-nil.
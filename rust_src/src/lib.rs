@@ -144,7 +144,9 @@ static ALLOCATOR: OsxUnexecAlloc = OsxUnexecAlloc;
 include!(concat!(env!("OUT_DIR"), "/c_exports.rs"));
 
 #[cfg(test)]
-pub use crate::functions::{lispsym, make_string, make_unibyte_string, Fcons};
+pub use crate::functions::{
+    lispsym, make_multibyte_string, make_string, make_unibyte_string, Fcons,
+};
 
 mod hacks {
     use core::mem::ManuallyDrop;
@@ -2,7 +2,6 @@
 //! lisp.h.
 
 use std::convert::{From, Into};
-use std::ffi::CString;
 use std::fmt;
 use std::fmt::{Debug, Display, Error, Formatter};
 use std::mem;
@@ -21,12 +20,12 @@ use crate::{
     multibyte::LispStringRef,
     process::LispProcessRef,
     remacs_sys::specbind_tag,
-    remacs_sys::{build_string, make_float, Fmake_hash_table},
     remacs_sys::{
         equal_kind, pvec_type, EmacsDouble, EmacsInt, EmacsUint, Lisp_Bits, USE_LSB_TAG, VALMASK,
     },
+    remacs_sys::{make_float, make_multibyte_string, make_unibyte_string, Fmake_hash_table},
     remacs_sys::{specbinding, Lisp_Misc_Any, Lisp_Misc_Type, Lisp_Subr, Lisp_Type},
-    remacs_sys::{QCtest, Qautoload, Qeq, Qnil, Qsubrp, Qt},
+    remacs_sys::{QCtest, Qautoload, Qeq, Qintegerp, Qnil, Qsubrp, Qt, Qwrong_type_argument},
     remacs_sys::{Vbuffer_alist, Vprocess_alist},
     symbols::LispSymbolRef,
 };
@@ -88,6 +87,72 @@ where
     }
 }
 
+/// An error to be signalled as `(SYMBOL . DATA)`.
+///
+/// Lisp functions may return `Result<T, LispError>` to propagate errors
+/// with `?`; converting an `Err` into a `LispObject`, as the `lisp_fn`
+/// wrapper does with every return value, signals it.
+#[derive(Clone, Copy, Debug)]
+pub struct LispError {
+    pub symbol: LispObject,
+    pub data: LispObject,
+}
+
+impl LispError {
+    pub fn new(symbol: LispObject, data: LispObject) -> Self {
+        Self { symbol, data }
+    }
+
+    pub fn signal(self) -> ! {
+        crate::eval::signal(self.symbol, self.data)
+    }
+}
+
+/// A failed conversion from a `LispObject`: OBJECT does not satisfy
+/// the type predicate PREDICATE.
+///
+/// Converting it into a `LispError` gives the usual
+/// `(wrong-type-argument PREDICATE OBJECT)`.
+#[derive(Clone, Copy, Debug)]
+pub struct LispTypeError {
+    pub predicate: LispObject,
+    pub object: LispObject,
+}
+
+impl LispTypeError {
+    pub fn new(predicate: LispObject, object: LispObject) -> Self {
+        Self { predicate, object }
+    }
+}
+
+impl From<LispTypeError> for LispError {
+    fn from(e: LispTypeError) -> Self {
+        Self::new(Qwrong_type_argument, list!(e.predicate, e.object))
+    }
+}
+
+/// Conversion from a `LispObject` that reports a type mismatch as an
+/// `Err` rather than signaling.
+///
+/// This plays the role of `TryFrom<LispObject>`, which is off limits
+/// for types that already convert with `From<LispObject>`: the
+/// standard library gives each of those an infallible `TryFrom`.
+pub trait TryFromLisp: Sized {
+    fn try_from_lisp(o: LispObject) -> Result<Self, LispTypeError>;
+}
+
+impl<T> From<Result<T, LispError>> for LispObject
+where
+    LispObject: From<T>,
+{
+    fn from(v: Result<T, LispError>) -> Self {
+        match v {
+            Ok(v) => v.into(),
+            Err(e) => e.signal(),
+        }
+    }
+}
+
 // ExternalPtr
 
 #[repr(transparent)]
@@ -383,11 +448,19 @@ impl From<!> for LispObject {
     }
 }
 
-/// Copies a Rust str into a new Lisp string
+/// Copies a Rust str into a new Lisp string.  Embedded NUL bytes are
+/// kept; the string is unibyte if S is pure ASCII, else multibyte.
 impl<'a> From<&'a str> for LispObject {
     fn from(s: &str) -> Self {
-        let cs = CString::new(s).unwrap();
-        unsafe { build_string(cs.as_ptr()) }
+        let ptr = s.as_ptr() as *const c_char;
+        let nbytes = s.len() as isize;
+        unsafe {
+            if s.is_ascii() {
+                make_unibyte_string(ptr, nbytes)
+            } else {
+                make_multibyte_string(ptr, s.chars().count() as isize, nbytes)
+            }
+        }
     }
 }
 
@@ -422,12 +495,34 @@ impl LispObject {
     }
 }
 
+impl LispObject {
+    /// If the LispObject is a number, or a marker that points somewhere,
+    /// get a floating point value for it.  Like
+    /// `CHECK_NUMBER_OR_FLOAT_COERCE_MARKER`, without the error.
+    pub fn as_fixnum_or_float(self) -> Option<EmacsDouble> {
+        self.as_float()
+            .or_else(|| self.as_fixnum().map(|n| n as EmacsDouble))
+            .or_else(|| {
+                self.as_marker()
+                    .and_then(|m| m.charpos())
+                    .map(|pos| pos as EmacsDouble)
+            })
+    }
+}
+
 impl From<LispObject> for EmacsInt {
     fn from(o: LispObject) -> Self {
         o.as_fixnum_or_error()
     }
 }
 
+impl TryFromLisp for EmacsInt {
+    fn try_from_lisp(o: LispObject) -> Result<Self, LispTypeError> {
+        o.as_fixnum()
+            .ok_or_else(|| LispTypeError::new(Qintegerp, o))
+    }
+}
+
 impl From<LispObject> for Option<EmacsInt> {
     fn from(o: LispObject) -> Self {
         if o.is_nil() {
@@ -809,8 +904,74 @@ macro_rules! protect_statics_from_GC {
     }
 }
 
+macro_rules! export_lisp_variables {
+    ($($var:ident, $lisp_name:expr, $value:expr);+) => {
+        pub fn rust_variable_syms() {
+            unsafe {
+                $({
+                    #[allow(const_err)]
+                    static mut o_fwd: crate::hacks::Hack<crate::data::Lisp_Objfwd> =
+                        unsafe { crate::hacks::Hack::uninitialized() };
+                    crate::remacs_sys::defvar_lisp(
+                        o_fwd.get_mut(),
+                        concat!($lisp_name, "\0").as_ptr() as *const libc::c_char,
+                        &mut $var,
+                    );
+                    $var = $value;
+                })+
+            }
+        }
+    }
+}
+
 #[test]
 fn test_lisp_misc_any_size() {
     // Should be 32 bits, which is 4 bytes.
     assert!(mem::size_of::<Lisp_Misc_Any>() == 4);
 }
+
+#[test]
+fn test_from_str_with_nul() {
+    let string = LispObject::from("a\0b").force_string();
+    assert!(!string.is_multibyte());
+    assert_eq!(string.len_bytes(), 3);
+    assert_eq!(string.as_slice(), b"a\0b");
+
+    let string = LispObject::from("\0é").force_string();
+    assert!(string.is_multibyte());
+    assert_eq!(string.len_chars(), 2);
+    assert_eq!(string.len_bytes(), 3);
+    assert_eq!(string.as_slice(), "\0é".as_bytes());
+}
+
+#[test]
+fn test_try_from_lisp() {
+    assert_eq!(EmacsInt::try_from_lisp(LispObject::from(42)).ok(), Some(42));
+
+    let string = mock_unibyte_string!("42");
+    let err = EmacsInt::try_from_lisp(string).unwrap_err();
+    assert!(err.predicate.eq(Qintegerp));
+    assert!(err.object.eq(string));
+    assert!(LispStringRef::try_from_lisp(string).is_ok());
+}
+
+#[test]
+fn test_as_fixnum_or_float() {
+    assert_eq!(LispObject::from(3).as_fixnum_or_float(), Some(3.0));
+    assert_eq!(mock_float!(2.5).as_fixnum_or_float(), Some(2.5));
+    assert_eq!(mock_unibyte_string!("3").as_fixnum_or_float(), None);
+
+    let mut buffer: Box<crate::remacs_sys::Lisp_Buffer> = Box::new(unsafe { mem::zeroed() });
+    let mut marker: Box<crate::remacs_sys::Lisp_Marker> = Box::new(unsafe { mem::zeroed() });
+    marker.set_type_(Lisp_Misc_Type::Lisp_Misc_Marker);
+    let marker = ExternalPtr::new(Box::into_raw(marker));
+    let object = LispObject::tag_ptr(marker, Lisp_Type::Lisp_Misc);
+
+    // A marker that points nowhere has no position.
+    assert_eq!(object.as_fixnum_or_float(), None);
+
+    let mut marker = marker;
+    marker.buffer = &mut *buffer;
+    marker.charpos = 7;
+    assert_eq!(object.as_fixnum_or_float(), Some(7.0));
+}
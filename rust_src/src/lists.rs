@@ -8,13 +8,17 @@ use libc::c_void;
 use remacs_macros::lisp_fn;
 
 use crate::{
+    data::set,
+    fns,
     hashtable::LispHashTableRef,
-    lisp::{LispObject, LispStructuralEqual},
+    lisp::{LispObject, LispStructuralEqual, LispTypeError, TryFromLisp},
+    math::{arithcompare, plus, times, ArithComparison},
     numbers::MOST_POSITIVE_FIXNUM,
     remacs_sys::{equal_kind, globals, EmacsInt, EmacsUint, Lisp_Cons, Lisp_Type},
     remacs_sys::{Fcons, CHECK_IMPURE},
-    remacs_sys::{Qcircular_list, Qconsp, Qlistp, Qnil, Qplistp},
-    symbols::LispSymbolRef,
+    remacs_sys::{Qcircular_list, Qconsp, Qeq, Qeql, Qlistp, Qnil, Qplistp},
+    symbols::{symbol_value, LispSymbolRef},
+    vectors,
 };
 
 // Cons support (LispType == 6 | 3)
@@ -270,6 +274,12 @@ impl From<LispObject> for LispCons {
     }
 }
 
+impl TryFromLisp for LispCons {
+    fn try_from_lisp(o: LispObject) -> Result<Self, LispTypeError> {
+        o.as_cons().ok_or_else(|| LispTypeError::new(Qconsp, o))
+    }
+}
+
 impl From<LispObject> for Option<LispCons> {
     fn from(o: LispObject) -> Self {
         if o.is_list() {
@@ -512,6 +522,87 @@ pub fn cdr_safe(object: LispObject) -> LispObject {
     }
 }
 
+/// Apply to X the `car' and `cdr' operations spelled out by OPS, where
+/// b'a' stands for `car' and b'd' for `cdr'.  As in the accessor names,
+/// the rightmost operation is applied first, so b"ad" is `cadr'.
+fn cxr(x: LispObject, ops: &[u8]) -> LispObject {
+    ops.iter()
+        .rev()
+        .fold(x, |x, &op| if op == b'a' { car(x) } else { cdr(x) })
+}
+
+/// Return the car of the car of X.
+#[lisp_fn]
+pub fn caar(x: LispObject) -> LispObject {
+    cxr(x, b"aa")
+}
+
+/// Return the car of the cdr of X.
+#[lisp_fn]
+pub fn cadr(x: LispObject) -> LispObject {
+    cxr(x, b"ad")
+}
+
+/// Return the cdr of the car of X.
+#[lisp_fn]
+pub fn cdar(x: LispObject) -> LispObject {
+    cxr(x, b"da")
+}
+
+/// Return the cdr of the cdr of X.
+#[lisp_fn]
+pub fn cddr(x: LispObject) -> LispObject {
+    cxr(x, b"dd")
+}
+
+/// Return the `car' of the `car' of the `car' of X.
+#[lisp_fn]
+pub fn caaar(x: LispObject) -> LispObject {
+    cxr(x, b"aaa")
+}
+
+/// Return the `car' of the `car' of the `cdr' of X.
+#[lisp_fn]
+pub fn caadr(x: LispObject) -> LispObject {
+    cxr(x, b"aad")
+}
+
+/// Return the `car' of the `cdr' of the `car' of X.
+#[lisp_fn]
+pub fn cadar(x: LispObject) -> LispObject {
+    cxr(x, b"ada")
+}
+
+/// Return the `car' of the `cdr' of the `cdr' of X.
+#[lisp_fn]
+pub fn caddr(x: LispObject) -> LispObject {
+    cxr(x, b"add")
+}
+
+/// Return the `cdr' of the `car' of the `car' of X.
+#[lisp_fn]
+pub fn cdaar(x: LispObject) -> LispObject {
+    cxr(x, b"daa")
+}
+
+/// Return the `cdr' of the `car' of the `cdr' of X.
+#[lisp_fn]
+pub fn cdadr(x: LispObject) -> LispObject {
+    cxr(x, b"dad")
+}
+
+/// Return the `cdr' of the `cdr' of the `car' of X.
+#[lisp_fn]
+pub fn cddar(x: LispObject) -> LispObject {
+    cxr(x, b"dda")
+}
+
+/// Return the `cdr' of the `cdr' of the `cdr' of X.
+#[lisp_fn]
+pub fn cdddr(x: LispObject) -> LispObject {
+    cxr(x, b"ddd")
+}
+
 /// Take cdr N times on LIST, return the result.
 #[lisp_fn]
 pub fn nthcdr(n: EmacsInt, list: LispObject) -> LispObject {
@@ -538,6 +629,90 @@ pub fn nth(n: EmacsInt, list: LispObject) -> LispObject {
     car(nthcdr(n, list))
 }
 
+/// Return the first N elements of LIST.
+/// If N is zero or negative, return nil.
+/// If N is greater or equal to the length of LIST, return a copy of LIST.
+#[lisp_fn]
+pub fn take(n: EmacsInt, list: LispObject) -> LispObject {
+    if n <= 0 {
+        return Qnil;
+    }
+
+    // The walk is bounded by N, so circular lists need no special care.
+    list.iter_cars(LispConsEndChecks::on, LispConsCircularChecks::off)
+        .take(n as usize)
+        .collect::<Vec<_>>()
+        .into()
+}
+
+/// Modify LIST to keep only the first N elements.
+/// If N is zero or negative, return nil.
+/// If N is greater or equal to the length of LIST, return LIST unmodified.
+/// Otherwise, return LIST after truncating it.
+#[lisp_fn]
+pub fn ntake(n: EmacsInt, list: LispObject) -> LispObject {
+    if n <= 0 {
+        return Qnil;
+    }
+
+    list.check_list();
+    if let Some(tail) = nthcdr(n - 1, list).as_cons() {
+        setcdr(tail, Qnil);
+    }
+    list
+}
+
+/// Make a copy of TREE.
+/// If TREE is a cons cell, this recursively copies both its car and its cdr.
+/// Contrast to `copy-sequence', which copies only along the cdrs.  With second
+/// argument VECP, this copies vectors as well as conses.
+#[lisp_fn(min = "1")]
+pub fn copy_tree(tree: LispObject, vecp: bool) -> LispObject {
+    if tree.is_cons() {
+        // Walk the cdrs iteratively and only recurse into the cars, so
+        // long lists don't exhaust the native stack.
+        let mut cars = Vec::new();
+        let mut tail = tree;
+        while let Some(cons) = tail.as_cons() {
+            cars.push(copy_tree(cons.car(), vecp));
+            tail = cons.cdr();
+        }
+        let last = copy_tree(tail, vecp);
+        cars.into_iter()
+            .rev()
+            .fold(last, |tail, car| LispObject::cons(car, tail))
+    } else if vecp && tree.is_vector() {
+        let mut new = fns::copy_sequence(tree).force_vector();
+        for elt in new.as_mut_slice() {
+            *elt = copy_tree(*elt, vecp);
+        }
+        new.into()
+    } else {
+        tree
+    }
+}
+
+/// Return the last link of LIST.  Its car is the last element.
+/// If LIST is nil, return nil.
+/// If N is non-nil, return the Nth-to-last link of LIST.
+/// If N is bigger than the length of LIST, return LIST.
+#[lisp_fn(min = "1")]
+pub fn last(list: LispObject, n: Option<EmacsInt>) -> LispObject {
+    let n = n.unwrap_or(1);
+    if n < 0 {
+        return Qnil;
+    }
+
+    let length = list
+        .iter_tails(LispConsEndChecks::off, LispConsCircularChecks::on)
+        .count() as EmacsInt;
+    if n < length {
+        nthcdr(length - n, list)
+    } else {
+        list
+    }
+}
+
 fn lookup_member(
     elt: LispObject,
     list: LispObject,
@@ -572,6 +747,61 @@ pub fn member(elt: LispObject, list: LispObject) -> LispObject {
     lookup_member(elt, list, LispObject::equal)
 }
 
+/// Add ELEMENT to the value of LIST-VAR if it isn't there yet.
+/// The test for presence of ELEMENT is done with `equal', or with
+/// COMPARE-FN if that's non-nil.
+/// If ELEMENT is added, it is added at the beginning of the list,
+/// unless the optional argument APPEND is non-nil, in which case
+/// ELEMENT is added at the end.
+///
+/// The return value is the new value of LIST-VAR.
+///
+/// This is handy to add some elements to configuration variables,
+/// but please do not abuse it in Elisp code, where you are usually
+/// better off using `push' or `cl-pushnew'.
+///
+/// If you want to use `add-to-list' on a variable that is not
+/// defined until a certain package is loaded, you should put the
+/// call to `add-to-list' into a hook function that will be run only
+/// after loading the package.  `eval-after-load' provides one way to
+/// do this.  In some cases other hooks, such as major mode hooks,
+/// can do the job.
+#[lisp_fn(min = "2")]
+pub fn add_to_list(
+    list_var: LispSymbolRef,
+    element: LispObject,
+    append: bool,
+    compare_fn: LispObject,
+) -> LispObject {
+    let list = symbol_value(list_var);
+    if !list.is_list() {
+        wrong_type!(Qlistp, list);
+    }
+
+    let present = if compare_fn.is_nil() {
+        member(element, list)
+    } else if compare_fn.eq(Qeq) {
+        memq(element, list)
+    } else if compare_fn.eq(Qeql) {
+        memql(element, list)
+    } else {
+        lookup_member(element, list, |elt, item| {
+            call!(compare_fn, elt, item).is_not_nil()
+        })
+    };
+
+    if present.is_not_nil() {
+        return list;
+    }
+
+    let newval = if append {
+        fns::append(&mut [list, list!(element)])
+    } else {
+        LispObject::cons(element, list)
+    };
+    set(list_var, newval)
+}
+
 fn assoc_impl(
     key: LispObject,
     list: LispObject,
@@ -656,6 +886,41 @@ pub fn delq(elt: LispObject, list: LispObject) -> LispObject {
         })
 }
 
+fn delete_all_impl(alist: LispObject, matches: impl Fn(LispCons) -> bool) -> LispObject {
+    let mut prev = None;
+    alist
+        .iter_tails(LispConsEndChecks::on, LispConsCircularChecks::on)
+        .fold(alist, |remaining, tail| {
+            let (item, rest) = tail.into();
+            if item.as_cons().map_or(false, &matches) {
+                match prev {
+                    Some(cons) => setcdr(cons, rest),
+                    None => return rest,
+                };
+            } else {
+                prev = Some(tail);
+            }
+
+            remaining
+        })
+}
+
+/// Delete from ALIST all elements whose car is `eq' to KEY.
+/// Return the modified alist.
+/// Elements of ALIST that are not conses are ignored.
+#[lisp_fn]
+pub fn assq_delete_all(key: LispObject, alist: LispObject) -> LispObject {
+    delete_all_impl(alist, |cons| key.eq(cons.car()))
+}
+
+/// Delete from ALIST all elements whose cdr is `eq' to VALUE.
+/// Return the modified alist.
+/// Elements of ALIST that are not conses are ignored.
+#[lisp_fn]
+pub fn rassq_delete_all(value: LispObject, alist: LispObject) -> LispObject {
+    delete_all_impl(alist, |cons| value.eq(cons.cdr()))
+}
+
 /// Extract a value from a property list.
 /// PLIST is a property list, which is a list of the form
 /// (PROP1 VALUE1 PROP2 VALUE2...).  This function returns the value
@@ -835,6 +1100,92 @@ pub fn make_list(length: EmacsUint, init: LispObject) -> LispObject {
     (0..length).fold(Qnil, |list, _| (init, list).into())
 }
 
+/// Return a sequence of numbers from FROM to TO (both inclusive) as a list.
+/// INC is the increment used between numbers in the sequence and defaults to 1.
+/// So, the Nth element of the list is (+ FROM (* N INC)) where N counts from
+/// zero.  TO is only included if there is an N for which TO = FROM + N * INC.
+/// If TO is nil or numerically equal to FROM, return (FROM).
+/// If INC is positive and TO is less than FROM, or INC is negative
+/// and TO is larger than FROM, return nil.
+/// If INC is zero and TO is neither nil nor numerically equal to
+/// FROM, signal an error.
+///
+/// This function is primarily designed for integer arguments.
+/// Nevertheless, FROM, TO and INC can be integer or float.  However,
+/// floating point arithmetic is inexact.  For instance, depending on
+/// the machine, it may quite well happen that
+/// \(number-sequence 0.4 0.6 0.2) returns the one element list (0.4),
+/// whereas (number-sequence 0.4 0.8 0.2) returns a list with three
+/// elements.  Thus, if some of the arguments are floats and one wants
+/// to make sure that TO is included, one may have to explicitly write
+/// TO as (+ FROM (* N INC)) or use a variable whose value was
+/// computed with this exact expression.  Alternatively, you can,
+/// of course, also replace TO with a slightly larger value
+/// \(or a slightly more negative value if INC is negative).
+#[lisp_fn(min = "1")]
+pub fn number_sequence(from: LispObject, to: LispObject, inc: LispObject) -> LispObject {
+    if to.is_nil() || arithcompare(from, to, ArithComparison::Equal) {
+        return list!(from);
+    }
+    let inc = if inc.is_nil() {
+        LispObject::from(1)
+    } else {
+        inc
+    };
+    if arithcompare(inc, LispObject::from(0), ArithComparison::Equal) {
+        error!("The increment can not be zero");
+    }
+
+    let (toward_to, away_from_last) =
+        if arithcompare(inc, LispObject::from(0), ArithComparison::Grtr) {
+            (ArithComparison::LessOrEqual, ArithComparison::GrtrOrEqual)
+        } else {
+            (ArithComparison::GrtrOrEqual, ArithComparison::LessOrEqual)
+        };
+
+    let mut seq = Vec::new();
+    let mut n: EmacsInt = 0;
+    let mut next = from;
+    let mut last = from;
+    // The comparison against LAST protects against integer overflow
+    // in computing NEXT.
+    while arithcompare(next, last, away_from_last) && arithcompare(next, to, toward_to) {
+        seq.push(next);
+        n += 1;
+        last = next;
+        next = plus(&[from, times(&[n.into(), inc])]);
+    }
+
+    seq.into()
+}
+
+/// Return a "flattened" copy of TREE.
+/// In other words, return a list of the non-nil terminal nodes, or
+/// leaves, of the tree of cons cells rooted at TREE.  Leaves in the
+/// returned list are in the same order as in TREE.
+///
+/// \(flatten-tree \='(1 (2 . 3) nil (4 5 (6)) 7))
+/// => (1 2 3 4 5 6 7)
+#[lisp_fn]
+pub fn flatten_tree(tree: LispObject) -> LispObject {
+    // Walk the tree with an explicit stack so that deeply nested trees
+    // cannot exhaust the native stack.
+    let mut leaves = Vec::new();
+    let mut pending = vec![tree];
+    while let Some(node) = pending.pop() {
+        match node.as_cons() {
+            Some(cons) => {
+                pending.push(cons.cdr());
+                pending.push(cons.car());
+            }
+            None if node.is_not_nil() => leaves.push(node),
+            None => {}
+        }
+    }
+
+    leaves.into()
+}
+
 /// Return the length of a list, but avoid error or infinite loop.
 /// This function never gets an error.  If LIST is not really a list,
 /// it returns 0.  If LIST is circular, it returns a finite value
@@ -845,6 +1196,52 @@ pub fn safe_length(list: LispObject) -> usize {
         .count()
 }
 
+/// Return the length of SEQUENCE, except that a list is only counted up
+/// to LIMIT elements.  This is what lets `length<' and friends answer
+/// without walking (possibly circular) lists to the end.
+fn length_up_to(sequence: LispObject, limit: EmacsInt) -> EmacsInt {
+    if sequence.is_cons() {
+        // If LIMIT is short (arbitrarily chosen cut-off point), the walk is
+        // bounded anyway, so don't bother checking whether SEQUENCE is
+        // circular.  Otherwise signal an error on circular lists.
+        let circular_checks = if limit < 0xffff {
+            LispConsCircularChecks::off
+        } else {
+            LispConsCircularChecks::on
+        };
+        sequence
+            .iter_tails(LispConsEndChecks::off, circular_checks)
+            .take(limit.max(0) as usize)
+            .count() as EmacsInt
+    } else {
+        vectors::length(sequence) as EmacsInt
+    }
+}
+
+/// Return non-nil if SEQUENCE is shorter than LENGTH.
+/// See `length' for allowed values of SEQUENCE and how elements are
+/// counted.
+#[lisp_fn(name = "length<", c_name = "length_less")]
+pub fn length_less(sequence: LispObject, length: EmacsInt) -> bool {
+    length_up_to(sequence, length) < length
+}
+
+/// Return non-nil if SEQUENCE is longer than LENGTH.
+/// See `length' for allowed values of SEQUENCE and how elements are
+/// counted.
+#[lisp_fn(name = "length>", c_name = "length_greater")]
+pub fn length_greater(sequence: LispObject, length: EmacsInt) -> bool {
+    length_up_to(sequence, length.saturating_add(1)) > length
+}
+
+/// Return non-nil if SEQUENCE has length equal to LENGTH.
+/// See `length' for allowed values of SEQUENCE and how elements are
+/// counted.
+#[lisp_fn(name = "length=", c_name = "length_equal")]
+pub fn length_equal(sequence: LispObject, length: EmacsInt) -> bool {
+    length >= 0 && length_up_to(sequence, length.saturating_add(1)) == length
+}
+
 // Used by sort() in vectors.rs.
 
 pub fn sort_list(list: LispObject, pred: LispObject) -> LispObject {
@@ -1,10 +1,17 @@
 //! Functions doing math on numbers.
 #![allow(clippy::float_cmp)]
 
-use crate::remacs_sys::{EmacsInt, Qnumberp};
+use std::cmp::Ordering;
+use std::mem;
+
+use crate::remacs_sys::{EmacsDouble, EmacsInt, EmacsUint, Lisp_Bits, INTMASK};
 use remacs_macros::lisp_fn;
 
-use crate::{floatfns, lisp::LispObject, numbers::LispNumber};
+use crate::{
+    floatfns,
+    lisp::LispObject,
+    numbers::{LispNumber, MOST_NEGATIVE_FIXNUM},
+};
 
 /// Return X modulo Y.
 /// The result falls between zero (inclusive) and Y (exclusive).
@@ -239,13 +246,14 @@ pub fn min(args: &[LispObject]) -> LispObject {
 
 /// Return the absolute value of ARG.
 #[lisp_fn]
-pub fn abs(arg: LispObject) -> LispObject {
-    if let Some(f) = arg.as_float() {
-        LispObject::from_float(f.abs())
-    } else if let Some(n) = arg.as_fixnum() {
-        LispObject::from(n.abs())
-    } else {
-        wrong_type!(Qnumberp, arg);
+pub fn abs(arg: LispNumber) -> LispObject {
+    match arg {
+        LispNumber::Float(f) => LispObject::from_float(f.abs()),
+        // The negation of `most-negative-fixnum' is not a fixnum.
+        LispNumber::Fixnum(MOST_NEGATIVE_FIXNUM) => {
+            LispObject::from_float(-(MOST_NEGATIVE_FIXNUM as EmacsDouble))
+        }
+        LispNumber::Fixnum(n) => LispObject::from(n.abs()),
     }
 }
 
@@ -260,18 +268,15 @@ pub enum ArithComparison {
     GrtrOrEqual,
 }
 
-pub fn arithcompare(obj1: LispObject, obj2: LispObject, comparison: ArithComparison) -> bool {
+/// Order the numbers N1 and N2, comparing two fixnums exactly.
+/// Return None if a NaN makes them unordered.
+fn compare_numbers(n1: LispNumber, n2: LispNumber) -> Option<Ordering> {
     // If either arg is floating point, set F1 and F2 to the 'double'
-    // approximations of the two arguments, and set FNEQ if floating-point
-    // comparison reports that F1 is not equal to F2, possibly because F1
-    // or F2 is a NaN.  Regardless, set I1 and I2 to integers that break
-    // ties if the floating-point comparison is either not done or reports
-    // equality.
+    // approximations of the two arguments.  Regardless, set I1 and I2
+    // to integers that break ties if the floating-point comparison is
+    // either not done or reports equality.
 
-    let (i1, i2, f1, f2) = match (
-        obj1.as_number_coerce_marker_or_error(),
-        obj2.as_number_coerce_marker_or_error(),
-    ) {
+    let (i1, i2, f1, f2) = match (n1, n2) {
         (LispNumber::Fixnum(n1), LispNumber::Fixnum(n2)) => (n1, n2, 0., 0.),
         (LispNumber::Fixnum(n1), LispNumber::Float(n2)) => {
             // Compare an integer NUM1 to a float NUM2.  This is the
@@ -291,39 +296,39 @@ pub fn arithcompare(obj1: LispObject, obj2: LispObject, comparison: ArithCompari
         }
         (LispNumber::Float(n1), LispNumber::Float(n2)) => (0, 0, n1, n2),
     };
-    let fneq = f1 != f2;
+
+    if f1 != f2 {
+        f1.partial_cmp(&f2)
+    } else {
+        Some(i1.cmp(&i2))
+    }
+}
+
+impl LispObject {
+    /// Order this number or marker against OTHER, as the arithmetic
+    /// comparisons do.  Return None if either is not a number or
+    /// marker, or if a NaN makes them unordered.
+    pub fn arith_compare(self, other: LispObject) -> Option<Ordering> {
+        compare_numbers(
+            self.as_number_coerce_marker()?,
+            other.as_number_coerce_marker()?,
+        )
+    }
+}
+
+pub fn arithcompare(obj1: LispObject, obj2: LispObject, comparison: ArithComparison) -> bool {
+    let ordering = compare_numbers(
+        obj1.as_number_coerce_marker_or_error(),
+        obj2.as_number_coerce_marker_or_error(),
+    );
 
     match comparison {
-        ArithComparison::Equal => !fneq && i1 == i2,
-        ArithComparison::Notequal => fneq || i1 != i2,
-        ArithComparison::Less => {
-            if fneq {
-                f1 < f2
-            } else {
-                i1 < i2
-            }
-        }
-        ArithComparison::LessOrEqual => {
-            if fneq {
-                f1 <= f2
-            } else {
-                i1 <= i2
-            }
-        }
-        ArithComparison::Grtr => {
-            if fneq {
-                f1 > f2
-            } else {
-                i1 > i2
-            }
-        }
-        ArithComparison::GrtrOrEqual => {
-            if fneq {
-                f1 >= f2
-            } else {
-                i1 >= i2
-            }
-        }
+        ArithComparison::Equal => ordering == Some(Ordering::Equal),
+        ArithComparison::Notequal => ordering != Some(Ordering::Equal),
+        ArithComparison::Less => ordering == Some(Ordering::Less),
+        ArithComparison::LessOrEqual => ordering.map_or(false, |o| o != Ordering::Greater),
+        ArithComparison::Grtr => ordering == Some(Ordering::Greater),
+        ArithComparison::GrtrOrEqual => ordering.map_or(false, |o| o != Ordering::Less),
     }
 }
 
@@ -413,4 +418,77 @@ pub fn lognot(number: EmacsInt) -> EmacsInt {
     !number
 }
 
+/// Wrap N into the fixnum range the way `XSETINT' does, discarding the
+/// bits that do not fit in a fixnum.
+fn wrap_fixnum(n: EmacsInt) -> EmacsInt {
+    (n << Lisp_Bits::INTTYPEBITS) >> Lisp_Bits::INTTYPEBITS
+}
+
+fn ash_lsh_impl(value: EmacsInt, count: EmacsInt, lsh: bool) -> EmacsInt {
+    let width = (mem::size_of::<EmacsInt>() * 8) as EmacsInt;
+    // The unsigned representation of VALUE as a fixnum.
+    let uvalue = (value & INTMASK) as EmacsUint;
+
+    let val = if count >= width {
+        0
+    } else if count > 0 {
+        (uvalue << count) as EmacsInt
+    } else if count <= -width {
+        if !lsh && value < 0 {
+            -1
+        } else {
+            0
+        }
+    } else if lsh {
+        (uvalue >> -count) as EmacsInt
+    } else {
+        value >> -count
+    };
+    wrap_fixnum(val)
+}
+
+/// Return VALUE with its bits shifted left by COUNT.
+/// If COUNT is negative, shifting is actually to the right.
+/// In this case, the sign bit is duplicated.
+#[lisp_fn]
+pub fn ash(value: EmacsInt, count: EmacsInt) -> EmacsInt {
+    ash_lsh_impl(value, count, false)
+}
+
+/// Return VALUE with its bits shifted left by COUNT.
+/// If COUNT is negative, shifting is actually to the right.
+/// In this case, zeros are shifted in on the left.
+#[lisp_fn]
+pub fn lsh(value: EmacsInt, count: EmacsInt) -> EmacsInt {
+    ash_lsh_impl(value, count, true)
+}
+
 include!(concat!(env!("OUT_DIR"), "/math_exports.rs"));
+
+#[test]
+fn test_arith_compare() {
+    let one = LispObject::from(1);
+    assert_eq!(one.arith_compare(mock_float!(1.5)), Some(Ordering::Less));
+    assert_eq!(mock_float!(1.0).arith_compare(one), Some(Ordering::Equal));
+    assert_eq!(
+        mock_float!(2.5).arith_compare(mock_float!(2.5)),
+        Some(Ordering::Equal)
+    );
+    assert_eq!(one.arith_compare(mock_float!(std::f64::NAN)), None);
+    assert_eq!(one.arith_compare(mock_unibyte_string!("1")), None);
+}
+
+#[test]
+fn test_ash_lsh() {
+    use crate::numbers::MOST_POSITIVE_FIXNUM;
+
+    assert_eq!(ash(1, 3), 8);
+    assert_eq!(ash(-8, -2), -2);
+    assert_eq!(ash(-1, -1), -1);
+    assert_eq!(lsh(-1, -1), MOST_POSITIVE_FIXNUM);
+    assert_eq!(lsh(8, -3), 1);
+    assert_eq!(ash(1, 64), 0);
+    assert_eq!(ash(-5, -64), -1);
+    assert_eq!(lsh(-5, -64), 0);
+    assert_eq!(ash(MOST_POSITIVE_FIXNUM, 1), -2);
+}
@@ -4,23 +4,25 @@ use remacs_macros::lisp_fn;
 
 use crate::{
     buffers::{current_buffer, LispBufferOrName},
+    data::set,
     editfns,
     editfns::field_end,
     eval, fns,
     keymap::get_keymap,
     lisp::LispObject,
-    lists::{car_safe, cdr_safe, memq, LispCons},
+    lists::{car_safe, cdr_safe, get, memq, nthcdr, LispCons},
     multibyte::LispStringRef,
     obarray::{intern, intern_lisp},
     remacs_sys::{
         globals, Qbuffer_name_history, Qcommandp, Qcompletion_ignore_case, Qcustom_variable_p,
-        Qfield, Qminibuffer_completion_table, Qminibuffer_history, Qnil, Qt, Vminibuffer_list,
+        Qfield, Qhistory_length, Qminibuffer_completion_table, Qminibuffer_history, Qnil, Qt,
+        Vminibuffer_list,
     },
     remacs_sys::{
         make_buffer_string, make_specified_string, minibuf_level, minibuf_prompt, minibuf_window,
-        read_minibuf, specbind, EmacsInt,
+        read_minibuf, specbind, EmacsInt, Fdelete,
     },
-    symbols::symbol_value,
+    symbols::{symbol_value, LispSymbolRef},
     textprop::get_char_property,
     threads::{c_specpdl_index, ThreadState},
 };
@@ -522,4 +524,54 @@ pub fn read_buffer(
     eval::unbind_to(count, result)
 }
 
+/// Add NEWELT to the history list stored in the variable HISTORY-VAR.
+/// Return the new history list.
+/// If MAXELT is non-nil, it specifies the maximum length of the history.
+/// Otherwise, the maximum history length is the value of the `history-length'
+/// property on symbol HISTORY-VAR, if set, or the value of the `history-length'
+/// variable.  The possible values of maximum length have the same meaning as
+/// the values of `history-length'.
+/// Remove duplicates of NEWELT if `history-delete-duplicates' is non-nil.
+/// If optional fourth arg KEEP-ALL is non-nil, add NEWELT to history even
+/// if it is empty or a duplicate.
+#[lisp_fn(min = "2")]
+pub fn add_to_history(
+    history_var: LispSymbolRef,
+    newelt: LispObject,
+    maxelt: LispObject,
+    keep_all: bool,
+) -> LispObject {
+    let maxelt = if maxelt.is_nil() {
+        let length = get(history_var, Qhistory_length);
+        if length.is_nil() {
+            unsafe { globals.Vhistory_length }
+        } else {
+            length
+        }
+    } else {
+        maxelt
+    };
+
+    let mut history = symbol_value(history_var);
+    if history.is_list()
+        && (keep_all || newelt.as_string().map_or(true, |s| s.len_chars() > 0))
+        && (keep_all || !car_safe(history).equal(newelt))
+    {
+        if unsafe { globals.history_delete_duplicates } {
+            history = unsafe { Fdelete(newelt, history) };
+        }
+        history = LispObject::cons(newelt, history);
+
+        if let Some(maxelt) = maxelt.as_fixnum() {
+            if maxelt == 0 {
+                history = Qnil;
+            } else if let Some(tail) = nthcdr(maxelt - 1, history).as_cons() {
+                tail.set_cdr(Qnil);
+            }
+        }
+    }
+
+    set(history_var, history)
+}
+
 include!(concat!(env!("OUT_DIR"), "/minibuf_exports.rs"));
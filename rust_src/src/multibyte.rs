@@ -39,19 +39,25 @@ use std::slice;
 
 use libc::{c_char, c_int, c_uchar, c_uint, c_void, memset, ptrdiff_t, size_t};
 
+use remacs_macros::lisp_fn;
+
 use crate::{
+    casefiddle::upcase,
+    character::char_head_p,
     hashtable::LispHashTableRef,
-    lisp::{ExternalPtr, LispObject, LispStructuralEqual},
+    lisp::{ExternalPtr, LispError, LispObject, LispStructuralEqual, LispTypeError, TryFromLisp},
     obarray::LispObarrayRef,
     remacs_sys::{
         buffer_display_table, char_width, compare_string_intervals, empty_unibyte_string,
-        find_composition as c_find_composition, get_composition_id, string_char_to_byte,
+        find_composition as c_find_composition, get_composition_id, make_specified_string,
+        string_char_to_byte,
     },
     remacs_sys::{
         char_bits, composition_table, equal_kind, EmacsDouble, EmacsInt, Lisp_Interval,
         Lisp_String, Lisp_Type,
     },
-    remacs_sys::{Qcharacterp, Qnil, Qstringp},
+    remacs_sys::{Qcharacterp, Qnil, Qstringp, Qwrong_length_argument, Qwrong_type_argument},
+    strings::{string_to_multibyte, string_to_unibyte},
     symbols::LispSymbolRef,
 };
 
@@ -334,6 +340,25 @@ impl From<LispObject> for Codepoint {
     }
 }
 
+impl From<char> for LispObject {
+    fn from(c: char) -> Self {
+        Codepoint::from(c).into()
+    }
+}
+
+/// Only Unicode scalar values convert; raw 8-bit bytes and other
+/// characters beyond Unicode are rejected along with non-characters.
+impl TryFrom<LispObject> for char {
+    type Error = LispError;
+
+    fn try_from(o: LispObject) -> Result<Self, Self::Error> {
+        o.as_fixnum()
+            .and_then(|i| u32::try_from(i).ok())
+            .and_then(|u| Self::try_from(u).ok())
+            .ok_or_else(|| LispError::new(Qwrong_type_argument, list!(Qcharacterp, o)))
+    }
+}
+
 // String support (LispType == 4)
 
 impl LispStringRef {
@@ -469,6 +494,10 @@ impl LispStringRef {
         s.data as *const c_char
     }
 
+    pub fn intervals(self) -> *mut Lisp_Interval {
+        unsafe { self.u.s.intervals }
+    }
+
     pub fn set_intervals(&mut self, interval: *mut Lisp_Interval) {
         let mut s = unsafe { self.u.s };
         s.intervals = interval;
@@ -581,6 +610,7 @@ impl fmt::Debug for LispStringRef {
 pub struct LispStringRefIterator<'a> {
     string_ref: &'a LispStringRef,
     cur: usize,
+    end: usize,
 }
 
 pub struct LispStringRefCharIterator<'a>(LispStringRefIterator<'a>);
@@ -590,7 +620,7 @@ impl<'a> Iterator for LispStringRefIterator<'a> {
     type Item = (usize, Codepoint);
 
     fn next(&mut self) -> Option<(usize, Codepoint)> {
-        if self.cur < self.string_ref.len_bytes() as usize {
+        if self.cur < self.end {
             let codepoint: Codepoint;
             let old_index = self.cur;
             let ref_slice = self.string_ref.as_slice();
@@ -610,6 +640,33 @@ impl<'a> Iterator for LispStringRefIterator<'a> {
     }
 }
 
+// Substitute for FETCH_STRING_CHAR_ADVANCE going backwards: step back
+// over continuation bytes until a character head is found.
+impl<'a> DoubleEndedIterator for LispStringRefIterator<'a> {
+    fn next_back(&mut self) -> Option<(usize, Codepoint)> {
+        if self.cur < self.end {
+            let codepoint: Codepoint;
+            let ref_slice = self.string_ref.as_slice();
+            if self.string_ref.is_multibyte() {
+                let mut start = self.end - 1;
+                while start > self.cur && !char_head_p(ref_slice[start]) {
+                    start -= 1;
+                }
+                let (cp, _) = multibyte_char_at(&ref_slice[start..]);
+                codepoint = cp;
+                self.end = start;
+            } else {
+                self.end -= 1;
+                codepoint = Codepoint::from(ref_slice[self.end]);
+            }
+
+            Some((self.end, codepoint))
+        } else {
+            None
+        }
+    }
+}
+
 impl<'a> Iterator for LispStringRefCharIterator<'a> {
     type Item = Codepoint;
 
@@ -618,18 +675,32 @@ impl<'a> Iterator for LispStringRefCharIterator<'a> {
     }
 }
 
+impl<'a> DoubleEndedIterator for LispStringRefCharIterator<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|result| result.1)
+    }
+}
+
 impl LispStringRef {
-    pub const fn char_indices(&self) -> LispStringRefIterator {
+    pub fn char_indices(&self) -> LispStringRefIterator {
         LispStringRefIterator {
             string_ref: self,
             cur: 0,
+            end: self.len_bytes() as usize,
         }
     }
 
-    #[allow(dead_code)]
     pub fn chars(&self) -> LispStringRefCharIterator {
         LispStringRefCharIterator(self.char_indices())
     }
+
+    /// Iterate over the characters of the string, converting unibyte
+    /// characters to their multibyte equivalents.
+    pub fn chars_as_multibyte(&self) -> impl DoubleEndedIterator<Item = Codepoint> + '_ {
+        let multibyte = self.is_multibyte();
+        self.chars()
+            .map(move |c| if multibyte { c } else { c.unibyte_to_char() })
+    }
 }
 
 impl From<EmacsDouble> for LispObject {
@@ -644,6 +715,12 @@ impl From<LispObject> for LispStringRef {
     }
 }
 
+impl TryFromLisp for LispStringRef {
+    fn try_from_lisp(o: LispObject) -> Result<Self, LispTypeError> {
+        o.as_string().ok_or_else(|| LispTypeError::new(Qstringp, o))
+    }
+}
+
 impl From<LispObject> for Option<LispStringRef> {
     fn from(o: LispObject) -> Self {
         if o.is_string() {
@@ -1211,3 +1288,259 @@ pub extern "C" fn lisp_string_width(
     };
     width as isize
 }
+
+/// Return the byte offset of the first occurrence of NEEDLE in
+/// HAYSTACK, or None if there is none.
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(0);
+    }
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+/// Return the bytes of NEEDLE in the representation used by HAYSTACK,
+/// or None if NEEDLE contains characters that cannot occur in HAYSTACK.
+fn bytes_matching(needle: LispStringRef, haystack: LispStringRef) -> Option<Vec<u8>> {
+    if needle.is_multibyte() == haystack.is_multibyte()
+        || needle.as_slice().is_ascii()
+        || haystack.as_slice().is_ascii()
+    {
+        Some(needle.as_slice().to_vec())
+    } else if haystack.is_multibyte() {
+        let needle = LispStringRef::from(string_to_multibyte(needle));
+        Some(needle.as_slice().to_vec())
+    } else if needle.chars().all(|c| c.is_ascii() || c.is_byte8()) {
+        // The only possible way we can find a non-ASCII multibyte
+        // needle in a unibyte haystack is if it consists of raw bytes.
+        let needle = LispStringRef::from(string_to_unibyte(needle));
+        Some(needle.as_slice().to_vec())
+    } else {
+        None
+    }
+}
+
+/// Search for the string NEEDLE in the string HAYSTACK.
+/// The return value is the position of the first occurrence of
+/// NEEDLE in HAYSTACK, or nil if no match was found.
+///
+/// The optional START-POS argument says where to start searching in
+/// HAYSTACK and defaults to zero (start at the beginning).
+/// It must be between zero and the length of HAYSTACK, inclusive.
+///
+/// Case is always significant and text properties are ignored.
+#[lisp_fn(min = "2")]
+pub fn string_search(
+    needle: LispStringRef,
+    haystack: LispStringRef,
+    start_pos: Option<EmacsInt>,
+) -> Option<EmacsInt> {
+    let start = start_pos.unwrap_or(0);
+    if start < 0 || start > haystack.len_chars() as EmacsInt {
+        args_out_of_range!(start);
+    }
+    let haystart = haystack
+        .char_indices()
+        .nth(start as usize)
+        .map_or(haystack.len_bytes() as usize, |(i, _)| i);
+
+    let needle = bytes_matching(needle, haystack)?;
+    let found = haystart + find_bytes(&haystack.as_slice()[haystart..], &needle)?;
+
+    // Map the byte offset back to a character position.
+    Some(
+        haystack
+            .char_indices()
+            .take_while(|&(i, _)| i < found)
+            .count() as EmacsInt,
+    )
+}
+
+/// Replace FROM-STRING with TO-STRING in IN-STRING.
+///
+/// This is a convenience function for the common case of replacing
+/// all non-overlapping occurrences of a string with another.  The
+/// result is multibyte if any of the arguments are.
+#[lisp_fn]
+pub fn string_replace(
+    from_string: LispStringRef,
+    to_string: LispStringRef,
+    in_string: LispStringRef,
+) -> LispObject {
+    if from_string.is_empty() {
+        xsignal!(Qwrong_length_argument, LispObject::from(0));
+    }
+
+    let multibyte =
+        from_string.is_multibyte() || to_string.is_multibyte() || in_string.is_multibyte();
+    let convert = |string: LispStringRef| {
+        if multibyte {
+            LispStringRef::from(string_to_multibyte(string))
+        } else {
+            string
+        }
+    };
+    let from = convert(from_string);
+    let to = convert(to_string);
+    let input = convert(in_string);
+
+    let mut result = Vec::with_capacity(input.len_bytes() as usize);
+    let mut rest = input.as_slice();
+    let mut matches = 0;
+    while let Some(pos) = find_bytes(rest, from.as_slice()) {
+        result.extend_from_slice(&rest[..pos]);
+        result.extend_from_slice(to.as_slice());
+        rest = &rest[pos + from.len_bytes() as usize..];
+        matches += 1;
+    }
+    result.extend_from_slice(rest);
+
+    let nchars = input.len_chars() + matches * (to.len_chars() - from.len_chars());
+    unsafe {
+        make_specified_string(
+            result.as_ptr() as *const c_char,
+            nchars,
+            result.len() as ptrdiff_t,
+            multibyte,
+        )
+    }
+}
+
+/// Whether the characters yielded by A and B are the same, ignoring
+/// case differences if IGNORE-CASE.
+fn chars_match(
+    a: impl Iterator<Item = Codepoint>,
+    b: impl Iterator<Item = Codepoint>,
+    ignore_case: bool,
+) -> bool {
+    a.zip(b)
+        .all(|(c1, c2)| c1 == c2 || (ignore_case && upcase(c1.into()) == upcase(c2.into())))
+}
+
+/// Return non-nil if PREFIX is a prefix of STRING.
+/// If IGNORE-CASE is non-nil, the comparison is done without paying attention
+/// to case differences.
+#[lisp_fn(min = "2")]
+pub fn string_prefix_p(prefix: LispStringRef, string: LispStringRef, ignore_case: bool) -> bool {
+    let len = prefix.len_chars();
+    if len > string.len_chars() {
+        return false;
+    }
+
+    if !ignore_case && prefix.is_multibyte() == string.is_multibyte() {
+        string.as_slice().starts_with(prefix.as_slice())
+    } else {
+        chars_match(
+            prefix.chars_as_multibyte(),
+            string.chars_as_multibyte().take(len as usize),
+            ignore_case,
+        )
+    }
+}
+
+/// Return non-nil if SUFFIX is a suffix of STRING.
+/// If IGNORE-CASE is non-nil, the comparison is done without paying
+/// attention to case differences.
+#[lisp_fn(min = "2")]
+pub fn string_suffix_p(suffix: LispStringRef, string: LispStringRef, ignore_case: bool) -> bool {
+    let len = suffix.len_chars();
+    if len > string.len_chars() {
+        return false;
+    }
+
+    if !ignore_case && suffix.is_multibyte() == string.is_multibyte() {
+        string.as_slice().ends_with(suffix.as_slice())
+    } else {
+        chars_match(
+            suffix.chars_as_multibyte().rev(),
+            string.chars_as_multibyte().rev().take(len as usize),
+            ignore_case,
+        )
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/multibyte_exports.rs"));
+
+#[test]
+fn test_chars_rev() {
+    let string = mock_multibyte_string!("aé€b\u{1D11E}c").force_string();
+    let forward: Vec<Codepoint> = string.chars().collect();
+    let mut backward: Vec<Codepoint> = string.chars().rev().collect();
+    backward.reverse();
+    assert_eq!(forward.len(), 6);
+    assert_eq!(forward, backward);
+    assert_eq!(string.chars().rev().next(), Some(Codepoint::from('c')));
+}
+
+#[test]
+fn test_char_indices_double_ended() {
+    let string = mock_multibyte_string!("aé€").force_string();
+    let mut it = string.char_indices();
+    assert_eq!(it.next_back(), Some((3, Codepoint::from('€'))));
+    assert_eq!(it.next(), Some((0, Codepoint::from('a'))));
+    assert_eq!(it.next_back(), Some((1, Codepoint::from('é'))));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next_back(), None);
+
+    let unibyte = mock_unibyte_string!("abc").force_string();
+    let chars: Vec<Codepoint> = unibyte.chars().rev().collect();
+    assert_eq!(
+        chars,
+        vec![
+            Codepoint::from('c'),
+            Codepoint::from('b'),
+            Codepoint::from('a')
+        ]
+    );
+}
+
+#[test]
+fn test_char_conversions() {
+    assert_eq!(char::try_from(LispObject::from('a')).ok(), Some('a'));
+    assert_eq!(
+        char::try_from(LispObject::from('\u{1F600}')).ok(),
+        Some('\u{1F600}')
+    );
+    let raw_byte = LispObject::from(Codepoint::from_raw(0xC8));
+    assert!(char::try_from(raw_byte).is_err());
+    assert!(char::try_from(LispObject::from_fixnum(0xD800)).is_err());
+    assert!(char::try_from(LispObject::from_fixnum(-1)).is_err());
+}
+
+#[test]
+fn test_string_search() {
+    let haystack = mock_unibyte_string!("hello world").force_string();
+    let needle = mock_unibyte_string!("o").force_string();
+    assert_eq!(string_search(needle, haystack, None), Some(4));
+    assert_eq!(string_search(needle, haystack, Some(5)), Some(7));
+    assert_eq!(string_search(needle, haystack, Some(8)), None);
+
+    let empty = mock_unibyte_string!("").force_string();
+    assert_eq!(string_search(empty, haystack, Some(3)), Some(3));
+    assert_eq!(string_search(empty, haystack, Some(11)), Some(11));
+
+    let haystack = mock_multibyte_string!("aé€b€").force_string();
+    let needle = mock_multibyte_string!("€").force_string();
+    assert_eq!(string_search(needle, haystack, None), Some(2));
+    assert_eq!(string_search(needle, haystack, Some(3)), Some(4));
+    let needle = mock_multibyte_string!("b").force_string();
+    assert_eq!(string_search(needle, haystack, None), Some(3));
+}
+
+#[test]
+fn test_string_prefix_suffix_p() {
+    let string = mock_unibyte_string!("foobar").force_string();
+    let foo = mock_unibyte_string!("foo").force_string();
+    let bar = mock_unibyte_string!("bar").force_string();
+    let long = mock_unibyte_string!("xfoobar").force_string();
+
+    assert!(string_prefix_p(foo, string, false));
+    assert!(!string_prefix_p(bar, string, false));
+    assert!(string_suffix_p(bar, string, false));
+    assert!(!string_suffix_p(foo, string, false));
+    assert!(!string_prefix_p(long, string, false));
+    assert!(!string_suffix_p(long, string, false));
+
+    let multibyte = mock_multibyte_string!("foobar").force_string();
+    assert!(string_prefix_p(foo, multibyte, false));
+    assert!(string_suffix_p(bar, multibyte, false));
+}
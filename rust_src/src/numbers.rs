@@ -191,6 +191,13 @@ impl LispNumber {
             LispNumber::Float(v) => v as EmacsInt,
         }
     }
+
+    pub fn to_float(&self) -> EmacsDouble {
+        match *self {
+            LispNumber::Fixnum(v) => v as EmacsDouble,
+            LispNumber::Float(v) => v,
+        }
+    }
 }
 
 impl LispStructuralEqual for EmacsInt {
@@ -7,12 +7,12 @@ use crate::{
     alloc::purecopy,
     lisp::LispObject,
     multibyte::{LispStringRef, LispSymbolOrString},
-    remacs_sys::Fmake_symbol,
     remacs_sys::{
         fatal_error_in_progress, globals, initial_obarray, initialized, intern_sym,
         make_pure_c_string, make_unibyte_string, oblookup,
     },
-    remacs_sys::{Qnil, Qvectorp},
+    remacs_sys::{EmacsInt, Fmake_symbol, Fmake_vector},
+    remacs_sys::{Qnatnump, Qnil, Qunbound, Qvectorp},
     symbols::LispSymbolRef,
     vectors::LispVectorRef,
 };
@@ -258,4 +258,35 @@ pub fn mapatoms(function: LispObject, obarray: Option<LispObarrayRef>) {
     map_obarray(obarray.into(), mapatoms_1, function);
 }
 
+/// Return the value of `obarray-default-size', falling back to the
+/// same arbitrary prime when obarray.el has not been loaded yet.
+fn obarray_default_size() -> EmacsInt {
+    let size = unsafe { intern("obarray-default-size").find_value() };
+    if size == Qunbound {
+        59
+    } else {
+        size.as_fixnum_or_error()
+    }
+}
+
+/// Return a new obarray of size SIZE or `obarray-default-size'.
+#[lisp_fn(min = "0")]
+pub fn obarray_make(size: Option<EmacsInt>) -> LispObject {
+    let size = size.unwrap_or_else(obarray_default_size);
+    if size < 0 {
+        wrong_type!(Qnatnump, size);
+    }
+    // An obarray needs at least one bucket.
+    if size == 0 {
+        args_out_of_range!(size);
+    }
+    unsafe { Fmake_vector(size.into(), LispObject::from(0)) }
+}
+
+/// Return t if OBJECT is an obarray.
+#[lisp_fn]
+pub fn obarrayp(object: LispObject) -> bool {
+    object.as_vector().map_or(false, |v| v.len() > 0)
+}
+
 include!(concat!(env!("OUT_DIR"), "/obarray_exports.rs"));
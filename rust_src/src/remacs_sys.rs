@@ -83,12 +83,6 @@ extern "C" {
     pub fn map_keymap_char_table_item(args: LispObject, key: LispObject, val: LispObject);
     pub static initial_obarray: LispObject;
     pub static oblookup_last_bucket_number: libc::size_t;
-    pub fn scan_lists(
-        from: EmacsInt,
-        count: EmacsInt,
-        depth: EmacsInt,
-        sexpflag: bool,
-    ) -> LispObject;
     pub fn read_minibuf(
         map: Lisp_Object,
         initial: Lisp_Object,
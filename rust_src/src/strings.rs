@@ -7,6 +7,7 @@ use libc;
 use remacs_macros::lisp_fn;
 
 use crate::{
+    fns::compare_strings,
     lisp::LispObject,
     multibyte,
     multibyte::{LispStringRef, LispSymbolOrString},
@@ -47,6 +48,15 @@ pub fn string_equal_lisp(s1: LispSymbolOrString, s2: LispSymbolOrString) -> bool
     string_equal(s1, s2)
 }
 
+/// Compare STRING1 and STRING2 case-insensitively.
+/// Upper-case and lower-case letters are treated as equal.
+/// Unibyte strings are converted to multibyte for comparison.
+/// Symbols are also allowed; their print names are used instead.
+#[lisp_fn]
+pub fn string_equal_ignore_case(string1: LispSymbolOrString, string2: LispSymbolOrString) -> bool {
+    compare_strings(string1.into(), None, None, string2.into(), None, None, true).is_t()
+}
+
 /// Return a multibyte string with the same individual bytes as STRING.
 /// If STRING is multibyte, the result is STRING itself.
 /// Otherwise it is a newly created string, with no text properties.
@@ -187,6 +197,51 @@ pub fn string_width(string: LispStringRef) -> usize {
     string.width()
 }
 
+/// Compute the Levenshtein distance between S1 and S2, keeping a
+/// single row of the edit matrix sized after the shorter sequence.
+fn levenshtein_distance<T: PartialEq>(s1: &[T], s2: &[T]) -> usize {
+    let (long, short) = if s1.len() < s2.len() {
+        (s2, s1)
+    } else {
+        (s1, s2)
+    };
+
+    let mut row: Vec<usize> = (0..=short.len()).collect();
+    for (i, l) in long.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, s) in short.iter().enumerate() {
+            let above = row[j + 1];
+            let cost = if l == s { 0 } else { 1 };
+            row[j + 1] = (above + 1).min(row[j] + 1).min(diagonal + cost);
+            diagonal = above;
+        }
+    }
+    row[short.len()]
+}
+
+/// Return Levenshtein distance between STRING1 and STRING2.
+/// The distance is the number of deletions, insertions, and substitutions
+/// required to transform STRING1 into STRING2.
+/// If BYTECOMPARE is nil or omitted, compute distance in terms of characters.
+/// If BYTECOMPARE is non-nil, compute distance in terms of bytes.
+/// Letter-case is significant, but text properties are ignored.
+#[lisp_fn(min = "2")]
+pub fn string_distance(
+    string1: LispStringRef,
+    string2: LispStringRef,
+    bytecompare: bool,
+) -> EmacsInt {
+    let distance = if bytecompare {
+        levenshtein_distance(string1.as_slice(), string2.as_slice())
+    } else {
+        let chars1: Vec<_> = string1.char_indices().map(|(_, c)| c).collect();
+        let chars2: Vec<_> = string2.char_indices().map(|(_, c)| c).collect();
+        levenshtein_distance(&chars1, &chars2)
+    };
+    distance as EmacsInt
+}
+
 macro_rules! new_unibyte_string {
     ($str:expr) => {{
         let strg = ::std::ffi::CString::new($str).unwrap();
@@ -253,3 +308,14 @@ fn test_stringlessp() {
     assert!(string_lessp(string, string2));
     assert!(!string_lessp(string2, string));
 }
+
+#[test]
+fn test_levenshtein_distance() {
+    assert_eq!(levenshtein_distance(b"", b""), 0);
+    assert_eq!(levenshtein_distance(b"abcd", b""), 4);
+    assert_eq!(levenshtein_distance(b"", b"abcd"), 4);
+    assert_eq!(levenshtein_distance(b"abcd", b"efgh"), 4);
+    assert_eq!(levenshtein_distance(b"abcd", b"abcdefgh"), 4);
+    assert_eq!(levenshtein_distance(b"kitten", b"sitting"), 3);
+    assert_eq!(levenshtein_distance(b"sitting", b"kitten"), 3);
+}
@@ -21,13 +21,18 @@ use crate::{
     multibyte::LispStringRef,
     remacs_sys::specbind_tag,
     remacs_sys::Fframe_terminal,
-    remacs_sys::{equal_kind, lispsym, EmacsInt, Lisp_Symbol, Lisp_Type, USE_LSB_TAG},
+    remacs_sys::{
+        allocate_pseudovector, equal_kind, lispsym, pvec_type, EmacsInt, Lisp_Symbol,
+        Lisp_Symbol_With_Pos, Lisp_Type, USE_LSB_TAG,
+    },
     remacs_sys::{
         get_symbol_declared_special, get_symbol_redirect, make_lisp_symbol,
         set_symbol_declared_special, set_symbol_redirect, swap_in_symval_forwarding,
         symbol_interned, symbol_redirect, symbol_trapped_write,
     },
-    remacs_sys::{Qcyclic_variable_indirection, Qnil, Qsymbolp, Qunbound},
+    remacs_sys::{
+        Qcyclic_variable_indirection, Qintegerp, Qnil, Qsymbol_with_pos_p, Qsymbolp, Qunbound,
+    },
     threads::ThreadState,
 };
 
@@ -179,6 +184,12 @@ impl LispSymbolRef {
         LispBufferLocalValueRef::new(s.val.blv)
     }
 
+    pub fn set_blv(mut self, mut blv: LispBufferLocalValueRef) {
+        assert!(self.get_redirect() == symbol_redirect::SYMBOL_LOCALIZED && !blv.is_null());
+        let s = unsafe { self.u.s.as_mut() };
+        s.val.blv = blv.as_mut();
+    }
+
     pub unsafe fn get_fwd(self) -> *mut Lisp_Fwd {
         let s = self.u.s.as_ref();
         s.val.fwd
@@ -355,6 +366,64 @@ impl Iterator for LispSymbolIter {
     }
 }
 
+pub type LispSymbolWithPosRef = ExternalPtr<Lisp_Symbol_With_Pos>;
+
+impl LispSymbolWithPosRef {
+    /// Allocate a new symbol with position pairing SYM with POS.
+    pub fn allocate(sym: LispSymbolRef, pos: EmacsInt) -> Self {
+        // Both slots are Lisp objects, so the whole structure is traced by GC.
+        let lisplen = vecsize!(Lisp_Symbol_With_Pos) as libc::c_int;
+        let mut sp = Self::new(unsafe {
+            allocate_pseudovector(lisplen, lisplen, lisplen, pvec_type::PVEC_SYMBOL_WITH_POS)
+        } as *mut Lisp_Symbol_With_Pos);
+        sp.sym = sym.into();
+        sp.pos = pos.into();
+        sp
+    }
+
+    pub fn bare_symbol(self) -> LispSymbolRef {
+        self.sym.force_symbol()
+    }
+
+    pub fn position(self) -> EmacsInt {
+        self.pos.as_fixnum_or_error()
+    }
+}
+
+impl From<LispObject> for LispSymbolWithPosRef {
+    fn from(o: LispObject) -> Self {
+        if let Some(sp) = o.as_symbol_with_pos() {
+            sp
+        } else {
+            wrong_type!(Qsymbol_with_pos_p, o)
+        }
+    }
+}
+
+impl From<LispSymbolWithPosRef> for LispObject {
+    fn from(sp: LispSymbolWithPosRef) -> Self {
+        Self::tag_ptr(sp, Lisp_Type::Lisp_Vectorlike)
+    }
+}
+
+impl LispObject {
+    pub fn is_symbol_with_pos(self) -> bool {
+        self.as_vectorlike().map_or(false, |v| {
+            v.is_pseudovector(pvec_type::PVEC_SYMBOL_WITH_POS)
+        })
+    }
+
+    pub fn as_symbol_with_pos(self) -> Option<LispSymbolWithPosRef> {
+        if self.is_symbol_with_pos() {
+            Some(LispSymbolWithPosRef::new(
+                self.get_untaggedptr() as *mut Lisp_Symbol_With_Pos
+            ))
+        } else {
+            None
+        }
+    }
+}
+
 // Wrapper around LispSymbolRef::get_indirect_variable()
 // could be removed when all C references are ported
 #[no_mangle]
@@ -612,4 +681,45 @@ pub unsafe extern "C" fn swap_in_global_binding(symbol: *mut Lisp_Symbol) {
     LispSymbolRef::new(symbol).swap_in_global_binding();
 }
 
+/// Return t if OBJECT is a symbol together with position.
+#[lisp_fn]
+pub fn symbol_with_pos_p(object: LispObject) -> bool {
+    object.is_symbol_with_pos()
+}
+
+/// Extract, if need be, the bare symbol from SYM, a symbol.
+#[lisp_fn]
+pub fn bare_symbol(sym: LispObject) -> LispSymbolRef {
+    match sym.as_symbol_with_pos() {
+        Some(sp) => sp.bare_symbol(),
+        None => sym.into(),
+    }
+}
+
+/// Extract the position from a symbol with position.
+#[lisp_fn]
+pub fn symbol_with_pos_pos(ls: LispSymbolWithPosRef) -> EmacsInt {
+    ls.position()
+}
+
+/// Create a new symbol with position.
+/// SYM is a symbol, with or without position, the symbol to position.
+/// POS, the position, is either a fixnum or a symbol with position from which
+/// the position will be taken.
+#[lisp_fn]
+pub fn position_symbol(sym: LispObject, pos: LispObject) -> LispSymbolWithPosRef {
+    let bare = bare_symbol(sym);
+    let position = match pos.as_symbol_with_pos() {
+        Some(sp) => sp.position(),
+        None => match pos.as_fixnum() {
+            Some(n) => n,
+            None => wrong_type!(Qintegerp, pos),
+        },
+    };
+
+    LispSymbolWithPosRef::allocate(bare, position)
+}
+
+def_lisp_sym!(Qsymbol_with_pos_p, "symbol-with-pos-p");
+
 include!(concat!(env!("OUT_DIR"), "/symbols_exports.rs"));
@@ -1,20 +1,44 @@
 //! Functions related to syntax
 
+use std::{ptr, slice};
+
+use libc::{c_int, ptrdiff_t};
+
 use remacs_macros::lisp_fn;
 
 use crate::{
+    buffers::{validate_region_rust, LispBufferRef},
     chartable::LispCharTableRef,
-    editfns::constrain_to_field,
+    data::aref,
+    editfns::{
+        bolp, buffer_substring_no_properties, char_after, constrain_to_field, goto_char,
+        line_beginning_position, line_end_position, point, point_max, point_min,
+        save_excursion_save,
+    },
+    eval::{record_unwind_protect, signal, unbind_to},
     fns::copy_sequence,
     lisp::LispObject,
+    lists::{nth, LispConsCircularChecks, LispConsEndChecks},
+    multibyte::Codepoint,
     numbers::LispNumber,
-    remacs_sys::Fset_char_table_parent,
+    obarray::intern,
+    remacs_sys::{
+        back_comment, buffer_defaults, char_quoted, clear_regexp_cache, forw_comment,
+        internal_condition_case_n, lisp_parse_state, maybe_quit, parse_state_style,
+        prev_char_comend_first, save_excursion_restore, save_restriction_restore,
+        save_restriction_save, scan_words, set_char_table_defalt, set_point, set_point_both,
+        skip_chars, skip_syntaxes, syntax_property, syntax_property_with_flags, syntaxcode,
+        SETUP_SYNTAX_TABLE, UPDATE_SYNTAX_TABLE_BACKWARD, UPDATE_SYNTAX_TABLE_FORWARD,
+    },
+    remacs_sys::{globals, EmacsInt, Qnil, Qscan_error, Qsyntax_table, Qsyntax_table_p, Qt},
     remacs_sys::{
-        buffer_defaults, scan_lists, scan_words, set_char_table_defalt, set_point, skip_chars,
-        skip_syntaxes,
+        Fforward_comment, Fnarrow_to_region, Fset_char_table_parent, Fset_char_table_range,
+        Fstring_to_syntax,
     },
-    remacs_sys::{EmacsInt, Qnil, Qsyntax_table, Qsyntax_table_p},
-    threads::ThreadState,
+    symbols::symbol_value,
+    textprop::get_char_property,
+    threads::{c_specpdl_index, ThreadState},
+    util::clip_to_bounds,
 };
 
 /// Return the current syntax table. This is the one specified by the
@@ -58,7 +82,7 @@ def_lisp_sym!(Qsyntax_table_p, "syntax-table-p");
 // with the same name
 #[lisp_fn(name = "scan-lists", c_name = "scan_lists")]
 pub fn scan_lists_lisp(from: EmacsInt, count: EmacsInt, depth: EmacsInt) -> LispObject {
-    unsafe { scan_lists(from, count, depth, false) }
+    scan_lists(from, count, depth, false)
 }
 
 /// Select a new syntax table for the current buffer.
@@ -95,6 +119,122 @@ pub extern "C" fn check_syntax_table(obj: LispObject) {
     }
 }
 
+/// Return the matching parenthesis of CHARACTER, or nil if none.
+#[lisp_fn]
+pub fn matching_paren(character: Codepoint) -> LispObject {
+    let table = syntax_table().force_char_table();
+    let entry = table.get(u32::from(character) as isize);
+    match entry.as_cons() {
+        Some(cons) => {
+            let code = cons.car().as_fixnum().map_or(-1, |c| c & 0xff);
+            if code == syntaxcode::Sopen as EmacsInt || code == syntaxcode::Sclose as EmacsInt {
+                cons.cdr()
+            } else {
+                Qnil
+            }
+        }
+        None => Qnil,
+    }
+}
+
+/// Return the raw syntax descriptor for the char after POS.
+/// If POS is outside the buffer's accessible portion, return nil.
+#[lisp_fn]
+pub fn syntax_after(pos: LispObject) -> LispObject {
+    let charpos = pos.as_fixnum_coerce_marker_or_error();
+    if charpos < point_min() || charpos >= point_max() {
+        return Qnil;
+    }
+    let st = if unsafe { globals.parse_sexp_lookup_properties } {
+        get_char_property(charpos.into(), Qsyntax_table, Qnil)
+    } else {
+        Qnil
+    };
+    if st.is_cons() {
+        return st;
+    }
+    let table = if st.is_nil() { syntax_table() } else { st };
+    let c = char_after(charpos.into()).unwrap_or_else(|| error!("Args out of range"));
+    aref(table, c)
+}
+
+/// Set syntax for character CHAR according to string NEWENTRY.
+/// The syntax is changed only for table SYNTAX-TABLE, which defaults to
+///  the current buffer's syntax table.
+/// CHAR may be a cons (MIN . MAX), in which case, syntaxes of all characters
+/// in the range MIN to MAX are changed.
+/// The first character of NEWENTRY should be one of the following:
+///   Space or -  whitespace syntax.    w   word constituent.
+///   _           symbol constituent.   .   punctuation.
+///   (           open-parenthesis.     )   close-parenthesis.
+///   "           string quote.         \\   escape.
+///   $           paired delimiter.     \\='   expression quote or prefix operator.
+///   <           comment starter.      >   comment ender.
+///   /           character-quote.      @   inherit from parent table.
+///   |           generic string fence. !   generic comment fence.
+///
+/// Only single-character comment start and end sequences are represented thus.
+/// Two-character sequences are represented as described below.
+/// The second character of NEWENTRY is the matching parenthesis,
+///  used only if the first character is `(' or `)'.
+/// Any additional characters are flags.
+/// Defined flags are the characters 1, 2, 3, 4, b, p, and n.
+///  1 means CHAR is the start of a two-char comment start sequence.
+///  2 means CHAR is the second character of such a sequence.
+///  3 means CHAR is the start of a two-char comment end sequence.
+///  4 means CHAR is the second character of such a sequence.
+///
+/// There can be several orthogonal comment sequences.  This is to support
+/// language modes such as C++.  By default, all comment sequences are of style
+/// a, but you can set the comment sequence style to b (on the second character
+/// of a comment-start, and the first character of a comment-end sequence) and/or
+/// c (on any of its chars) using this flag:
+///  b means CHAR is part of comment sequence b.
+///  c means CHAR is part of comment sequence c.
+///  n means CHAR is part of a nestable comment sequence.
+///
+///  p means CHAR is a prefix character for `backward-prefix-chars';
+///    such characters are treated as whitespace when they occur
+///    between expressions.
+/// usage: (modify-syntax-entry CHAR NEWENTRY &optional SYNTAX-TABLE)
+#[lisp_fn(
+    min = "2",
+    intspec = "cSet syntax for character: \nsSet syntax for %s to: "
+)]
+pub fn modify_syntax_entry(c: LispObject, newentry: LispObject, syntax_table: LispObject) {
+    // A single character, or None for a (MIN . MAX) range.
+    let ch = match c.as_cons() {
+        Some(range) => {
+            let _: Codepoint = range.car().into();
+            let _: Codepoint = range.cdr().into();
+            None
+        }
+        None => Some(Codepoint::from(c)),
+    };
+
+    let syntax_table = if syntax_table.is_nil() {
+        ThreadState::current_buffer_unchecked().syntax_table_
+    } else {
+        check_syntax_table(syntax_table);
+        syntax_table
+    };
+
+    let newentry = unsafe { Fstring_to_syntax(newentry) };
+    match ch {
+        Some(ch) => {
+            let table: LispCharTableRef = syntax_table.into();
+            table.set_unchecked(u32::from(ch) as isize, newentry);
+        }
+        None => unsafe {
+            Fset_char_table_range(syntax_table, c, newentry);
+        },
+    }
+
+    // We clear the regexp cache, since character classes can now have
+    // different values from those in the compiled regexps.
+    unsafe { clear_regexp_cache() };
+}
+
 /// Construct a new syntax table and return it.
 /// It is a copy of the TABLE, which defaults to the standard syntax table.
 #[lisp_fn(min = "0")]
@@ -157,6 +297,22 @@ pub fn forward_word(arg: Option<EmacsInt>) -> bool {
     val == orig_val
 }
 
+/// Move backward until encountering the beginning of a word.
+/// With argument ARG, do this that many times.
+/// If ARG is omitted or nil, move point backward one word.
+///
+/// The word boundaries are normally determined by the buffer's
+/// syntax table and character script (according to
+/// `char-script-table'), but `find-word-boundary-function-table',
+/// such as set up by `subword-mode', can change that.  If a Lisp
+/// program needs to move by words determined strictly by the syntax
+/// table, it should use `backward-word-strictly' instead.  See Info
+/// node `(elisp) Word Motion' for details.
+#[lisp_fn(min = "0", intspec = "^p")]
+pub fn backward_word(arg: Option<EmacsInt>) -> bool {
+    forward_word(Some(-arg.unwrap_or(1)))
+}
+
 /// Move point forward, stopping before a char not in STRING, or at pos LIM.
 /// STRING is like the inside of a `[...]' in a regular expression
 /// except that `]' is never special and `\\' quotes `^', `-' or `\\'
@@ -212,7 +368,1484 @@ pub fn skip_syntax_backward(syntax: LispObject, lim: LispObject) -> LispObject {
 /// but before count is used up, nil is returned.
 #[lisp_fn]
 pub fn scan_sexps(from: EmacsInt, count: EmacsInt) -> LispObject {
-    unsafe { scan_lists(from, count, 0, true) }
+    scan_lists(from, count, 0, true)
+}
+
+// Accessors for the flags stored above the syntax class in the car of
+// a syntax entry.  See the comment at the top of syntax.c.
+
+fn syntax_flags_comstart_first(flags: c_int) -> bool {
+    (flags >> 16) & 1 != 0
+}
+
+fn syntax_flags_comstart_second(flags: c_int) -> bool {
+    (flags >> 17) & 1 != 0
+}
+
+fn syntax_flags_comend_second(flags: c_int) -> bool {
+    (flags >> 19) & 1 != 0
+}
+
+fn syntax_flags_prefix(flags: c_int) -> bool {
+    (flags >> 20) & 1 != 0
+}
+
+fn syntax_flags_comment_nested(flags: c_int) -> bool {
+    (flags >> 22) & 1 != 0
+}
+
+/// FLAGS should be the flags of the main char of the comment marker,
+/// e.g. the second for comstart and the first for comend.
+fn syntax_flags_comment_style(flags: c_int, other_flags: c_int) -> c_int {
+    ((flags >> 21) & 1) | ((flags >> 22) & 2) | ((other_flags >> 22) & 2)
+}
+
+fn syntax_flags_comstartend_first(flags: c_int) -> bool {
+    flags & 0x50000 != 0
+}
+
+/// Return the syntax class stored in the low byte of FLAGS.
+fn syntax_flags_class(flags: c_int) -> syntaxcode {
+    const CLASSES: [syntaxcode; syntaxcode::Smax as usize] = [
+        syntaxcode::Swhitespace,
+        syntaxcode::Spunct,
+        syntaxcode::Sword,
+        syntaxcode::Ssymbol,
+        syntaxcode::Sopen,
+        syntaxcode::Sclose,
+        syntaxcode::Squote,
+        syntaxcode::Sstring,
+        syntaxcode::Smath,
+        syntaxcode::Sescape,
+        syntaxcode::Scharquote,
+        syntaxcode::Scomment,
+        syntaxcode::Sendcomment,
+        syntaxcode::Sinherit,
+        syntaxcode::Scomment_fence,
+        syntaxcode::Sstring_fence,
+    ];
+    CLASSES
+        .get((flags & 0xff) as usize)
+        .cloned()
+        .unwrap_or(syntaxcode::Smax)
+}
+
+/// Return the syntax class of C, consulting the syntax-table
+/// properties recorded in `gl_state`.
+fn syntax(c: Codepoint) -> syntaxcode {
+    unsafe { syntax_property(c.val() as c_int, true) }
+}
+
+/// Return the syntax class and flags of C, consulting the
+/// syntax-table properties recorded in `gl_state`.
+fn syntax_with_flags(c: Codepoint) -> c_int {
+    unsafe { syntax_property_with_flags(c.val() as c_int, true) }
+}
+
+/// Return the syntax class of C if it is an ASCII character or
+/// MULTIBYTE_SYMBOL_P is false.  Otherwise, return `Ssymbol`.
+fn syntax_multibyte(c: Codepoint, multibyte_symbol_p: bool) -> syntaxcode {
+    if c.is_ascii() || !multibyte_symbol_p {
+        syntax(c)
+    } else {
+        syntaxcode::Ssymbol
+    }
+}
+
+/// Check for a quit once every 65536 calls, counting in COUNT.
+fn rarely_quit(count: &mut u16) {
+    *count = count.wrapping_add(1);
+    if *count == 0 {
+        unsafe { maybe_quit() };
+    }
+}
+
+/// A position in the current buffer, kept as both a character and a
+/// byte position while the scanners below step over it.
+#[derive(Clone, Copy)]
+struct ScanPos {
+    buffer: LispBufferRef,
+    charpos: isize,
+    bytepos: isize,
+}
+
+impl ScanPos {
+    fn new(charpos: isize) -> Self {
+        let buffer = ThreadState::current_buffer_unchecked();
+        Self {
+            buffer,
+            charpos,
+            bytepos: buffer.charpos_to_bytepos(charpos),
+        }
+    }
+
+    /// Move forward one character, like INC_BOTH.
+    fn inc(&mut self) {
+        self.charpos += 1;
+        self.bytepos = if self.buffer.multibyte_characters_enabled() {
+            self.buffer.inc_pos(self.bytepos)
+        } else {
+            self.bytepos + 1
+        };
+    }
+
+    /// Move back one character, like DEC_BOTH.
+    fn dec(&mut self) {
+        self.charpos -= 1;
+        self.bytepos = self.prev_bytepos();
+    }
+
+    /// Return the byte position of the character before this one.
+    fn prev_bytepos(self) -> isize {
+        if self.buffer.multibyte_characters_enabled() {
+            self.buffer.dec_pos(self.bytepos)
+        } else {
+            self.bytepos - 1
+        }
+    }
+
+    /// Return the character at byte position BYTEPOS, converting a
+    /// unibyte buffer's bytes to multibyte characters.
+    fn char_at(self, bytepos: isize) -> Codepoint {
+        if self.buffer.multibyte_characters_enabled() {
+            self.buffer.fetch_multibyte_char(bytepos)
+        } else {
+            Codepoint::from(self.buffer.fetch_byte(bytepos)).unibyte_to_char()
+        }
+    }
+
+    /// Return the character at this position.
+    fn char(self) -> Codepoint {
+        self.char_at(self.bytepos)
+    }
+
+    /// Return true if the character here is quoted by an escape or
+    /// character quote.
+    fn is_quoted(self) -> bool {
+        unsafe { char_quoted(self.charpos, self.bytepos) }
+    }
+
+    /// Skip forward over the rest of a comment that started before
+    /// this position, stopping at STOP.  Return true if the end of the
+    /// comment was found.  See `forw_comment` for the other arguments.
+    fn skip_comment_forward(
+        &mut self,
+        stop: isize,
+        nesting: EmacsInt,
+        style: c_int,
+        prev_syntax: c_int,
+        incomment: &mut EmacsInt,
+        last_syntax: &mut c_int,
+    ) -> bool {
+        let (mut charpos, mut bytepos) = (0, 0);
+        let found = unsafe {
+            forw_comment(
+                self.charpos,
+                self.bytepos,
+                stop,
+                nesting,
+                style,
+                prev_syntax,
+                &mut charpos,
+                &mut bytepos,
+                incomment,
+                last_syntax,
+            )
+        };
+        self.charpos = charpos;
+        self.bytepos = bytepos;
+        found
+    }
+
+    /// If this position ends a comment, move back to its start and
+    /// return true.  Do not move back before STOP.
+    fn skip_comment_backward(&mut self, stop: isize, comnested: bool, comstyle: c_int) -> bool {
+        let (mut charpos, mut bytepos) = (0, 0);
+        let found = unsafe {
+            back_comment(
+                self.charpos,
+                self.bytepos,
+                stop,
+                comnested,
+                comstyle,
+                &mut charpos,
+                &mut bytepos,
+            )
+        };
+        if found {
+            self.charpos = charpos;
+            self.bytepos = bytepos;
+        }
+        found
+    }
+}
+
+/// Signal a `scan-error' for unbalanced parentheses met between
+/// LAST_GOOD and FROM.
+fn unbalanced_parentheses(last_good: EmacsInt, from: isize) -> ! {
+    xsignal!(
+        Qscan_error,
+        "Unbalanced parentheses",
+        last_good,
+        from as EmacsInt
+    )
+}
+
+/// Signal a `scan-error' for a list that closes before the depth we
+/// started scanning at.
+fn ends_prematurely(last_good: EmacsInt, from: isize) -> ! {
+    xsignal!(
+        Qscan_error,
+        "Containing expression ends prematurely",
+        last_good,
+        from as EmacsInt
+    )
+}
+
+/// Scan from FROM over COUNT lists, or over COUNT balanced expressions
+/// if SEXPFLAG, starting DEPTH parentheses deep.  Return the position
+/// reached, or nil if the buffer edge is reached between lists.  This
+/// is the core of `scan-lists' and `scan-sexps'.
+pub fn scan_lists(
+    from: EmacsInt,
+    mut count: EmacsInt,
+    mut depth: EmacsInt,
+    sexpflag: bool,
+) -> LispObject {
+    let buffer = ThreadState::current_buffer_unchecked();
+    let stop = if count > 0 { buffer.zv } else { buffer.begv };
+    let ignore_comments = unsafe { globals.parse_sexp_ignore_comments };
+    let multibyte_symbol_p = sexpflag && unsafe { globals.multibyte_syntax_as_symbol };
+    // Err out if depth gets less than this.
+    let min_depth = if depth > 0 { 0 } else { depth };
+    let mut mathexit = false;
+    let mut last_good = from;
+    let mut quit_count = 0;
+
+    let mut pos = ScanPos::new(clip_to_bounds(buffer.begv, from, buffer.zv));
+
+    unsafe {
+        maybe_quit();
+        SETUP_SYNTAX_TABLE(pos.charpos, count as isize);
+    }
+
+    while count > 0 {
+        let mut done = false;
+
+        'forward: while pos.charpos < stop {
+            rarely_quit(&mut quit_count);
+            unsafe { UPDATE_SYNTAX_TABLE_FORWARD(pos.charpos) };
+            let mut c = pos.char();
+            let syntax = syntax_with_flags(c);
+            let mut code = syntax_multibyte(c, multibyte_symbol_p);
+            let mut comnested = syntax_flags_comment_nested(syntax);
+            let mut comstyle = syntax_flags_comment_style(syntax, 0);
+            if depth == min_depth {
+                last_good = pos.charpos as EmacsInt;
+            }
+            pos.inc();
+            unsafe { UPDATE_SYNTAX_TABLE_FORWARD(pos.charpos) };
+            if pos.charpos < stop && syntax_flags_comstart_first(syntax) {
+                c = pos.char();
+                let other_syntax = syntax_with_flags(c);
+                if syntax_flags_comstart_second(other_syntax) && ignore_comments {
+                    // We have encountered a comment start sequence and we
+                    // are ignoring all text inside comments.  We must
+                    // record the comment style this sequence begins so
+                    // that later, only a comment end of the same style
+                    // actually ends the comment section.
+                    code = syntaxcode::Scomment;
+                    comstyle = syntax_flags_comment_style(other_syntax, syntax);
+                    comnested |= syntax_flags_comment_nested(other_syntax);
+                    pos.inc();
+                    unsafe { UPDATE_SYNTAX_TABLE_FORWARD(pos.charpos) };
+                }
+            }
+
+            if syntax_flags_prefix(syntax) {
+                continue;
+            }
+
+            match code {
+                syntaxcode::Sescape
+                | syntaxcode::Scharquote
+                | syntaxcode::Sword
+                | syntaxcode::Ssymbol => {
+                    if code == syntaxcode::Sescape || code == syntaxcode::Scharquote {
+                        if pos.charpos == stop {
+                            unbalanced_parentheses(last_good, pos.charpos);
+                        }
+                        // Treat following character as a word constituent.
+                        pos.inc();
+                    }
+                    if depth != 0 || !sexpflag {
+                        continue;
+                    }
+                    // This word counts as a sexp; return at end of it.
+                    while pos.charpos < stop {
+                        unsafe { UPDATE_SYNTAX_TABLE_FORWARD(pos.charpos) };
+                        match syntax_multibyte(pos.char(), multibyte_symbol_p) {
+                            syntaxcode::Scharquote | syntaxcode::Sescape => {
+                                pos.inc();
+                                if pos.charpos == stop {
+                                    unbalanced_parentheses(last_good, pos.charpos);
+                                }
+                            }
+                            syntaxcode::Sword | syntaxcode::Ssymbol | syntaxcode::Squote => {}
+                            _ => break,
+                        }
+                        pos.inc();
+                        rarely_quit(&mut quit_count);
+                    }
+                    done = true;
+                    break 'forward;
+                }
+
+                syntaxcode::Scomment_fence | syntaxcode::Scomment => {
+                    if code == syntaxcode::Scomment_fence {
+                        comstyle = parse_state_style::ST_COMMENT_STYLE as c_int;
+                    }
+                    if !ignore_comments {
+                        continue;
+                    }
+                    unsafe { UPDATE_SYNTAX_TABLE_FORWARD(pos.charpos) };
+                    let found = pos.skip_comment_forward(
+                        stop,
+                        comnested.into(),
+                        comstyle,
+                        0,
+                        &mut 0,
+                        &mut 0,
+                    );
+                    if !found {
+                        if depth == 0 {
+                            done = true;
+                            break 'forward;
+                        }
+                        unbalanced_parentheses(last_good, pos.charpos);
+                    }
+                    pos.inc();
+                    unsafe { UPDATE_SYNTAX_TABLE_FORWARD(pos.charpos) };
+                }
+
+                syntaxcode::Smath | syntaxcode::Sopen | syntaxcode::Sclose => {
+                    let mut opens = code == syntaxcode::Sopen;
+                    if code == syntaxcode::Smath {
+                        if !sexpflag {
+                            continue;
+                        }
+                        if pos.charpos != stop && c == pos.char() {
+                            pos.inc();
+                        }
+                        // Math delimiters alternately open and close.
+                        opens = !mathexit;
+                        mathexit = !mathexit;
+                    }
+                    depth += if opens { 1 } else { -1 };
+                    if depth == 0 {
+                        done = true;
+                        break 'forward;
+                    }
+                    if !opens && depth < min_depth {
+                        ends_prematurely(last_good, pos.charpos);
+                    }
+                }
+
+                syntaxcode::Sstring | syntaxcode::Sstring_fence => {
+                    let stringterm = pos.char_at(pos.prev_bytepos());
+                    loop {
+                        if pos.charpos >= stop {
+                            unbalanced_parentheses(last_good, pos.charpos);
+                        }
+                        unsafe { UPDATE_SYNTAX_TABLE_FORWARD(pos.charpos) };
+                        let c = pos.char();
+                        let c_code = syntax_multibyte(c, multibyte_symbol_p);
+                        if code == syntaxcode::Sstring {
+                            if c == stringterm && c_code == syntaxcode::Sstring {
+                                break;
+                            }
+                        } else if c_code == syntaxcode::Sstring_fence {
+                            break;
+                        }
+
+                        if c_code == syntaxcode::Scharquote || c_code == syntaxcode::Sescape {
+                            pos.inc();
+                        }
+                        pos.inc();
+                        rarely_quit(&mut quit_count);
+                    }
+                    pos.inc();
+                    if depth == 0 && sexpflag {
+                        done = true;
+                        break 'forward;
+                    }
+                }
+
+                // Ignore whitespace, punctuation, quote, endcomment.
+                _ => {}
+            }
+        }
+
+        // Reached end of buffer.  Error if within object, return nil if
+        // between.
+        if !done {
+            if depth != 0 {
+                unbalanced_parentheses(last_good, pos.charpos);
+            }
+            return Qnil;
+        }
+
+        count -= 1;
+    }
+
+    while count < 0 {
+        let mut done = false;
+
+        'backward: while pos.charpos > stop {
+            rarely_quit(&mut quit_count);
+            pos.dec();
+            unsafe { UPDATE_SYNTAX_TABLE_BACKWARD(pos.charpos) };
+            let c = pos.char();
+            let syntax = syntax_with_flags(c);
+            let mut code = syntax_multibyte(c, multibyte_symbol_p);
+            if depth == min_depth {
+                last_good = pos.charpos as EmacsInt;
+            }
+            let mut comstyle = 0;
+            let mut comnested = syntax_flags_comment_nested(syntax);
+            if code == syntaxcode::Sendcomment {
+                comstyle = syntax_flags_comment_style(syntax, 0);
+            }
+            if pos.charpos > stop
+                && syntax_flags_comend_second(syntax)
+                && unsafe { prev_char_comend_first(pos.charpos, pos.bytepos) }
+                && ignore_comments
+            {
+                // We must record the comment style encountered so that
+                // later, we can match only the proper comment begin
+                // sequence of the same style.
+                pos.dec();
+                unsafe { UPDATE_SYNTAX_TABLE_BACKWARD(pos.charpos) };
+                code = syntaxcode::Sendcomment;
+                let other_syntax = syntax_with_flags(pos.char());
+                comstyle = syntax_flags_comment_style(other_syntax, syntax);
+                comnested |= syntax_flags_comment_nested(other_syntax);
+            }
+
+            // Quoting turns anything except a comment-ender into a word
+            // character.  Note that this cannot be true if we moved back
+            // over a comment ender above.
+            if code != syntaxcode::Sendcomment && pos.is_quoted() {
+                pos.dec();
+                code = syntaxcode::Sword;
+            } else if syntax_flags_prefix(syntax) {
+                continue;
+            }
+
+            match code {
+                syntaxcode::Sword
+                | syntaxcode::Ssymbol
+                | syntaxcode::Sescape
+                | syntaxcode::Scharquote => {
+                    if depth != 0 || !sexpflag {
+                        continue;
+                    }
+                    // This word counts as a sexp; count object finished
+                    // after passing it.
+                    while pos.charpos > stop {
+                        let temp_pos = pos.prev_bytepos();
+                        unsafe { UPDATE_SYNTAX_TABLE_BACKWARD(pos.charpos - 1) };
+                        // Don't allow comment-end to be quoted.
+                        if syntax_multibyte(pos.char_at(temp_pos), multibyte_symbol_p)
+                            == syntaxcode::Sendcomment
+                        {
+                            break;
+                        }
+                        let quoted = unsafe { char_quoted(pos.charpos - 1, temp_pos) };
+                        if quoted {
+                            pos.dec();
+                            unsafe { UPDATE_SYNTAX_TABLE_BACKWARD(pos.charpos - 1) };
+                        } else {
+                            match syntax_multibyte(pos.char_at(temp_pos), multibyte_symbol_p) {
+                                syntaxcode::Sword | syntaxcode::Ssymbol | syntaxcode::Squote => {}
+                                _ => break,
+                            }
+                        }
+                        pos.dec();
+                        rarely_quit(&mut quit_count);
+                    }
+                    done = true;
+                    break 'backward;
+                }
+
+                syntaxcode::Smath | syntaxcode::Sclose | syntaxcode::Sopen => {
+                    let mut closes = code == syntaxcode::Sclose;
+                    if code == syntaxcode::Smath {
+                        if !sexpflag {
+                            continue;
+                        }
+                        if pos.charpos > buffer.begv {
+                            let temp_pos = pos.prev_bytepos();
+                            unsafe { UPDATE_SYNTAX_TABLE_BACKWARD(pos.charpos - 1) };
+                            if pos.charpos != stop && c == pos.char_at(temp_pos) {
+                                pos.dec();
+                            }
+                        }
+                        // Math delimiters alternately close and open.
+                        closes = !mathexit;
+                        mathexit = !mathexit;
+                    }
+                    depth += if closes { 1 } else { -1 };
+                    if depth == 0 {
+                        done = true;
+                        break 'backward;
+                    }
+                    if !closes && depth < min_depth {
+                        ends_prematurely(last_good, pos.charpos);
+                    }
+                }
+
+                syntaxcode::Sendcomment => {
+                    if ignore_comments {
+                        // If no comment start is found, this wasn't
+                        // really a comment end and is just skipped.
+                        // Retrying a two-char ender as two separate
+                        // chars is not worth the trouble.
+                        pos.skip_comment_backward(stop, comnested, comstyle);
+                    }
+                }
+
+                syntaxcode::Scomment_fence | syntaxcode::Sstring_fence => {
+                    loop {
+                        if pos.charpos == stop {
+                            unbalanced_parentheses(last_good, pos.charpos);
+                        }
+                        pos.dec();
+                        unsafe { UPDATE_SYNTAX_TABLE_BACKWARD(pos.charpos) };
+                        if !pos.is_quoted()
+                            && syntax_multibyte(pos.char(), multibyte_symbol_p) == code
+                        {
+                            break;
+                        }
+                        rarely_quit(&mut quit_count);
+                    }
+                    if code == syntaxcode::Sstring_fence && depth == 0 && sexpflag {
+                        done = true;
+                        break 'backward;
+                    }
+                }
+
+                syntaxcode::Sstring => {
+                    let stringterm = pos.char();
+                    loop {
+                        if pos.charpos == stop {
+                            unbalanced_parentheses(last_good, pos.charpos);
+                        }
+                        pos.dec();
+                        unsafe { UPDATE_SYNTAX_TABLE_BACKWARD(pos.charpos) };
+                        if !pos.is_quoted() {
+                            let c = pos.char();
+                            if c == stringterm
+                                && syntax_multibyte(c, multibyte_symbol_p) == syntaxcode::Sstring
+                            {
+                                break;
+                            }
+                        }
+                        rarely_quit(&mut quit_count);
+                    }
+                    if depth == 0 && sexpflag {
+                        done = true;
+                        break 'backward;
+                    }
+                }
+
+                // Ignore whitespace, punctuation, quote, endcomment.
+                _ => {}
+            }
+        }
+
+        // Reached start of buffer.  Error if within object, return nil if
+        // between.
+        if !done {
+            if depth != 0 {
+                unbalanced_parentheses(last_good, pos.charpos);
+            }
+            return Qnil;
+        }
+
+        count += 1;
+    }
+
+    pos.charpos.into()
+}
+
+/// Convert a (lisp) parse state to the internal form used in
+/// `scan_sexps_forward`.  A nil state describes the beginning of a
+/// function.
+fn parse_state_from_lisp(external: LispObject) -> lisp_parse_state {
+    let depth = nth(0, external);
+    let instring = nth(3, external);
+    let incomment = nth(4, external);
+    let comstyle = nth(7, external);
+    let prev_syntax = nth(10, external);
+
+    lisp_parse_state {
+        depth: if depth.is_nil() {
+            0
+        } else {
+            depth.as_fixnum_or_error()
+        },
+        // Check whether we are inside string_fence-style string.
+        instring: if instring.is_nil() {
+            -1
+        } else if instring.is_character() {
+            instring.as_fixnum_or_error() as c_int
+        } else {
+            parse_state_style::ST_STRING_STYLE as c_int
+        },
+        incomment: if incomment.is_nil() {
+            0
+        } else {
+            incomment.as_fixnum().unwrap_or(-1)
+        },
+        quoted: nth(5, external).is_not_nil(),
+        // If the eighth element of the list is nil, we are in comment
+        // style a.  If it is non-nil, we are in comment style b.
+        comstyle: if comstyle.is_nil() {
+            0
+        } else {
+            match comstyle.as_fixnum() {
+                Some(n) if n >= 0 && n <= EmacsInt::from(parse_state_style::ST_COMMENT_STYLE) => {
+                    n as c_int
+                }
+                _ => parse_state_style::ST_COMMENT_STYLE as c_int,
+            }
+        },
+        comstr_start: nth(8, external).as_fixnum().map_or(-1, |n| n as isize),
+        levelstarts: nth(9, external),
+        prev_syntax: if prev_syntax.is_nil() {
+            syntaxcode::Smax as c_int
+        } else {
+            prev_syntax.as_fixnum_or_error() as c_int
+        },
+        // These are filled in by `scan_sexps_forward`.
+        mindepth: 0,
+        thislevelstart: -1,
+        prevlevelstart: -1,
+        location: 0,
+        location_byte: 0,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn internalize_parse_state(
+    external: LispObject,
+    state: *mut lisp_parse_state,
+) {
+    *state = parse_state_from_lisp(external);
+}
+
+/// The scanning position of `scan_sexps_forward`, together with the
+/// position before it and the syntax of the last two characters
+/// passed over.
+struct SexpScan {
+    from: ScanPos,
+    prev_from: ScanPos,
+    prev_from_syntax: c_int,
+    prev_prev_from_syntax: c_int,
+    end: isize,
+}
+
+impl SexpScan {
+    /// Move past the character at `from`, remembering its syntax.
+    fn inc_from(&mut self) {
+        self.prev_from = self.from;
+        self.prev_prev_from_syntax = self.prev_from_syntax;
+        self.prev_from_syntax = syntax_with_flags(self.prev_from.char());
+        self.from.inc();
+        if self.from.charpos < self.end {
+            unsafe { UPDATE_SYNTAX_TABLE_FORWARD(self.from.charpos) };
+        }
+    }
+
+    /// If the character at `from` is the second part of a 2-character
+    /// comment opener begun by the previous one, record the comment in
+    /// STATE and return true.
+    fn in_2char_comment_start(&self, state: &mut lisp_parse_state) -> bool {
+        if !syntax_flags_comstart_first(self.prev_from_syntax) {
+            return false;
+        }
+        let syntax = syntax_with_flags(self.from.char());
+        if !syntax_flags_comstart_second(syntax) {
+            return false;
+        }
+        // Record the comment style we have entered so that only the
+        // comment-end sequence of the same style actually terminates
+        // the comment section.
+        state.comstyle = syntax_flags_comment_style(syntax, self.prev_from_syntax);
+        let comnested = syntax_flags_comment_nested(self.prev_from_syntax)
+            || syntax_flags_comment_nested(syntax);
+        state.incomment = if comnested { 1 } else { -1 };
+        state.comstr_start = self.prev_from.charpos;
+        true
+    }
+}
+
+/// Where `scan_sexps_forward` carries on scanning.
+enum SexpScanStep {
+    /// Between sexps, at the top of the main loop.
+    Top,
+    /// After an escape or character quote that starts a symbol.
+    Quoted,
+    /// Inside a symbol.
+    Symbol,
+    /// Just after a comment starter.
+    AtComment,
+    /// Inside a comment.
+    InComment,
+    /// Inside a string, possibly just after an escape.
+    InString { quoted: bool },
+}
+
+/// How `scan_sexps_forward` stopped.
+enum SexpScanEnd {
+    /// At the end, or at a place that the arguments asked to stop at.
+    Done,
+    /// Just after the start of a sexp, which STOPBEFORE asks to stop
+    /// before.
+    Stop,
+    /// Just after an escape or character quote.
+    Quoted,
+}
+
+/// The sexp starts recorded for one level of parentheses.
+#[derive(Clone, Copy)]
+struct ParseLevel {
+    /// Char number of most recent start-of-expression at this level.
+    last: isize,
+    /// Char number of the start of the previous expression.
+    prev: isize,
+}
+
+/// The deepest nesting of levels that `scan_sexps_forward` keeps
+/// track of.
+const MAX_PARSE_LEVELS: usize = 100;
+
+/// Parse forward from FROM / FROM_BYTE to END, assuming that FROM has
+/// state STATE, and store a description of the state of the parse at
+/// END into STATE.
+/// If STOPBEFORE, stop at the start of an atom.
+/// If COMMENTSTOP is 1, stop at the start of a comment.
+/// If COMMENTSTOP is -1, stop at the start or end of a comment,
+/// after the beginning of a string, or after the end of a string.
+#[no_mangle]
+pub unsafe extern "C" fn scan_sexps_forward(
+    state: *mut lisp_parse_state,
+    from: ptrdiff_t,
+    from_byte: ptrdiff_t,
+    end: ptrdiff_t,
+    targetdepth: EmacsInt,
+    stopbefore: bool,
+    commentstop: c_int,
+) {
+    let state = &mut *state;
+    let buffer = ThreadState::current_buffer_unchecked();
+    let boundary_stop = commentstop == -1;
+    let mut quit_count = 0;
+
+    let from = ScanPos {
+        buffer,
+        charpos: from,
+        bytepos: from_byte,
+    };
+    let mut prev_from = from;
+    if from.charpos != buffer.begv {
+        prev_from.dec();
+    }
+    let mut scan = SexpScan {
+        from,
+        prev_from,
+        prev_from_syntax: state.prev_syntax,
+        prev_prev_from_syntax: syntaxcode::Smax as c_int,
+        end,
+    };
+
+    maybe_quit();
+
+    let mut depth = state.depth;
+    let mut mindepth = depth;
+    let start_quoted = state.quoted;
+    state.quoted = false;
+
+    // The levels we are in, with the innermost one at `curlevel`.
+    // Deeper levels than MAX_PARSE_LEVELS share the last slot.
+    let mut levels = [ParseLevel { last: -1, prev: -1 }; MAX_PARSE_LEVELS];
+    let mut curlevel = 0;
+    for start in state
+        .levelstarts
+        .iter_cars(LispConsEndChecks::on, LispConsCircularChecks::on)
+    {
+        if let Some(start) = start.as_fixnum() {
+            levels[curlevel].last = start as isize;
+        }
+        curlevel = (curlevel + 1).min(MAX_PARSE_LEVELS - 1);
+        levels[curlevel] = ParseLevel { last: -1, prev: -1 };
+    }
+
+    SETUP_SYNTAX_TABLE(scan.from.charpos, 1);
+
+    // Enter the loop at a place appropriate for initial state.
+    let mut step = if state.incomment != 0 {
+        SexpScanStep::InComment
+    } else if state.instring >= 0 {
+        SexpScanStep::InString {
+            quoted: start_quoted,
+        }
+    } else if start_quoted {
+        SexpScanStep::Quoted
+    } else if scan.from.charpos < end && scan.in_2char_comment_start(state) {
+        scan.inc_from();
+        // The syntax has already been "used up".
+        scan.prev_from_syntax = syntaxcode::Smax as c_int;
+        SexpScanStep::AtComment
+    } else {
+        SexpScanStep::Top
+    };
+
+    let stopped = 'scan: loop {
+        step = match step {
+            SexpScanStep::Top => {
+                if scan.from.charpos >= end {
+                    break SexpScanEnd::Done;
+                }
+                rarely_quit(&mut quit_count);
+                scan.inc_from();
+
+                if scan.from.charpos < end && scan.in_2char_comment_start(state) {
+                    scan.inc_from();
+                    scan.prev_from_syntax = syntaxcode::Smax as c_int;
+                    SexpScanStep::AtComment
+                } else if syntax_flags_prefix(scan.prev_from_syntax) {
+                    SexpScanStep::Top
+                } else {
+                    let code = syntax_flags_class(scan.prev_from_syntax);
+                    match code {
+                        syntaxcode::Sescape
+                        | syntaxcode::Scharquote
+                        | syntaxcode::Sword
+                        | syntaxcode::Ssymbol => {
+                            // This arg means stop at sexp start.
+                            if stopbefore {
+                                break SexpScanEnd::Stop;
+                            }
+                            levels[curlevel].last = scan.prev_from.charpos;
+                            if code == syntaxcode::Sescape || code == syntaxcode::Scharquote {
+                                SexpScanStep::Quoted
+                            } else {
+                                SexpScanStep::Symbol
+                            }
+                        }
+
+                        syntaxcode::Scomment_fence => {
+                            // Record the comment style we have entered so
+                            // that only the comment-end sequence of the
+                            // same style actually terminates the comment
+                            // section.
+                            state.comstyle = parse_state_style::ST_COMMENT_STYLE as c_int;
+                            state.incomment = -1;
+                            state.comstr_start = scan.prev_from.charpos;
+                            SexpScanStep::AtComment
+                        }
+
+                        syntaxcode::Scomment => {
+                            state.comstyle = syntax_flags_comment_style(scan.prev_from_syntax, 0);
+                            state.incomment = if syntax_flags_comment_nested(scan.prev_from_syntax)
+                            {
+                                1
+                            } else {
+                                -1
+                            };
+                            state.comstr_start = scan.prev_from.charpos;
+                            SexpScanStep::AtComment
+                        }
+
+                        syntaxcode::Sopen => {
+                            if stopbefore {
+                                break SexpScanEnd::Stop;
+                            }
+                            depth += 1;
+                            levels[curlevel].last = scan.prev_from.charpos;
+                            curlevel = (curlevel + 1).min(MAX_PARSE_LEVELS - 1);
+                            levels[curlevel] = ParseLevel { last: -1, prev: -1 };
+                            if targetdepth == depth {
+                                break SexpScanEnd::Done;
+                            }
+                            SexpScanStep::Top
+                        }
+
+                        syntaxcode::Sclose => {
+                            depth -= 1;
+                            mindepth = mindepth.min(depth);
+                            if curlevel > 0 {
+                                curlevel -= 1;
+                            }
+                            levels[curlevel].prev = levels[curlevel].last;
+                            if targetdepth == depth {
+                                break SexpScanEnd::Done;
+                            }
+                            SexpScanStep::Top
+                        }
+
+                        syntaxcode::Sstring | syntaxcode::Sstring_fence => {
+                            state.comstr_start = scan.from.charpos - 1;
+                            if stopbefore {
+                                break SexpScanEnd::Stop;
+                            }
+                            levels[curlevel].last = scan.prev_from.charpos;
+                            state.instring = if code == syntaxcode::Sstring {
+                                scan.prev_from.char().val() as c_int
+                            } else {
+                                parse_state_style::ST_STRING_STYLE as c_int
+                            };
+                            if boundary_stop {
+                                break SexpScanEnd::Done;
+                            }
+                            SexpScanStep::InString { quoted: false }
+                        }
+
+                        // Ignore whitespace, punctuation, quote, endcomment
+                        // and math delimiters.
+                        _ => SexpScanStep::Top,
+                    }
+                }
+            }
+
+            SexpScanStep::Quoted => {
+                if scan.from.charpos == end {
+                    break SexpScanEnd::Quoted;
+                }
+                // Treat following character as a word constituent.
+                scan.inc_from();
+                SexpScanStep::Symbol
+            }
+
+            SexpScanStep::Symbol => {
+                let mut at_comment = false;
+                while scan.from.charpos < end {
+                    if scan.in_2char_comment_start(state) {
+                        scan.inc_from();
+                        scan.prev_from_syntax = syntaxcode::Smax as c_int;
+                        at_comment = true;
+                        break;
+                    }
+
+                    match syntax(scan.from.char()) {
+                        syntaxcode::Scharquote | syntaxcode::Sescape => {
+                            scan.inc_from();
+                            if scan.from.charpos == end {
+                                break 'scan SexpScanEnd::Quoted;
+                            }
+                        }
+                        syntaxcode::Sword | syntaxcode::Ssymbol | syntaxcode::Squote => {}
+                        _ => break,
+                    }
+                    scan.inc_from();
+                    rarely_quit(&mut quit_count);
+                }
+                if at_comment {
+                    SexpScanStep::AtComment
+                } else {
+                    levels[curlevel].prev = levels[curlevel].last;
+                    SexpScanStep::Top
+                }
+            }
+
+            SexpScanStep::AtComment => {
+                if commentstop != 0 || boundary_stop {
+                    break SexpScanEnd::Done;
+                }
+                SexpScanStep::InComment
+            }
+
+            SexpScanStep::InComment => {
+                // The (from == BEGV) test was to enter the loop in the
+                // middle so that we find a 2-char comment ender even if
+                // we start in the middle of it.  We don't want to do that
+                // if we're just at the beginning of the comment (think of
+                // (*) ... (*)).
+                let prev_syntax = if scan.from.charpos == buffer.begv {
+                    0
+                } else {
+                    scan.prev_from_syntax
+                };
+                let found = scan.from.skip_comment_forward(
+                    end,
+                    state.incomment,
+                    state.comstyle,
+                    prev_syntax,
+                    &mut state.incomment,
+                    &mut scan.prev_from_syntax,
+                );
+                // Beware!  prev_from is invalid now.  Luckily, stopping
+                // doesn't use it and inc_from sets it to a sane value
+                // without looking at it.
+                if !found {
+                    break SexpScanEnd::Done;
+                }
+                scan.inc_from();
+                state.incomment = 0;
+                // Reset the comment style.
+                state.comstyle = 0;
+                // For the comment closer.
+                scan.prev_from_syntax = syntaxcode::Smax as c_int;
+                if boundary_stop {
+                    break SexpScanEnd::Done;
+                }
+                SexpScanStep::Top
+            }
+
+            SexpScanStep::InString { mut quoted } => {
+                let nofence = state.instring != parse_state_style::ST_STRING_STYLE as c_int;
+                loop {
+                    if quoted {
+                        if scan.from.charpos >= end {
+                            break 'scan SexpScanEnd::Quoted;
+                        }
+                        quoted = false;
+                    } else {
+                        if scan.from.charpos >= end {
+                            break 'scan SexpScanEnd::Done;
+                        }
+                        let c = scan.from.char();
+                        let c_code = syntax(c);
+
+                        // Check C_CODE here so that if the char has a
+                        // syntax-table property which says it is NOT a
+                        // string character, it does not end the string.
+                        if nofence
+                            && c.val() as c_int == state.instring
+                            && c_code == syntaxcode::Sstring
+                        {
+                            break;
+                        }
+
+                        match c_code {
+                            syntaxcode::Sstring_fence if !nofence => break,
+                            syntaxcode::Scharquote | syntaxcode::Sescape => {
+                                scan.inc_from();
+                                quoted = true;
+                                continue;
+                            }
+                            _ => {}
+                        }
+                    }
+                    scan.inc_from();
+                    rarely_quit(&mut quit_count);
+                }
+                state.instring = -1;
+                levels[curlevel].prev = levels[curlevel].last;
+                scan.inc_from();
+                if boundary_stop {
+                    break SexpScanEnd::Done;
+                }
+                SexpScanStep::Top
+            }
+        };
+    };
+
+    match stopped {
+        SexpScanEnd::Stop => {
+            // We have just fetched the char that starts the sexp, but
+            // return the position before it.
+            scan.from = scan.prev_from;
+            scan.prev_from_syntax = scan.prev_prev_from_syntax;
+        }
+        SexpScanEnd::Quoted => state.quoted = true,
+        SexpScanEnd::Done => {}
+    }
+
+    state.depth = depth;
+    state.mindepth = mindepth;
+    state.thislevelstart = levels[curlevel].prev;
+    state.prevlevelstart = if curlevel == 0 {
+        -1
+    } else {
+        levels[curlevel - 1].last
+    };
+    state.location = scan.from.charpos;
+    state.location_byte = scan.from.bytepos;
+    state.levelstarts = levels[..curlevel]
+        .iter()
+        .rev()
+        .fold(Qnil, |tail, level| LispObject::cons(level.last, tail));
+    state.prev_syntax = if syntax_flags_comstartend_first(scan.prev_from_syntax) || state.quoted {
+        scan.prev_from_syntax
+    } else {
+        syntaxcode::Smax as c_int
+    };
+}
+
+/// Parse Lisp syntax starting at FROM until TO; return status of parse at TO.
+/// Parsing stops at TO or when certain criteria are met;
+///  point is set to where parsing stops.
+/// If fifth arg OLDSTATE is omitted or nil,
+///  parsing assumes that FROM is the beginning of a function.
+///
+/// Value is a list of elements describing final state of parsing:
+///  0. depth in parens.
+///  1. character address of start of innermost containing list; nil if none.
+///  2. character address of start of last complete sexp terminated.
+///  3. non-nil if inside a string.
+///     (it is the character that will terminate the string,
+///      or t if the string should be terminated by a generic string delimiter.)
+///  4. nil if outside a comment, t if inside a non-nestable comment,
+///     else an integer (the current comment nesting).
+///  5. t if following a quote character.
+///  6. the minimum paren-depth encountered during this scan.
+///  7. style of comment, if any.
+///  8. character address of start of comment or string; nil if not in one.
+///  9. List of positions of currently open parens, outermost first.
+/// 10. When the last position scanned holds the first character of a
+///     (potential) two character construct, the syntax of that position,
+///     otherwise nil.  That construct can be a two character comment
+///     delimiter or an Escaped or Char-quoted character.
+/// 11..... Possible further internal information used by `parse-partial-sexp'.
+///
+/// If third arg TARGETDEPTH is non-nil, parsing stops if the depth
+/// in parentheses becomes equal to TARGETDEPTH.
+/// Fourth arg STOPBEFORE non-nil means stop when we come to
+///  any character that starts a sexp.
+/// Fifth arg OLDSTATE is a list like what this function returns.
+///  It is used to initialize the state of the parse.  Elements number 1, 2, 6
+///  are ignored.
+/// Sixth arg COMMENTSTOP non-nil means stop after the start of a comment.
+///  If it is the symbol `syntax-table', stop after the start of a comment or a
+///  string, or after end of a comment or a string.
+#[lisp_fn(min = "2")]
+pub fn parse_partial_sexp(
+    from: LispObject,
+    to: LispObject,
+    targetdepth: Option<EmacsInt>,
+    stopbefore: bool,
+    oldstate: LispObject,
+    commentstop: LispObject,
+) -> LispObject {
+    // Without a target we won't reach this depth.
+    let target = targetdepth.unwrap_or(EmacsInt::min_value());
+    let (from, to) = validate_region_rust(from, to);
+    let commentstop = if commentstop.is_nil() {
+        0
+    } else if commentstop.eq(Qsyntax_table) {
+        -1
+    } else {
+        1
+    };
+
+    let mut state = parse_state_from_lisp(oldstate);
+    let from_byte = ThreadState::current_buffer_unchecked().charpos_to_bytepos(from);
+    unsafe {
+        scan_sexps_forward(
+            &mut state,
+            from,
+            from_byte,
+            to,
+            target,
+            stopbefore,
+            commentstop,
+        );
+        set_point_both(state.location, state.location_byte);
+    }
+
+    let position_or_nil = |pos: isize| if pos < 0 { Qnil } else { pos.into() };
+    let instring = if state.instring < 0 {
+        Qnil
+    } else if state.instring == parse_state_style::ST_STRING_STYLE as c_int {
+        Qt
+    } else {
+        state.instring.into()
+    };
+    let incomment = match state.incomment {
+        0 => Qnil,
+        n if n < 0 => Qt,
+        n => n.into(),
+    };
+    let comstyle = match state.comstyle {
+        0 => Qnil,
+        n if n == parse_state_style::ST_COMMENT_STYLE as c_int => Qsyntax_table,
+        n => n.into(),
+    };
+    let comstr_start = if state.incomment != 0 || state.instring >= 0 {
+        state.comstr_start.into()
+    } else {
+        Qnil
+    };
+    let prev_syntax = if state.prev_syntax == syntaxcode::Smax as c_int {
+        Qnil
+    } else {
+        state.prev_syntax.into()
+    };
+
+    list!(
+        state.depth,
+        position_or_nil(state.prevlevelstart),
+        position_or_nil(state.thislevelstart),
+        instring,
+        incomment,
+        state.quoted,
+        state.mindepth,
+        comstyle,
+        comstr_start,
+        state.levelstarts,
+        prev_syntax
+    )
+}
+
+/// Move backward out of one level of parentheses.
+/// This command will also work on other parentheses-like expressions
+/// defined by the current language mode.  With ARG, do this that
+/// many times.  A negative argument means move forward but still to
+/// a less deep spot.  If ESCAPE-STRINGS is non-nil (as it is
+/// interactively), move out of enclosing strings as well.  If
+/// NO-SYNTAX-CROSSING is non-nil (as it is interactively), prefer to
+/// break out of any enclosing string instead of moving to the start
+/// of a list broken across multiple strings.  On error, location of
+/// point is unspecified.
+#[lisp_fn(min = "0", intspec = "^p\nd\nd")]
+pub fn backward_up_list(arg: Option<EmacsInt>, escape_strings: bool, no_syntax_crossing: bool) {
+    up_list(Some(-arg.unwrap_or(1)), escape_strings, no_syntax_crossing)
+}
+
+/// Move forward out of one level of parentheses.
+/// This command will also work on other parentheses-like expressions
+/// defined by the current language mode.  With ARG, do this that
+/// many times.  A negative argument means move backward but still to
+/// a less deep spot.  If ESCAPE-STRINGS is non-nil (as it is
+/// interactively), move out of enclosing strings as well. If
+/// NO-SYNTAX-CROSSING is non-nil (as it is interactively), prefer to
+/// break out of any enclosing string instead of moving to the start
+/// of a list broken across multiple strings.  On error, location of
+/// point is unspecified.
+#[lisp_fn(min = "0", intspec = "^p\nd\nd")]
+pub fn up_list(arg: Option<EmacsInt>, escape_strings: bool, no_syntax_crossing: bool) {
+    let mut arg = arg.unwrap_or(1);
+    let inc: EmacsInt = if arg > 0 { 1 } else { -1 };
+
+    while arg != 0 {
+        let mut args = [inc.into(), no_syntax_crossing.into()];
+        // The handler hands back the error data, so a cons here means
+        // scan-lists (or `forward-sexp-function') signaled a scan-error.
+        let err = unsafe {
+            internal_condition_case_n(
+                Some(up_list_1),
+                args.len() as ptrdiff_t,
+                args.as_mut_ptr(),
+                Qscan_error,
+                Some(return_error_data),
+            )
+        };
+
+        if let Some(err) = err.as_cons() {
+            let syntax = if escape_strings || no_syntax_crossing {
+                call!(intern("syntax-ppss").into())
+            } else {
+                Qnil
+            };
+            // If we bumped up against the end of a list, see whether
+            // we're inside a string: if so, just go to the beginning
+            // or end of that string.
+            let escaped = escape_strings && nth(3, syntax).is_not_nil() && {
+                goto_char(nth(8, syntax));
+                if inc > 0 {
+                    call!(intern("forward-sexp").into());
+                }
+                true
+            };
+            // If we narrowed to a comment above and failed to escape
+            // it, the error might be our fault, not an indication
+            // that we're out of syntax.  Try again from beginning or
+            // end of the comment.
+            let retry = !escaped && no_syntax_crossing && nth(4, syntax).is_not_nil() && {
+                goto_char(nth(8, syntax));
+                inc < 0 || unsafe { Fforward_comment(1.into()) }.is_not_nil()
+            };
+            if retry {
+                arg += inc;
+            } else if !escaped {
+                signal(err.car(), err.cdr());
+            }
+        }
+        arg -= inc;
+    }
+}
+
+/// Move out of one list level in direction ARGS[0], inside a
+/// restriction to the enclosing string or comment when ARGS[1] is
+/// non-nil.
+extern "C" fn up_list_1(nargs: ptrdiff_t, args: *mut LispObject) -> LispObject {
+    let args = unsafe { slice::from_raw_parts_mut(args, nargs as usize) };
+    let inc = args[0].as_fixnum_or_error();
+    let buffer_end = || if inc > 0 { point_max() } else { point_min() };
+
+    let count = c_specpdl_index();
+    unsafe { record_unwind_protect(Some(save_restriction_restore), save_restriction_save()) };
+
+    // If we've been asked not to cross string boundaries and we're
+    // inside a string, narrow to that string so that scan-lists
+    // doesn't find a match in a different string.
+    if args[1].is_not_nil() {
+        let syntax = call!(intern("syntax-ppss").into());
+        let string_comment_start = nth(8, syntax);
+        if string_comment_start.is_not_nil() {
+            let oldpoint = point();
+            goto_char(string_comment_start);
+            let end = if nth(3, syntax).is_not_nil() {
+                let end = unsafe {
+                    internal_condition_case_n(
+                        Some(forward_sexp_end),
+                        0,
+                        ptr::null_mut(),
+                        Qscan_error,
+                        Some(return_error_data),
+                    )
+                };
+                if end.is_cons() {
+                    point_max()
+                } else {
+                    point()
+                }
+            } else {
+                unsafe { Fforward_comment(1.into()) };
+                point()
+            };
+            unsafe { Fnarrow_to_region(string_comment_start, end.into()) };
+            goto_char(oldpoint.into());
+        }
+    }
+
+    let forward_sexp_function = symbol_value(intern("forward-sexp-function"));
+    if forward_sexp_function.is_nil() {
+        let pos = scan_lists(point(), inc, 1, false);
+        goto_char(if pos.is_nil() {
+            buffer_end().into()
+        } else {
+            pos
+        });
+    } else {
+        // Keep calling `forward-sexp' until it stops moving or runs
+        // into the end of the enclosing list.
+        let mut sexp_args = [inc.into(), Qnil];
+        let err = unsafe {
+            internal_condition_case_n(
+                Some(forward_sexp_until_stuck),
+                sexp_args.len() as ptrdiff_t,
+                sexp_args.as_mut_ptr(),
+                Qscan_error,
+                Some(return_error_data),
+            )
+        };
+        if err.is_cons() {
+            goto_char(nth(if inc > 0 { 3 } else { 2 }, err));
+        }
+        let pos = sexp_args[1];
+        if point() == pos.as_fixnum_or_error() {
+            xsignal!(Qscan_error, "Unbalanced parentheses", pos, pos);
+        }
+    }
+
+    unbind_to(count, Qnil)
+}
+
+/// Call `forward-sexp' with ARGS[0] until point stops moving,
+/// recording in ARGS[1] the position before the last call.
+extern "C" fn forward_sexp_until_stuck(nargs: ptrdiff_t, args: *mut LispObject) -> LispObject {
+    let args = unsafe { slice::from_raw_parts_mut(args, nargs as usize) };
+    loop {
+        let pos = point();
+        args[1] = pos.into();
+        call!(intern("forward-sexp").into(), args[0]);
+        if point() == pos {
+            return Qnil;
+        }
+    }
+}
+
+/// Move over one balanced expression and return point.
+extern "C" fn forward_sexp_end(_nargs: ptrdiff_t, _args: *mut LispObject) -> LispObject {
+    call!(intern("forward-sexp").into());
+    point().into()
+}
+
+/// Condition handler that returns the error data, so that a caller
+/// can tell a caught signal apart from a normal return value.
+extern "C" fn return_error_data(
+    err: LispObject,
+    _nargs: ptrdiff_t,
+    _args: *mut LispObject,
+) -> LispObject {
+    err
+}
+
+/// Return true if character C has word syntax, or symbol syntax when
+/// REALLY_WORD is false, in the current syntax table.
+/// Return the word at or near point, as a string.
+/// The return value includes no text properties.
+///
+/// If optional arg STRICT is non-nil, return nil unless point is
+/// within or adjacent to a word, otherwise look for a word within
+/// point's line.  If there is no word anywhere on point's line, the
+/// value is nil regardless of STRICT.
+///
+/// By default, this function treats as a single word any sequence of
+/// characters that have either word or symbol syntax.  If optional
+/// arg REALLY-WORD is non-nil, only characters of word syntax can
+/// constitute a word.
+#[lisp_fn(min = "0")]
+pub fn current_word(strict: bool, really_word: bool) -> LispObject {
+    let count = c_specpdl_index();
+    unsafe { record_unwind_protect(Some(save_excursion_restore), save_excursion_save()) };
+
+    let (syntaxes, not_syntaxes) = if really_word {
+        (LispObject::from("w"), LispObject::from("^w"))
+    } else {
+        (LispObject::from("w_"), LispObject::from("^w_"))
+    };
+    // Skip over SYNTAX in the given direction and return the new point.
+    let skip_forward = |syntax: LispObject, lim: LispObject| {
+        skip_syntax_forward(syntax, lim);
+        point()
+    };
+    let skip_backward = |syntax: LispObject, lim: LispObject| {
+        skip_syntax_backward(syntax, lim);
+        point()
+    };
+
+    let oldpoint = point();
+    let mut start = skip_backward(syntaxes, Qnil);
+    goto_char(oldpoint.into());
+    let mut end = skip_forward(syntaxes, Qnil);
+
+    // Point is neither within nor adjacent to a word.
+    if start == oldpoint && end == oldpoint && !strict {
+        // Look for preceding word in same line.
+        skip_backward(not_syntaxes, line_beginning_position(None).into());
+        if bolp() {
+            // No preceding word in same line.
+            // Look for following word in same line.
+            start = skip_forward(not_syntaxes, line_end_position(None).into());
+            end = skip_forward(syntaxes, Qnil);
+        } else {
+            end = point();
+            start = skip_backward(syntaxes, Qnil);
+        }
+    }
+
+    // If we found something nonempty, return it as a string.
+    let word = if start == end {
+        Qnil
+    } else {
+        buffer_substring_no_properties(start.into(), end.into())
+    };
+    unbind_to(count, word)
 }
 
 include!(concat!(env!("OUT_DIR"), "/syntax_exports.rs"));
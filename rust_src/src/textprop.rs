@@ -5,11 +5,15 @@ use std::ptr;
 use remacs_macros::lisp_fn;
 
 use crate::{
+    buffers::current_buffer,
     lisp::LispObject,
     numbers::LispNumber,
-    remacs_sys::Ftext_properties_at,
-    remacs_sys::Qt,
-    remacs_sys::{get_char_property_and_overlay, set_text_properties, textget},
+    remacs_sys::{
+        find_interval, get_char_property_and_overlay, intervals_equal, next_interval,
+        remove_list_of_text_properties, remove_text_properties, set_text_properties, textget,
+    },
+    remacs_sys::{EmacsInt, Lisp_Interval, Qbuffer_or_string_p, Qt},
+    remacs_sys::{Fadd_text_properties, Ftext_properties_at},
 };
 
 /// Return the value of POSITION's property PROP, in OBJECT.
@@ -34,6 +38,23 @@ pub fn get_text_property(position: LispNumber, prop: LispObject, object: LispObj
     unsafe { textget(Ftext_properties_at(position.into(), object), prop) }
 }
 
+/// Set one property of the text from START to END.
+/// The third and fourth arguments PROPERTY and VALUE
+/// specify the property to add.
+/// If the optional fifth argument OBJECT is a buffer (or nil, which means
+/// the current buffer), START and END are buffer positions (integers or
+/// markers).  If OBJECT is a string, START and END are 0-based indices into it.
+#[lisp_fn(min = "4")]
+pub fn put_text_property(
+    start: LispObject,
+    end: LispObject,
+    property: LispObject,
+    value: LispObject,
+    object: LispObject,
+) {
+    unsafe { Fadd_text_properties(start, end, list!(property, value), object) };
+}
+
 /// Completely replace properties of the text from START to END
 /// The third argument PROPERTIES is the new property list.
 /// If the optional fourth argument OBJECT is a buffer (or nil, which
@@ -55,4 +76,214 @@ pub fn set_text_properties_lisp(
     unsafe { set_text_properties(start, end, properties, object, Qt) }
 }
 
+/// Remove some properties from text from START to END.
+/// The third argument PROPERTIES is a property list
+/// whose property names specify the properties to remove.
+/// \(The values stored in PROPERTIES are ignored.)
+/// If the optional fourth argument OBJECT is a buffer (or nil, which means
+/// the current buffer), START and END are buffer positions (integers or
+/// markers).  If OBJECT is a string, START and END are 0-based indices into it.
+/// Return t if any property was actually removed, nil otherwise.
+///
+/// Use `set-text-properties' if you want to remove all text properties.
+#[lisp_fn(
+    c_name = "remove_text_properties",
+    name = "remove-text-properties",
+    min = "3"
+)]
+pub fn remove_text_properties_lisp(
+    start: LispObject,
+    end: LispObject,
+    properties: LispObject,
+    object: LispObject,
+) -> bool {
+    let object = if object.is_nil() {
+        current_buffer()
+    } else {
+        object
+    };
+    unsafe { remove_text_properties(start, end, properties, object) }.is_not_nil()
+}
+
+/// Remove some properties from text from START to END.
+/// The third argument LIST-OF-PROPERTIES is a list of property names to remove.
+/// If the optional fourth argument OBJECT is a buffer (or nil, which means
+/// the current buffer), START and END are buffer positions (integers or
+/// markers).  If OBJECT is a string, START and END are 0-based indices into it.
+/// Return t if any property was actually removed, nil otherwise.
+#[lisp_fn(
+    c_name = "remove_list_of_text_properties",
+    name = "remove-list-of-text-properties",
+    min = "3"
+)]
+pub fn remove_list_of_text_properties_lisp(
+    start: LispObject,
+    end: LispObject,
+    list_of_properties: LispObject,
+    object: LispObject,
+) -> bool {
+    let object = if object.is_nil() {
+        current_buffer()
+    } else {
+        object
+    };
+    unsafe { remove_list_of_text_properties(start, end, list_of_properties, object) }.is_not_nil()
+}
+
+/// Return the interval of OBJECT containing POSITION, together with the
+/// position of the end of OBJECT's accessible text.  OBJECT must be a
+/// buffer or a string, and POSITION must lie within its accessible
+/// portion.  The interval is null if OBJECT has no text properties.
+fn interval_at(object: LispObject, position: EmacsInt) -> (*mut Lisp_Interval, EmacsInt) {
+    let (root, start, end) = if let Some(buffer) = object.as_buffer() {
+        (
+            buffer.intervals(),
+            buffer.begv as EmacsInt,
+            buffer.zv as EmacsInt,
+        )
+    } else if let Some(string) = object.as_string() {
+        (string.intervals(), 0, string.len_chars() as EmacsInt)
+    } else {
+        wrong_type!(Qbuffer_or_string_p, object)
+    };
+
+    if position < start || position > end {
+        args_out_of_range!(position, position);
+    }
+
+    // If there's no text, there are no properties.
+    if root.is_null() || start == end {
+        (ptr::null_mut(), end)
+    } else {
+        (unsafe { find_interval(root, position as isize) }, end)
+    }
+}
+
+/// Return the position of the interval NEXT if it starts before LIMIT
+/// (or before END, the end of the text, if LIMIT is nil); otherwise
+/// return LIMIT.
+fn change_before_limit(
+    next: *mut Lisp_Interval,
+    limit: Option<EmacsInt>,
+    end: EmacsInt,
+) -> Option<EmacsInt> {
+    match unsafe { next.as_ref() } {
+        Some(next) if (next.position as EmacsInt) < limit.unwrap_or(end) => {
+            Some(next.position as EmacsInt)
+        }
+        _ => limit,
+    }
+}
+
+/// Return the position of next property change.
+/// Scans characters forward from POSITION in OBJECT till it finds
+/// a change in some text property, then returns the position of the change.
+/// If the optional second argument OBJECT is a buffer (or nil, which means
+/// the current buffer), POSITION is a buffer position (integer or marker).
+/// If OBJECT is a string, POSITION is a 0-based index into it.
+/// Return nil if LIMIT is nil or omitted, and the property is constant all
+/// the way to the end of OBJECT; if the value is non-nil, it is a position
+/// greater than POSITION, never equal.
+///
+/// If the optional third argument LIMIT is non-nil, don't search
+/// past position LIMIT; return LIMIT if nothing is found before LIMIT.
+#[lisp_fn(min = "1")]
+pub fn next_property_change(
+    position: LispObject,
+    object: LispObject,
+    limit: LispObject,
+) -> Option<EmacsInt> {
+    let object = if object.is_nil() {
+        current_buffer()
+    } else {
+        object
+    };
+    let limit_is_t = limit.is_t();
+    let limit = if limit.is_nil() || limit_is_t {
+        None
+    } else {
+        Some(limit.as_fixnum_coerce_marker_or_error())
+    };
+
+    let (i, end) = interval_at(object, position.as_fixnum_coerce_marker_or_error());
+
+    // If LIMIT is t, return start of next interval--don't
+    // bother checking further intervals.
+    if limit_is_t {
+        let next = if i.is_null() {
+            i
+        } else {
+            unsafe { next_interval(i) }
+        };
+        return Some(unsafe { next.as_ref() }.map_or(end, |next| next.position as EmacsInt));
+    }
+
+    if i.is_null() {
+        return limit;
+    }
+
+    let mut next = unsafe { next_interval(i) };
+    while let Some(n) = unsafe { next.as_ref() } {
+        if !unsafe { intervals_equal(i, next) }
+            || limit.map_or(false, |l| n.position as EmacsInt >= l)
+        {
+            break;
+        }
+        next = unsafe { next_interval(next) };
+    }
+
+    change_before_limit(next, limit, end)
+}
+
+/// Return the position of next property change for a specific property.
+/// Scans characters forward from POSITION till it finds
+/// a change in the PROP property, then returns the position of the change.
+/// If the optional third argument OBJECT is a buffer (or nil, which means
+/// the current buffer), POSITION is a buffer position (integer or marker).
+/// If OBJECT is a string, POSITION is a 0-based index into it.
+/// The property values are compared with `eq'.
+/// Return nil if LIMIT is nil or omitted, and the property is constant all
+/// the way to the end of OBJECT; if the value is non-nil, it is a position
+/// greater than POSITION, never equal.
+///
+/// If the optional fourth argument LIMIT is non-nil, don't search
+/// past position LIMIT; return LIMIT if nothing is found before LIMIT.
+#[lisp_fn(min = "2")]
+pub fn next_single_property_change(
+    position: LispObject,
+    prop: LispObject,
+    object: LispObject,
+    limit: LispObject,
+) -> Option<EmacsInt> {
+    let object = if object.is_nil() {
+        current_buffer()
+    } else {
+        object
+    };
+    let limit = if limit.is_nil() {
+        None
+    } else {
+        Some(limit.as_fixnum_coerce_marker_or_error())
+    };
+
+    let (i, end) = interval_at(object, position.as_fixnum_coerce_marker_or_error());
+    let here = match unsafe { i.as_ref() } {
+        Some(here) => here,
+        None => return limit,
+    };
+
+    let here_val = unsafe { textget(here.plist, prop) };
+    let mut next = unsafe { next_interval(i) };
+    while let Some(n) = unsafe { next.as_ref() } {
+        if !here_val.eq(unsafe { textget(n.plist, prop) })
+            || limit.map_or(false, |l| n.position as EmacsInt >= l)
+        {
+            break;
+        }
+        next = unsafe { next_interval(next) };
+    }
+
+    change_before_limit(next, limit, end)
+}
+
 include!(concat!(env!("OUT_DIR"), "/textprop_exports.rs"));
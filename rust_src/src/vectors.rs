@@ -25,7 +25,7 @@ use crate::{
         Lisp_Vectorlike_With_Slots, More_Lisp_Bits, BITS_PER_BITS_WORD, BOOL_VECTOR_BITS_PER_CHAR,
         PSEUDOVECTOR_FLAG,
     },
-    remacs_sys::{Qarrayp, Qsequencep, Qvectorp},
+    remacs_sys::{Qarrayp, Qbool_vector_p, Qsequencep, Qvectorp},
     threads::ThreadStateRef,
     window_configuration::SaveWindowDataRef,
     windows::LispWindowRef,
@@ -475,6 +475,19 @@ impl From<LispBoolVecRef> for LispObject {
     }
 }
 
+impl From<LispObject> for LispBoolVecRef {
+    fn from(o: LispObject) -> Self {
+        o.as_bool_vector()
+            .unwrap_or_else(|| wrong_type!(Qbool_vector_p, o))
+    }
+}
+
+impl From<LispObject> for Option<LispBoolVecRef> {
+    fn from(o: LispObject) -> Self {
+        o.as_bool_vector()
+    }
+}
+
 impl LispBoolVecRef {
     pub fn len(self) -> usize {
         self.size as usize
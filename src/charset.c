@@ -1782,125 +1782,6 @@ encode_char (struct charset *charset, int c)
 }
 
 
-DEFUN ("decode-char", Fdecode_char, Sdecode_char, 2, 2, 0,
-       doc: /* Decode the pair of CHARSET and CODE-POINT into a character.
-Return nil if CODE-POINT is not valid in CHARSET.
-
-CODE-POINT may be a cons (HIGHER-16-BIT-VALUE . LOWER-16-BIT-VALUE).  */)
-  (Lisp_Object charset, Lisp_Object code_point)
-{
-  int c, id;
-  unsigned code;
-  struct charset *charsetp;
-
-  CHECK_CHARSET_GET_ID (charset, id);
-  code = cons_to_unsigned (code_point, UINT_MAX);
-  charsetp = CHARSET_FROM_ID (id);
-  c = DECODE_CHAR (charsetp, code);
-  return (c >= 0 ? make_number (c) : Qnil);
-}
-
-
-DEFUN ("encode-char", Fencode_char, Sencode_char, 2, 2, 0,
-       doc: /* Encode the character CH into a code-point of CHARSET.
-Return nil if CHARSET doesn't include CH.  */)
-  (Lisp_Object ch, Lisp_Object charset)
-{
-  int c, id;
-  unsigned code;
-  struct charset *charsetp;
-
-  CHECK_CHARSET_GET_ID (charset, id);
-  CHECK_CHARACTER (ch);
-  c = XFASTINT (ch);
-  charsetp = CHARSET_FROM_ID (id);
-  code = ENCODE_CHAR (charsetp, c);
-  if (code == CHARSET_INVALID_CODE (charsetp))
-    return Qnil;
-  return INTEGER_TO_CONS (code);
-}
-
-
-DEFUN ("make-char", Fmake_char, Smake_char, 1, 5, 0,
-       doc:
-       /* Return a character of CHARSET whose position codes are CODEn.
-
-CODE1 through CODE4 are optional, but if you don't supply sufficient
-position codes, it is assumed that the minimum code in each dimension
-is specified.  */)
-  (Lisp_Object charset, Lisp_Object code1, Lisp_Object code2, Lisp_Object code3, Lisp_Object code4)
-{
-  int id, dimension;
-  struct charset *charsetp;
-  unsigned code;
-  int c;
-
-  CHECK_CHARSET_GET_ID (charset, id);
-  charsetp = CHARSET_FROM_ID (id);
-
-  dimension = CHARSET_DIMENSION (charsetp);
-  if (NILP (code1))
-    code = (CHARSET_ASCII_COMPATIBLE_P (charsetp)
-	    ? 0 : CHARSET_MIN_CODE (charsetp));
-  else
-    {
-      CHECK_NATNUM (code1);
-      if (XFASTINT (code1) >= 0x100)
-	args_out_of_range (make_number (0xFF), code1);
-      code = XFASTINT (code1);
-
-      if (dimension > 1)
-	{
-	  code <<= 8;
-	  if (NILP (code2))
-	    code |= charsetp->code_space[(dimension - 2) * 4];
-	  else
-	    {
-	      CHECK_NATNUM (code2);
-	      if (XFASTINT (code2) >= 0x100)
-		args_out_of_range (make_number (0xFF), code2);
-	      code |= XFASTINT (code2);
-	    }
-
-	  if (dimension > 2)
-	    {
-	      code <<= 8;
-	      if (NILP (code3))
-		code |= charsetp->code_space[(dimension - 3) * 4];
-	      else
-		{
-		  CHECK_NATNUM (code3);
-		  if (XFASTINT (code3) >= 0x100)
-		    args_out_of_range (make_number (0xFF), code3);
-		  code |= XFASTINT (code3);
-		}
-
-	      if (dimension > 3)
-		{
-		  code <<= 8;
-		  if (NILP (code4))
-		    code |= charsetp->code_space[0];
-		  else
-		    {
-		      CHECK_NATNUM (code4);
-		      if (XFASTINT (code4) >= 0x100)
-			args_out_of_range (make_number (0xFF), code4);
-		      code |= XFASTINT (code4);
-		    }
-		}
-	    }
-	}
-    }
-
-  if (CHARSET_ISO_FINAL (charsetp) >= 0)
-    code &= 0x7F7F7F7F;
-  c = DECODE_CHAR (charsetp, code);
-  if (c < 0)
-    error ("Invalid code(s)");
-  return make_number (c);
-}
-
-
 /* Return the first charset in CHARSET_LIST that contains C.
    CHARSET_LIST is a list of charset IDs.  If it is nil, use
    Vcharset_ordered_list.  */
@@ -1938,76 +1819,6 @@ char_charset (int c, Lisp_Object charset_list, unsigned int *code_return)
 }
 
 
-DEFUN ("split-char", Fsplit_char, Ssplit_char, 1, 1, 0,
-       doc:
-       /*Return list of charset and one to four position-codes of CH.
-The charset is decided by the current priority order of charsets.
-A position-code is a byte value of each dimension of the code-point of
-CH in the charset.  */)
-  (Lisp_Object ch)
-{
-  struct charset *charset;
-  int c, dimension;
-  unsigned code;
-  Lisp_Object val;
-
-  CHECK_CHARACTER (ch);
-  c = XFASTINT (ch);
-  charset = CHAR_CHARSET (c);
-  if (! charset)
-    emacs_abort ();
-  code = ENCODE_CHAR (charset, c);
-  if (code == CHARSET_INVALID_CODE (charset))
-    emacs_abort ();
-  dimension = CHARSET_DIMENSION (charset);
-  for (val = Qnil; dimension > 0; dimension--)
-    {
-      val = Fcons (make_number (code & 0xFF), val);
-      code >>= 8;
-    }
-  return Fcons (CHARSET_NAME (charset), val);
-}
-
-
-DEFUN ("char-charset", Fchar_charset, Schar_charset, 1, 2, 0,
-       doc: /* Return the charset of highest priority that contains CH.
-ASCII characters are an exception: for them, this function always
-returns `ascii'.
-If optional 2nd arg RESTRICTION is non-nil, it is a list of charsets
-from which to find the charset.  It may also be a coding system.  In
-that case, find the charset from what supported by that coding system.  */)
-  (Lisp_Object ch, Lisp_Object restriction)
-{
-  struct charset *charset;
-
-  CHECK_CHARACTER (ch);
-  if (NILP (restriction))
-    charset = CHAR_CHARSET (XINT (ch));
-  else
-    {
-      if (CONSP (restriction))
-	{
-	  int c = XFASTINT (ch);
-
-	  for (; CONSP (restriction); restriction = XCDR (restriction))
-	    {
-	      struct charset *rcharset;
-
-	      CHECK_CHARSET_GET_CHARSET (XCAR (restriction), rcharset);
-	      if (ENCODE_CHAR (rcharset, c) != CHARSET_INVALID_CODE (rcharset))
-		return XCAR (restriction);
-	    }
-	  return Qnil;
-	}
-      restriction = coding_system_charset_list (restriction);
-      charset = char_charset (XINT (ch), restriction, NULL);
-      if (! charset)
-	return Qnil;
-    }
-  return (CHARSET_NAME (charset));
-}
-
-
 DEFUN ("charset-after", Fcharset_after, Scharset_after, 0, 1, 0,
        doc: /*
 Return charset of a character in the current buffer at position POS.
@@ -2287,11 +2098,6 @@ syms_of_charset (void)
   defsubr (&Sdeclare_equiv_charset);
   defsubr (&Sfind_charset_region);
   defsubr (&Sfind_charset_string);
-  defsubr (&Sdecode_char);
-  defsubr (&Sencode_char);
-  defsubr (&Ssplit_char);
-  defsubr (&Smake_char);
-  defsubr (&Schar_charset);
   defsubr (&Scharset_after);
   defsubr (&Siso_charset);
   defsubr (&Scharset_priority_list);
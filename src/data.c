@@ -41,13 +41,6 @@ extern bool OBJFWDP (union Lisp_Fwd *a);
 
 _Noreturn void wrong_range (Lisp_Object, Lisp_Object, Lisp_Object);
 
-static void
-set_blv_found (struct Lisp_Buffer_Local_Value *blv, int found)
-{
-  eassert (found == !EQ (blv->defcell, blv->valcell));
-  blv->found = found;
-}
-
 static void
 set_blv_value (struct Lisp_Buffer_Local_Value *blv, Lisp_Object val)
 {
@@ -60,12 +53,6 @@ set_blv_where (struct Lisp_Buffer_Local_Value *blv, Lisp_Object val)
   blv->where = val;
 }
 
-static void
-set_blv_defcell (struct Lisp_Buffer_Local_Value *blv, Lisp_Object val)
-{
-  blv->defcell = val;
-}
-
 static void
 set_blv_valcell (struct Lisp_Buffer_Local_Value *blv, Lisp_Object val)
 {
@@ -499,89 +486,6 @@ notify_variable_watchers (Lisp_Object symbol,
 }
 
 
-/* Access or set a buffer-local symbol's default value.  */
-
-void
-set_default_internal (Lisp_Object symbol, Lisp_Object value,
-                      enum Set_Internal_Bind bindflag)
-{
-  struct Lisp_Symbol *sym;
-
-  CHECK_SYMBOL (symbol);
-  sym = XSYMBOL (symbol);
-  switch (sym->u.s.trapped_write)
-    {
-    case SYMBOL_NOWRITE:
-      if (NILP (Fkeywordp (symbol))
-          || !EQ (value, Fsymbol_value (symbol)))
-        xsignal1 (Qsetting_constant, symbol);
-      else
-        /* Allow setting keywords to their own value.  */
-        return;
-
-    case SYMBOL_TRAPPED_WRITE:
-      /* Don't notify here if we're going to call Fset anyway.  */
-      if (sym->u.s.redirect != SYMBOL_PLAINVAL
-          /* Setting due to thread switching doesn't count.  */
-          && bindflag != SET_INTERNAL_THREAD_SWITCH)
-        notify_variable_watchers (symbol, value, Qset_default, Qnil);
-      /* FALLTHROUGH!  */
-    case SYMBOL_UNTRAPPED_WRITE:
-        break;
-
-    default: emacs_abort ();
-    }
-
- start:
-  switch (sym->u.s.redirect)
-    {
-    case SYMBOL_VARALIAS: sym = indirect_variable (sym); goto start;
-    case SYMBOL_PLAINVAL: set_internal (symbol, value, Qnil, bindflag); return;
-    case SYMBOL_LOCALIZED:
-      {
-	struct Lisp_Buffer_Local_Value *blv = SYMBOL_BLV (sym);
-
-	/* Store new value into the DEFAULT-VALUE slot.  */
-	XSETCDR (blv->defcell, value);
-
-	/* If the default binding is now loaded, set the REALVALUE slot too.  */
-	if (blv->fwd && EQ (blv->defcell, blv->valcell))
-	  store_symval_forwarding (blv->fwd, value, NULL);
-        return;
-      }
-    case SYMBOL_FORWARDED:
-      {
-	union Lisp_Fwd *valcontents = SYMBOL_FWD (sym);
-
-	/* Handle variables like case-fold-search that have special slots
-	   in the buffer.
-	   Make them work apparently like Lisp_Buffer_Local_Value variables.  */
-	if (BUFFER_OBJFWDP (valcontents))
-	  {
-	    int offset = XBUFFER_OBJFWD (valcontents)->offset;
-	    int idx = PER_BUFFER_IDX (offset);
-
-	    set_per_buffer_default (offset, value);
-
-	    /* If this variable is not always local in all buffers,
-	       set it in the buffers that don't nominally have a local value.  */
-	    if (idx > 0)
-	      {
-		struct buffer *b;
-
-		FOR_EACH_BUFFER (b)
-		  if (!PER_BUFFER_VALUE_P (b, idx))
-		    set_per_buffer_value (b, offset, value);
-	      }
-	  }
-	else
-          set_internal (symbol, value, Qnil, bindflag);
-        return;
-      }
-    default: emacs_abort ();
-    }
-}
-
 DEFUN ("setq-default", Fsetq_default, Ssetq_default, 0, UNEVALLED, 0,
        doc: /* Set the default value of variable VAR to VALUE.
 VAR, the variable name, is literal (not evaluated);
@@ -614,210 +518,6 @@ usage: (setq-default [VAR VALUE]...)  */)
 
 /* Lisp functions for creating and removing buffer-local variables.  */
 
-union Lisp_Val_Fwd
-  {
-    Lisp_Object value;
-    union Lisp_Fwd *fwd;
-  };
-
-static struct Lisp_Buffer_Local_Value *
-make_blv (struct Lisp_Symbol *sym, bool forwarded,
-	  union Lisp_Val_Fwd valcontents)
-{
-  struct Lisp_Buffer_Local_Value *blv = xmalloc (sizeof *blv);
-  Lisp_Object symbol;
-  Lisp_Object tem;
-
- XSETSYMBOL (symbol, sym);
- tem = Fcons (symbol, (forwarded
-                       ? do_symval_forwarding (valcontents.fwd)
-                       : valcontents.value));
-
-  /* Buffer_Local_Values cannot have as realval a buffer-local
-     or keyboard-local forwarding.  */
-  eassert (!(forwarded && BUFFER_OBJFWDP (valcontents.fwd)));
-  eassert (!(forwarded && KBOARD_OBJFWDP (valcontents.fwd)));
-  blv->fwd = forwarded ? valcontents.fwd : NULL;
-  set_blv_where (blv, Qnil);
-  blv->local_if_set = 0;
-  set_blv_defcell (blv, tem);
-  set_blv_valcell (blv, tem);
-  set_blv_found (blv, 0);
-  return blv;
-}
-
-DEFUN ("make-variable-buffer-local", Fmake_variable_buffer_local,
-       Smake_variable_buffer_local, 1, 1, "vMake Variable Buffer Local: ",
-       doc: /* Make VARIABLE become buffer-local whenever it is set.
-At any time, the value for the current buffer is in effect,
-unless the variable has never been set in this buffer,
-in which case the default value is in effect.
-Note that binding the variable with `let', or setting it while
-a `let'-style binding made in this buffer is in effect,
-does not make the variable buffer-local.  Return VARIABLE.
-
-This globally affects all uses of this variable, so it belongs together with
-the variable declaration, rather than with its uses (if you just want to make
-a variable local to the current buffer for one particular use, use
-`make-local-variable').  Buffer-local bindings are normally cleared
-while setting up a new major mode, unless they have a `permanent-local'
-property.
-
-The function `default-value' gets the default value and `set-default' sets it.  */)
-  (register Lisp_Object variable)
-{
-  struct Lisp_Symbol *sym;
-  struct Lisp_Buffer_Local_Value *blv = NULL;
-  union Lisp_Val_Fwd valcontents;
-  bool forwarded UNINIT;
-
-  CHECK_SYMBOL (variable);
-  sym = XSYMBOL (variable);
-
- start:
-  switch (sym->u.s.redirect)
-    {
-    case SYMBOL_VARALIAS: sym = indirect_variable (sym); goto start;
-    case SYMBOL_PLAINVAL:
-      forwarded = 0; valcontents.value = SYMBOL_VAL (sym);
-      if (EQ (valcontents.value, Qunbound))
-	valcontents.value = Qnil;
-      break;
-    case SYMBOL_LOCALIZED:
-      blv = SYMBOL_BLV (sym);
-      break;
-    case SYMBOL_FORWARDED:
-      forwarded = 1; valcontents.fwd = SYMBOL_FWD (sym);
-      if (KBOARD_OBJFWDP (valcontents.fwd))
-	error ("Symbol %s may not be buffer-local",
-	       SDATA (SYMBOL_NAME (variable)));
-      else if (BUFFER_OBJFWDP (valcontents.fwd))
-	return variable;
-      break;
-    default: emacs_abort ();
-    }
-
-  if (SYMBOL_CONSTANT_P (variable))
-    xsignal1 (Qsetting_constant, variable);
-
-  if (!blv)
-    {
-      blv = make_blv (sym, forwarded, valcontents);
-      sym->u.s.redirect = SYMBOL_LOCALIZED;
-      SET_SYMBOL_BLV (sym, blv);
-    }
-
-  blv->local_if_set = 1;
-  return variable;
-}
-
-DEFUN ("make-local-variable", Fmake_local_variable, Smake_local_variable,
-       1, 1, "vMake Local Variable: ",
-       doc: /* Make VARIABLE have a separate value in the current buffer.
-Other buffers will continue to share a common default value.
-\(The buffer-local value of VARIABLE starts out as the same value
-VARIABLE previously had.  If VARIABLE was void, it remains void.)
-Return VARIABLE.
-
-If the variable is already arranged to become local when set,
-this function causes a local value to exist for this buffer,
-just as setting the variable would do.
-
-This function returns VARIABLE, and therefore
-  (set (make-local-variable \\='VARIABLE) VALUE-EXP)
-works.
-
-See also `make-variable-buffer-local'.
-
-Do not use `make-local-variable' to make a hook variable buffer-local.
-Instead, use `add-hook' and specify t for the LOCAL argument.  */)
-  (Lisp_Object variable)
-{
-  Lisp_Object tem;
-  bool forwarded UNINIT;
-  union Lisp_Val_Fwd valcontents;
-  struct Lisp_Symbol *sym;
-  struct Lisp_Buffer_Local_Value *blv = NULL;
-
-  CHECK_SYMBOL (variable);
-  sym = XSYMBOL (variable);
-
- start:
-  switch (sym->u.s.redirect)
-    {
-    case SYMBOL_VARALIAS: sym = indirect_variable (sym); goto start;
-    case SYMBOL_PLAINVAL:
-      forwarded = 0; valcontents.value = SYMBOL_VAL (sym); break;
-    case SYMBOL_LOCALIZED:
-      blv = SYMBOL_BLV (sym);
-      break;
-    case SYMBOL_FORWARDED:
-      forwarded = 1; valcontents.fwd = SYMBOL_FWD (sym);
-      if (KBOARD_OBJFWDP (valcontents.fwd))
-	error ("Symbol %s may not be buffer-local",
-	       SDATA (SYMBOL_NAME (variable)));
-      break;
-    default: emacs_abort ();
-    }
-
-  if (sym->u.s.trapped_write == SYMBOL_NOWRITE)
-    xsignal1 (Qsetting_constant, variable);
-
-  if (blv ? blv->local_if_set
-      : (forwarded && BUFFER_OBJFWDP (valcontents.fwd)))
-    {
-      tem = Fboundp (variable);
-      /* Make sure the symbol has a local value in this particular buffer,
-	 by setting it to the same value it already has.  */
-      Fset (variable, (EQ (tem, Qt) ? Fsymbol_value (variable) : Qunbound));
-      return variable;
-    }
-  if (!blv)
-    {
-      blv = make_blv (sym, forwarded, valcontents);
-      sym->u.s.redirect = SYMBOL_LOCALIZED;
-      SET_SYMBOL_BLV (sym, blv);
-    }
-
-  /* Make sure this buffer has its own value of symbol.  */
-  XSETSYMBOL (variable, sym);	/* Update in case of aliasing.  */
-  tem = Fassq (variable, BVAR (current_buffer, local_var_alist));
-  if (NILP (tem))
-    {
-      if (let_shadows_buffer_binding_p (sym))
-	{
-	  AUTO_STRING (format,
-		       "Making %s buffer-local while locally let-bound!");
-	  CALLN (Fmessage, format, SYMBOL_NAME (variable));
-	}
-
-      /* Swap out any local binding for some other buffer, and make
-	 sure the current value is permanently recorded, if it's the
-	 default value.  */
-      find_symbol_value (variable);
-
-      bset_local_var_alist
-	(current_buffer,
-	 Fcons (Fcons (variable, XCDR (blv->defcell)),
-		BVAR (current_buffer, local_var_alist)));
-
-      /* Make sure symbol does not think it is set up for this buffer;
-	 force it to look once again for this buffer's value.  */
-      if (current_buffer == XBUFFER (blv->where))
-	set_blv_where (blv, Qnil);
-      set_blv_found (blv, 0);
-    }
-
-  /* If the symbol forwards into a C variable, then load the binding
-     for this buffer now.  If C code modifies the variable before we
-     load the binding in, then that new value will clobber the default
-     binding the next time we unload it.  */
-  if (blv->fwd)
-    swap_in_symval_forwarding (sym, blv);
-
-  return variable;
-}
-
 DEFUN ("kill-local-variable", Fkill_local_variable, Skill_local_variable,
        1, 1, "vKill Local Variable: ",
        doc: /* Make VARIABLE no longer have a separate value in the current buffer.
@@ -1056,66 +756,6 @@ cons_to_signed (Lisp_Object c, intmax_t min, intmax_t max)
   return val;
 }
 
-DEFUN ("number-to-string", Fnumber_to_string, Snumber_to_string, 1, 1, 0,
-       doc: /* Return the decimal representation of NUMBER as a string.
-Uses a minus sign if negative.
-NUMBER may be an integer or a floating point number.  */)
-  (Lisp_Object number)
-{
-  char buffer[max (FLOAT_TO_STRING_BUFSIZE, INT_BUFSIZE_BOUND (EMACS_INT))];
-  int len;
-
-  CHECK_NUMBER_OR_FLOAT (number);
-
-  if (FLOATP (number))
-    len = float_to_string (buffer, XFLOAT_DATA (number));
-  else
-    len = sprintf (buffer, "%"pI"d", XINT (number));
-
-  return make_unibyte_string (buffer, len);
-}
-
-static Lisp_Object
-ash_lsh_impl (Lisp_Object value, Lisp_Object count, bool lsh)
-{
-  /* This code assumes that signed right shifts are arithmetic.  */
-  verify ((EMACS_INT) -1 >> 1 == -1);
-
-  Lisp_Object val;
-
-  CHECK_NUMBER (value);
-  CHECK_NUMBER (count);
-
-  if (XINT (count) >= EMACS_INT_WIDTH)
-    XSETINT (val, 0);
-  else if (XINT (count) > 0)
-    XSETINT (val, XUINT (value) << XINT (count));
-  else if (XINT (count) <= -EMACS_INT_WIDTH)
-    XSETINT (val, lsh ? 0 : XINT (value) < 0 ? -1 : 0);
-  else
-    XSETINT (val, (lsh ? XUINT (value) >> -XINT (count)
-		   : XINT (value) >> -XINT (count)));
-  return val;
-}
-
-DEFUN ("ash", Fash, Sash, 2, 2, 0,
-       doc: /* Return VALUE with its bits shifted left by COUNT.
-If COUNT is negative, shifting is actually to the right.
-In this case, the sign bit is duplicated.  */)
-  (register Lisp_Object value, Lisp_Object count)
-{
-  return ash_lsh_impl (value, count, false);
-}
-
-DEFUN ("lsh", Flsh, Slsh, 2, 2, 0,
-       doc: /* Return VALUE with its bits shifted left by COUNT.
-If COUNT is negative, shifting is actually to the right.
-In this case, zeros are shifted in on the left.  */)
-  (register Lisp_Object value, Lisp_Object count)
-{
-  return ash_lsh_impl (value, count, true);
-}
-
 /* Because we round up the bool vector allocate size to word_size
    units, we can safely read past the "end" of the vector in the
    operations below.  These extra bits are always zero.  */
@@ -1586,16 +1226,11 @@ syms_of_data (void)
   defsubr (&Sinteractive_form);
   defsubr (&Smodule_function_p);
   defsubr (&Ssetq_default);
-  defsubr (&Smake_variable_buffer_local);
-  defsubr (&Smake_local_variable);
   defsubr (&Skill_local_variable);
 #if 0                           /* XXX Remove this. --lorentey */
   defsubr (&Sterminal_local_value);
   defsubr (&Sset_terminal_local_value);
 #endif
-  defsubr (&Snumber_to_string);
-  defsubr (&Slsh);
-  defsubr (&Sash);
 #ifdef HAVE_MODULES
   defsubr (&Suser_ptrp);
 #endif
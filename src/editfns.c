@@ -344,145 +344,6 @@ init_editfns (bool dumping)
   Voperating_system_release = Qnil;
 #endif
 }
-
-/* Find all the overlays in the current buffer that touch position POS.
-   Return the number found, and store them in a vector in VEC
-   of length LEN.  */
-
-static ptrdiff_t
-overlays_around (EMACS_INT pos, Lisp_Object *vec, ptrdiff_t len)
-{
-  Lisp_Object overlay, start, end;
-  struct Lisp_Overlay *tail;
-  ptrdiff_t startpos, endpos;
-  ptrdiff_t idx = 0;
-
-  for (tail = current_buffer->overlays_before; tail; tail = tail->next)
-    {
-      XSETMISC (overlay, tail);
-
-      end = OVERLAY_END (overlay);
-      endpos = OVERLAY_POSITION (end);
-      if (endpos < pos)
-	  break;
-      start = OVERLAY_START (overlay);
-      startpos = OVERLAY_POSITION (start);
-      if (startpos <= pos)
-	{
-	  if (idx < len)
-	    vec[idx] = overlay;
-	  /* Keep counting overlays even if we can't return them all.  */
-	  idx++;
-	}
-    }
-
-  for (tail = current_buffer->overlays_after; tail; tail = tail->next)
-    {
-      XSETMISC (overlay, tail);
-
-      start = OVERLAY_START (overlay);
-      startpos = OVERLAY_POSITION (start);
-      if (pos < startpos)
-	break;
-      end = OVERLAY_END (overlay);
-      endpos = OVERLAY_POSITION (end);
-      if (pos <= endpos)
-	{
-	  if (idx < len)
-	    vec[idx] = overlay;
-	  idx++;
-	}
-    }
-
-  return idx;
-}
-
-DEFUN ("get-pos-property", Fget_pos_property, Sget_pos_property, 2, 3, 0,
-       doc: /* Return the value of POSITION's property PROP, in OBJECT.
-Almost identical to `get-char-property' except for the following difference:
-Whereas `get-char-property' returns the property of the char at (i.e. right
-after) POSITION, this pays attention to properties's stickiness and overlays's
-advancement settings, in order to find the property of POSITION itself,
-i.e. the property that a char would inherit if it were inserted
-at POSITION.  */)
-  (Lisp_Object position, register Lisp_Object prop, Lisp_Object object)
-{
-  CHECK_NUMBER_COERCE_MARKER (position);
-
-  if (NILP (object))
-    XSETBUFFER (object, current_buffer);
-  else if (WINDOWP (object))
-    object = XWINDOW (object)->contents;
-
-  if (!BUFFERP (object))
-    /* pos-property only makes sense in buffers right now, since strings
-       have no overlays and no notion of insertion for which stickiness
-       could be obeyed.  */
-    return Fget_text_property (position, prop, object);
-  else
-    {
-      EMACS_INT posn = XINT (position);
-      ptrdiff_t noverlays;
-      Lisp_Object *overlay_vec, tem;
-      struct buffer *obuf = current_buffer;
-      USE_SAFE_ALLOCA;
-
-      set_buffer_temp (XBUFFER (object));
-
-      /* First try with room for 40 overlays.  */
-      Lisp_Object overlay_vecbuf[40];
-      noverlays = ARRAYELTS (overlay_vecbuf);
-      overlay_vec = overlay_vecbuf;
-      noverlays = overlays_around (posn, overlay_vec, noverlays);
-
-      /* If there are more than 40,
-	 make enough space for all, and try again.  */
-      if (ARRAYELTS (overlay_vecbuf) < noverlays)
-	{
-	  SAFE_ALLOCA_LISP (overlay_vec, noverlays);
-	  noverlays = overlays_around (posn, overlay_vec, noverlays);
-	}
-      noverlays = sort_overlays (overlay_vec, noverlays, NULL);
-
-      set_buffer_temp (obuf);
-
-      /* Now check the overlays in order of decreasing priority.  */
-      while (--noverlays >= 0)
-	{
-	  Lisp_Object ol = overlay_vec[noverlays];
-	  tem = Foverlay_get (ol, prop);
-	  if (!NILP (tem))
-	    {
-	      /* Check the overlay is indeed active at point.  */
-	      Lisp_Object start = OVERLAY_START (ol), finish = OVERLAY_END (ol);
-	      if ((OVERLAY_POSITION (start) == posn
-		   && XMARKER (start)->insertion_type == 1)
-		  || (OVERLAY_POSITION (finish) == posn
-		      && XMARKER (finish)->insertion_type == 0))
-		; /* The overlay will not cover a char inserted at point.  */
-	      else
-		{
-		  SAFE_FREE ();
-		  return tem;
-		}
-	    }
-	}
-      SAFE_FREE ();
-
-      { /* Now check the text properties.  */
-	int stickiness = text_property_stickiness (prop, position, object);
-	if (stickiness > 0)
-	  return Fget_text_property (position, prop, object);
-	else if (stickiness < 0
-		 && XINT (position) > BUF_BEGV (XBUFFER (object)))
-	  return Fget_text_property (make_number (XINT (position) - 1),
-				     prop, object);
-	else
-	  return Qnil;
-      }
-    }
-}
-
 
 /* Restore saved buffer before leaving `save-excursion' special form.  */
 
@@ -3584,10 +3445,6 @@ syms_of_editfns (void)
   DEFSYM (Qbuffer_access_fontify_functions, "buffer-access-fontify-functions");
   DEFSYM (Qwall, "wall");
 
-  DEFVAR_LISP ("inhibit-field-text-motion", Vinhibit_field_text_motion,
-	       doc: /* Non-nil means text motion commands don't notice fields.  */);
-  Vinhibit_field_text_motion = Qnil;
-
   DEFVAR_LISP ("buffer-access-fontify-functions",
 	       Vbuffer_access_fontify_functions,
 	       doc: /* List of functions called by `buffer-substring' to fontify if necessary.
@@ -3629,7 +3486,6 @@ functions if all the text being accessed has this property.  */);
   DEFVAR_LISP ("operating-system-release", Voperating_system_release,
 	       doc: /* The release of the operating system Emacs is running on.  */);
 
-  defsubr (&Sget_pos_property);
 
   /* Symbol for the text property used to mark fields.  */
   DEFSYM (Qfield, "field");
@@ -149,31 +149,6 @@ check_executable (char *filename)
   return faccessat (AT_FDCWD, filename, X_OK, AT_EACCESS) == 0;
 }
 
-/* Return true if file FILENAME exists and can be accessed
-   according to AMODE, which should include W_OK.
-   On failure, return false and set errno.  */
-
-static bool
-check_writable (const char *filename, int amode)
-{
-  bool res = faccessat (AT_FDCWD, filename, amode, AT_EACCESS) == 0;
-#ifdef CYGWIN
-  /* faccessat may have returned failure because Cygwin couldn't
-     determine the file's UID or GID; if so, we return success.  */
-  if (!res)
-    {
-      int faccessat_errno = errno;
-      struct stat st;
-      if (stat (filename, &st) < 0)
-        return 0;
-      res = (st.st_uid == -1 || st.st_gid == -1);
-      errno = faccessat_errno;
-    }
-#endif /* CYGWIN */
-  return res;
-}
-
-
 /* Signal a file-access failure.  STRING describes the failure,
    NAME the file involved, and ERRORNO the errno value.
 
@@ -494,76 +469,6 @@ file_name_as_directory (char *dst, const char *src, ptrdiff_t srclen,
   return srclen;
 }
 
-DEFUN ("file-name-as-directory", Ffile_name_as_directory,
-       Sfile_name_as_directory, 1, 1, 0,
-       doc: /* Return a string representing the file name FILE interpreted as a directory.
-This operation exists because a directory is also a file, but its name as
-a directory is different from its name as a file.
-The result can be used as the value of `default-directory'
-or passed as second argument to `expand-file-name'.
-For a Unix-syntax file name, just appends a slash unless a trailing slash
-is already present.  */)
-  (Lisp_Object file)
-{
-  char *buf;
-  ptrdiff_t length;
-  Lisp_Object handler, val;
-  USE_SAFE_ALLOCA;
-
-  CHECK_STRING (file);
-
-  /* If the file name has special constructs in it,
-     call the corresponding file handler.  */
-  handler = Ffind_file_name_handler (file, Qfile_name_as_directory);
-  if (!NILP (handler))
-    {
-      Lisp_Object handled_name = call2 (handler, Qfile_name_as_directory,
-					file);
-      if (STRINGP (handled_name))
-	return handled_name;
-      error ("Invalid handler in `file-name-handler-alist'");
-    }
-
-#ifdef WINDOWSNT
-  if (!NILP (Vw32_downcase_file_names))
-    file = Fdowncase (file);
-#endif
-  buf = SAFE_ALLOCA (SBYTES (file) + file_name_as_directory_slop + 1);
-  length = file_name_as_directory (buf, SSDATA (file), SBYTES (file),
-				   STRING_MULTIBYTE (file));
-  val = make_specified_string (buf, -1, length, STRING_MULTIBYTE (file));
-  SAFE_FREE ();
-  return val;
-}
-
-
-/* Convert from directory name SRC of length SRCLEN to file name in
-   DST.  MULTIBYTE non-zero means the file name in SRC is a multibyte
-   string.  On UNIX, just make sure there isn't a terminating /.
-   Return the length of DST in bytes.  */
-
-static ptrdiff_t
-directory_file_name (char *dst, char *src, ptrdiff_t srclen, bool multibyte)
-{
-  /* In Unix-like systems, just remove any final slashes.  However, if
-     they are all slashes, leave "/" and "//" alone, and treat "///"
-     and longer as if they were "/".  */
-  if (! (srclen == 2 && IS_DIRECTORY_SEP (src[0])))
-    while (srclen > 1
-#ifdef DOS_NT
-	   && !(srclen > 2 && IS_DEVICE_SEP (src[srclen - 2]))
-#endif
-	   && IS_DIRECTORY_SEP (src[srclen - 1]))
-      srclen--;
-
-  memcpy (dst, src, srclen);
-  dst[srclen] = 0;
-#ifdef DOS_NT
-  dostounix_filename (dst);
-#endif
-  return srclen;
-}
-
 /* Return the expansion of NEWNAME, except that if NEWNAME is a
    directory name then return the expansion of FILE's basename under
    NEWNAME.  This resembles how 'cp FILE NEWNAME' works, except that
@@ -578,46 +483,6 @@ expand_cp_target (Lisp_Object file, Lisp_Object newname)
 	  : Fexpand_file_name (newname, Qnil));
 }
 
-DEFUN ("directory-file-name", Fdirectory_file_name, Sdirectory_file_name,
-       1, 1, 0,
-       doc: /* Returns the file name of the directory named DIRECTORY.
-This is the name of the file that holds the data for the directory DIRECTORY.
-This operation exists because a directory is also a file, but its name as
-a directory is different from its name as a file.
-In Unix-syntax, this function just removes the final slash.  */)
-  (Lisp_Object directory)
-{
-  char *buf;
-  ptrdiff_t length;
-  Lisp_Object handler, val;
-  USE_SAFE_ALLOCA;
-
-  CHECK_STRING (directory);
-
-  /* If the file name has special constructs in it,
-     call the corresponding file handler.  */
-  handler = Ffind_file_name_handler (directory, Qdirectory_file_name);
-  if (!NILP (handler))
-    {
-      Lisp_Object handled_name = call2 (handler, Qdirectory_file_name,
-					directory);
-      if (STRINGP (handled_name))
-	return handled_name;
-      error ("Invalid handler in `file-name-handler-alist'");
-    }
-
-#ifdef WINDOWSNT
-  if (!NILP (Vw32_downcase_file_names))
-    directory = Fdowncase (directory);
-#endif
-  buf = SAFE_ALLOCA (SBYTES (directory) + 1);
-  length = directory_file_name (buf, SSDATA (directory), SBYTES (directory),
-				STRING_MULTIBYTE (directory));
-  val = make_specified_string (buf, -1, length, STRING_MULTIBYTE (directory));
-  SAFE_FREE ();
-  return val;
-}
-
 DEFUN ("make-temp-file-internal", Fmake_temp_file_internal,
        Smake_temp_file_internal, 4, 4, 0,
        doc: /* Generate a new file whose name starts with PREFIX, a string.
@@ -2396,65 +2261,6 @@ This happens for interactive use with M-x.  */)
   report_file_error ("Making symbolic link", list2 (target, linkname));
 }
 
-DEFUN ("file-readable-p", Ffile_readable_p, Sfile_readable_p, 1, 1, 0,
-       doc: /* Return t if file FILENAME exists and you can read it.
-See also `file-exists-p' and `file-attributes'.  */)
-  (Lisp_Object filename)
-{
-  Lisp_Object absname;
-  Lisp_Object handler;
-
-  CHECK_STRING (filename);
-  absname = Fexpand_file_name (filename, Qnil);
-
-  /* If the file name has special constructs in it,
-     call the corresponding file handler.  */
-  handler = Ffind_file_name_handler (absname, Qfile_readable_p);
-  if (!NILP (handler))
-    return call2 (handler, Qfile_readable_p, absname);
-
-  absname = ENCODE_FILE (absname);
-  return (faccessat (AT_FDCWD, SSDATA (absname), R_OK, AT_EACCESS) == 0
-	  ? Qt : Qnil);
-}
-
-DEFUN ("file-writable-p", Ffile_writable_p, Sfile_writable_p, 1, 1, 0,
-       doc: /* Return t if file FILENAME can be written or created by you.  */)
-  (Lisp_Object filename)
-{
-  Lisp_Object absname, dir, encoded;
-  Lisp_Object handler;
-
-  CHECK_STRING (filename);
-  absname = Fexpand_file_name (filename, Qnil);
-
-  /* If the file name has special constructs in it,
-     call the corresponding file handler.  */
-  handler = Ffind_file_name_handler (absname, Qfile_writable_p);
-  if (!NILP (handler))
-    return call2 (handler, Qfile_writable_p, absname);
-
-  encoded = ENCODE_FILE (absname);
-  if (check_writable (SSDATA (encoded), W_OK))
-    return Qt;
-  if (errno != ENOENT)
-    return Qnil;
-
-  dir = Ffile_name_directory (absname);
-  eassert (!NILP (dir));
-
-  dir = ENCODE_FILE (dir);
-#ifdef WINDOWSNT
-  /* The read-only attribute of the parent directory doesn't affect
-     whether a file or directory can be created within it.  Some day we
-     should check ACLs though, which do affect this.  */
-  return file_directory_p (dir) ? Qt : Qnil;
-#else
-  return check_writable (SSDATA (dir), W_OK | X_OK) ? Qt : Qnil;
-#endif
-}
-
-
 DEFUN ("access-file", Faccess_file, Saccess_file, 2, 2, 0,
        doc: /* Access file FILENAME, and get an error if that does not work.
 The second argument STRING is prepended to the error message.
@@ -5974,8 +5780,6 @@ This includes interactive calls to `delete-file' and
   defsubr (&Sfile_name_directory);
   defsubr (&Sfile_name_nondirectory);
   defsubr (&Sunhandled_file_name_directory);
-  defsubr (&Sfile_name_as_directory);
-  defsubr (&Sdirectory_file_name);
   defsubr (&Smake_temp_file_internal);
   defsubr (&Sexpand_file_name);
   defsubr (&Ssubstitute_in_file_name);
@@ -5986,8 +5790,6 @@ This includes interactive calls to `delete-file' and
   defsubr (&Srename_file);
   defsubr (&Sadd_name_to_file);
   defsubr (&Smake_symbolic_link);
-  defsubr (&Sfile_readable_p);
-  defsubr (&Sfile_writable_p);
   defsubr (&Saccess_file);
   defsubr (&Sfile_symlink_p);
   defsubr (&Sfile_accessible_directory_p);
@@ -281,6 +281,10 @@ extern Lisp_Object set_text_properties (Lisp_Object, Lisp_Object,
                                         Lisp_Object);
 extern void set_text_properties_1 (Lisp_Object, Lisp_Object,
                                    Lisp_Object, Lisp_Object, INTERVAL);
+extern Lisp_Object remove_text_properties (Lisp_Object, Lisp_Object,
+                                          Lisp_Object, Lisp_Object);
+extern Lisp_Object remove_list_of_text_properties (Lisp_Object, Lisp_Object,
+                                                  Lisp_Object, Lisp_Object);
 
 Lisp_Object text_property_list (Lisp_Object, Lisp_Object, Lisp_Object,
                                 Lisp_Object);
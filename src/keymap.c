@@ -81,18 +81,11 @@ Lisp_Object apropos_accumulate;
 /* Alist of elements like (DEL . "\d").  */
 static Lisp_Object exclude_keys;
 
-/* Pre-allocated 2-element vector for Fcommand_remapping to use.  */
-static Lisp_Object command_remapping_vector;
-
-static Lisp_Object store_in_keymap (Lisp_Object, Lisp_Object, Lisp_Object);
-
-static Lisp_Object define_as_prefix (Lisp_Object, Lisp_Object);
 static void describe_command (Lisp_Object, Lisp_Object);
 static void describe_translation (Lisp_Object, Lisp_Object);
 static void describe_map (Lisp_Object, Lisp_Object,
                           void (*) (Lisp_Object, Lisp_Object),
 			  bool, Lisp_Object, Lisp_Object *, bool, bool);
-static void silly_event_symbol_error (Lisp_Object);
 static Lisp_Object get_keyelt (Lisp_Object, bool);
 
 void map_keymap_item (map_keymap_function_t, Lisp_Object, Lisp_Object, Lisp_Object, void *);
@@ -109,13 +102,14 @@ void map_keymap_char_table_item (Lisp_Object, Lisp_Object, Lisp_Object);
 void
 initial_define_key (Lisp_Object keymap, int key, const char *defname)
 {
-  store_in_keymap (keymap, make_number (key), intern_c_string (defname));
+  store_in_keymap (keymap, make_number (key), intern_c_string (defname), false);
 }
 
 void
 initial_define_lispy_key (Lisp_Object keymap, const char *keyname, const char *defname)
 {
-  store_in_keymap (keymap, intern_c_string (keyname), intern_c_string (defname));
+  store_in_keymap (keymap, intern_c_string (keyname), intern_c_string (defname),
+		   false);
 }
 
 /* Look up IDX in MAP.  IDX may be any sort of event.
@@ -415,8 +409,13 @@ get_keyelt (Lisp_Object object, bool autoload)
     }
 }
 
-static Lisp_Object
-store_in_keymap (Lisp_Object keymap, register Lisp_Object idx, Lisp_Object def)
+/* Store DEF as the binding of IDX in KEYMAP.  If REMOVE, delete the
+   binding of IDX instead, so that lookups fall through to KEYMAP's
+   parent.  */
+
+Lisp_Object
+store_in_keymap (Lisp_Object keymap, register Lisp_Object idx,
+		 Lisp_Object def, bool remove)
 {
   /* Flush any reverse-map cache.  */
   set_where_is_cache(Qnil);
@@ -434,6 +433,9 @@ store_in_keymap (Lisp_Object keymap, register Lisp_Object idx, Lisp_Object def)
   if (!CONSP (keymap) || !EQ (XCAR (keymap), Qkeymap))
     error ("attempt to define a key in a non-keymap");
 
+  if (remove)
+    def = Qnil;
+
   /* If idx is a cons, and the car part is a character, idx must be of
      the form (FROM-CHAR . TO-CHAR).  */
   if (CONSP (idx) && CHARACTERP (XCAR (idx)))
@@ -505,12 +507,13 @@ store_in_keymap (Lisp_Object keymap, register Lisp_Object idx, Lisp_Object def)
 		       /* nil has a special meaning for char-tables, so
 			  we use something else to record an explicitly
 			  unbound entry.  */
-		       NILP (def) ? Qt : def);
+		       NILP (def) && !remove ? Qt : def);
 		return def;
 	      }
 	    else if (CONSP (idx) && CHARACTERP (XCAR (idx)))
 	      {
-		Fset_char_table_range (elt, idx, NILP (def) ? Qt : def);
+		Fset_char_table_range (elt, idx,
+				       NILP (def) && !remove ? Qt : def);
 		return def;
 	      }
 	    insertion_point = tail;
@@ -529,7 +532,11 @@ store_in_keymap (Lisp_Object keymap, register Lisp_Object idx, Lisp_Object def)
 	    else if (EQ (idx, XCAR (elt)))
 	      {
 		CHECK_IMPURE (elt, XCONS (elt));
-		XSETCDR (elt, def);
+		if (remove)
+		  /* ELT is never the head of KEYMAP, which is `keymap'.  */
+		  Fdelq (elt, keymap);
+		else
+		  XSETCDR (elt, def);
 		return def;
 	      }
 	    else if (CONSP (idx)
@@ -542,7 +549,10 @@ store_in_keymap (Lisp_Object keymap, register Lisp_Object idx, Lisp_Object def)
 		if (from <= XFASTINT (XCAR (elt))
 		    && to >= XFASTINT (XCAR (elt)))
 		  {
-		    XSETCDR (elt, def);
+		    if (remove)
+		      Fdelq (elt, keymap);
+		    else
+		      XSETCDR (elt, def);
 		    if (from == to)
 		      return def;
 		  }
@@ -560,7 +570,8 @@ store_in_keymap (Lisp_Object keymap, register Lisp_Object idx, Lisp_Object def)
 
   keymap_end:
     /* We have scanned the entire keymap, and not found a binding for
-       IDX.  Let's add one.  */
+       IDX.  Let's add one, unless we were asked to remove it.  */
+    if (!remove)
     {
       Lisp_Object elt;
 
@@ -644,186 +655,6 @@ copy_keymap_item (Lisp_Object elt)
 
 /* Simple Keymap mutators and accessors.				*/
 
-/* GC is possible in this function if it autoloads a keymap.  */
-
-DEFUN ("define-key", Fdefine_key, Sdefine_key, 3, 3, 0,
-       doc: /* In KEYMAP, define key sequence KEY as DEF.
-KEYMAP is a keymap.
-
-KEY is a string or a vector of symbols and characters, representing a
-sequence of keystrokes and events.  Non-ASCII characters with codes
-above 127 (such as ISO Latin-1) can be represented by vectors.
-Two types of vector have special meanings:
- [remap COMMAND] remaps any key binding for COMMAND.
- [t] creates a default definition, which applies to any event with no
-    other definition in KEYMAP.
-
-DEF is anything that can be a key's definition:
- nil (means key is undefined in this keymap),
- a command (a Lisp function suitable for interactive calling),
- a string (treated as a keyboard macro),
- a keymap (to define a prefix key),
- a symbol (when the key is looked up, the symbol will stand for its
-    function definition, which should at that time be one of the above,
-    or another symbol whose function definition is used, etc.),
- a cons (STRING . DEFN), meaning that DEFN is the definition
-    (DEFN should be a valid definition in its own right),
- or a cons (MAP . CHAR), meaning use definition of CHAR in keymap MAP,
- or an extended menu item definition.
- (See info node `(elisp)Extended Menu Items'.)
-
-If KEYMAP is a sparse keymap with a binding for KEY, the existing
-binding is altered.  If there is no binding for KEY, the new pair
-binding KEY to DEF is added at the front of KEYMAP.  */)
-  (Lisp_Object keymap, Lisp_Object key, Lisp_Object def)
-{
-  ptrdiff_t idx;
-  Lisp_Object c;
-  Lisp_Object cmd;
-  bool metized = 0;
-  int meta_bit;
-  ptrdiff_t length;
-
-  keymap = get_keymap (keymap, 1, 1);
-
-  length = CHECK_VECTOR_OR_STRING (key);
-  if (length == 0)
-    return Qnil;
-
-  if (SYMBOLP (def) && !EQ (Vdefine_key_rebound_commands, Qt))
-    Vdefine_key_rebound_commands = Fcons (def, Vdefine_key_rebound_commands);
-
-  meta_bit = (VECTORP (key) || (STRINGP (key) && STRING_MULTIBYTE (key))
-	      ? meta_modifier : 0x80);
-
-  if (VECTORP (def) && ASIZE (def) > 0 && CONSP (AREF (def, 0)))
-    { /* DEF is apparently an XEmacs-style keyboard macro.  */
-      Lisp_Object tmp = Fmake_vector (make_number (ASIZE (def)), Qnil);
-      ptrdiff_t i = ASIZE (def);
-      while (--i >= 0)
-	{
-	  Lisp_Object defi = AREF (def, i);
-	  if (CONSP (defi) && lucid_event_type_list_p (defi))
-	    defi = Fevent_convert_list (defi);
-	  ASET (tmp, i, defi);
-	}
-      def = tmp;
-    }
-
-  idx = 0;
-  while (1)
-    {
-      c = Faref (key, make_number (idx));
-
-      if (CONSP (c))
-	{
-	  /* C may be a Lucid style event type list or a cons (FROM .
-	     TO) specifying a range of characters.  */
-	  if (lucid_event_type_list_p (c))
-	    c = Fevent_convert_list (c);
-	  else if (CHARACTERP (XCAR (c)))
-	    CHECK_CHARACTER_CDR (c);
-	}
-
-      if (SYMBOLP (c))
-	silly_event_symbol_error (c);
-
-      if (INTEGERP (c)
-	  && (XINT (c) & meta_bit)
-	  && !metized)
-	{
-	  c = meta_prefix_char;
-	  metized = 1;
-	}
-      else
-	{
-	  if (INTEGERP (c))
-	    XSETINT (c, XINT (c) & ~meta_bit);
-
-	  metized = 0;
-	  idx++;
-	}
-
-      if (!INTEGERP (c) && !SYMBOLP (c)
-	  && (!CONSP (c)
-	      /* If C is a range, it must be a leaf.  */
-	      || (INTEGERP (XCAR (c)) && idx != length)))
-	message_with_string ("Key sequence contains invalid event %s", c, 1);
-
-      if (idx == length)
-	return store_in_keymap (keymap, c, def);
-
-      cmd = access_keymap (keymap, c, 0, 1, 1);
-
-      /* If this key is undefined, make it a prefix.  */
-      if (NILP (cmd))
-	cmd = define_as_prefix (keymap, c);
-
-      keymap = get_keymap (cmd, 0, 1);
-      if (!CONSP (keymap))
-	{
-	  const char *trailing_esc = ((EQ (c, meta_prefix_char) && metized)
-				      ? (idx == 0 ? "ESC" : " ESC")
-				      : "");
-
-	  /* We must use Fkey_description rather than just passing key to
-	     error; key might be a vector, not a string.  */
-	  error ("Key sequence %s starts with non-prefix key %s%s",
-		 SDATA (Fkey_description (key, Qnil)),
-		 SDATA (Fkey_description (Fsubstring (key, make_number (0),
-						      make_number (idx)),
-					  Qnil)),
-		 trailing_esc);
-	}
-    }
-}
-
-/* This function may GC (it calls Fkey_binding).  */
-
-DEFUN ("command-remapping", Fcommand_remapping, Scommand_remapping, 1, 3, 0,
-       doc: /* Return the remapping for command COMMAND.
-Returns nil if COMMAND is not remapped (or not a symbol).
-
-If the optional argument POSITION is non-nil, it specifies a mouse
-position as returned by `event-start' and `event-end', and the
-remapping occurs in the keymaps associated with it.  It can also be a
-number or marker, in which case the keymap properties at the specified
-buffer position instead of point are used.  The KEYMAPS argument is
-ignored if POSITION is non-nil.
-
-If the optional argument KEYMAPS is non-nil, it should be a list of
-keymaps to search for command remapping.  Otherwise, search for the
-remapping in all currently active keymaps.  */)
-  (Lisp_Object command, Lisp_Object position, Lisp_Object keymaps)
-{
-  if (!SYMBOLP (command))
-    return Qnil;
-
-  ASET (command_remapping_vector, 1, command);
-
-  if (NILP (keymaps))
-    command = Fkey_binding (command_remapping_vector, Qnil, Qt, position);
-  else
-    command = Flookup_key (Fcons (Qkeymap, keymaps),
-			   command_remapping_vector, Qnil);
-  return INTEGERP (command) ? Qnil : command;
-}
-
-/* Make KEYMAP define event C as a keymap (i.e., as a prefix).
-   Assume that currently it does not define C at all.
-   Return the keymap.  */
-
-static Lisp_Object
-define_as_prefix (Lisp_Object keymap, Lisp_Object c)
-{
-  Lisp_Object cmd;
-
-  cmd = Fmake_sparse_keymap (Qnil);
-  store_in_keymap (keymap, c, cmd);
-
-  return cmd;
-}
-
 /* Append a key to the end of a key sequence.  We always make a vector.  */
 
 static Lisp_Object
@@ -836,7 +667,7 @@ append_key (Lisp_Object key_sequence, Lisp_Object key)
 /* Given an event type C which is a symbol,
    signal an error if is a mistake such as RET or M-RET or C-DEL, etc.  */
 
-static void
+void
 silly_event_symbol_error (Lisp_Object c)
 {
   Lisp_Object parsed, base, name, assoc;
@@ -1352,288 +1183,6 @@ then the value includes only maps for prefixes that start with PREFIX.  */)
   return maps;
 }
 
-/* This function cannot GC.  */
-
-DEFUN ("key-description", Fkey_description, Skey_description, 1, 2, 0,
-       doc: /* Return a pretty description of key-sequence KEYS.
-Optional arg PREFIX is the sequence of keys leading up to KEYS.
-For example, [?\C-x ?l] is converted into the string \"C-x l\".
-
-For an approximate inverse of this, see `kbd'.  */)
-  (Lisp_Object keys, Lisp_Object prefix)
-{
-  ptrdiff_t len = 0;
-  EMACS_INT i;
-  ptrdiff_t i_byte;
-  Lisp_Object *args;
-  EMACS_INT size = XINT (Flength (keys));
-  Lisp_Object list;
-  Lisp_Object sep = build_string (" ");
-  Lisp_Object key;
-  Lisp_Object result;
-  bool add_meta = 0;
-  USE_SAFE_ALLOCA;
-
-  if (!NILP (prefix))
-    size += XINT (Flength (prefix));
-
-  /* This has one extra element at the end that we don't pass to Fconcat.  */
-  EMACS_INT size4;
-  if (INT_MULTIPLY_WRAPV (size, 4, &size4))
-    memory_full (SIZE_MAX);
-  SAFE_ALLOCA_LISP (args, size4);
-
-  /* In effect, this computes
-     (mapconcat 'single-key-description keys " ")
-     but we shouldn't use mapconcat because it can do GC.  */
-
- next_list:
-  if (!NILP (prefix))
-    list = prefix, prefix = Qnil;
-  else if (!NILP (keys))
-    list = keys, keys = Qnil;
-  else
-    {
-      if (add_meta)
-	{
-	  args[len] = Fsingle_key_description (meta_prefix_char, Qnil);
-	  result = Fconcat (len + 1, args);
-	}
-      else if (len == 0)
-	result = empty_unibyte_string;
-      else
-	result = Fconcat (len - 1, args);
-      SAFE_FREE ();
-      return result;
-    }
-
-  if (STRINGP (list))
-    size = SCHARS (list);
-  else if (VECTORP (list))
-    size = ASIZE (list);
-  else if (CONSP (list))
-    size = XINT (Flength (list));
-  else
-    wrong_type_argument (Qarrayp, list);
-
-  i = i_byte = 0;
-
-  while (i < size)
-    {
-      if (STRINGP (list))
-	{
-	  int c;
-	  FETCH_STRING_CHAR_ADVANCE (c, list, i, i_byte);
-	  if (SINGLE_BYTE_CHAR_P (c) && (c & 0200))
-	    c ^= 0200 | meta_modifier;
-	  XSETFASTINT (key, c);
-	}
-      else if (VECTORP (list))
-	{
-	  key = AREF (list, i); i++;
-	}
-      else
-	{
-	  key = XCAR (list);
-	  list = XCDR (list);
-	  i++;
-	}
-
-      if (add_meta)
-	{
-	  if (!INTEGERP (key)
-	      || EQ (key, meta_prefix_char)
-	      || (XINT (key) & meta_modifier))
-	    {
-	      args[len++] = Fsingle_key_description (meta_prefix_char, Qnil);
-	      args[len++] = sep;
-	      if (EQ (key, meta_prefix_char))
-		continue;
-	    }
-	  else
-	    XSETINT (key, XINT (key) | meta_modifier);
-	  add_meta = 0;
-	}
-      else if (EQ (key, meta_prefix_char))
-	{
-	  add_meta = 1;
-	  continue;
-	}
-      args[len++] = Fsingle_key_description (key, Qnil);
-      args[len++] = sep;
-    }
-  goto next_list;
-}
-
-
-char *
-push_key_description (EMACS_INT ch, char *p)
-{
-  int c, c2;
-  bool tab_as_ci;
-
-  /* Clear all the meaningless bits above the meta bit.  */
-  c = ch & (meta_modifier | ~ - meta_modifier);
-  c2 = c & ~(alt_modifier | ctrl_modifier | hyper_modifier
-	     | meta_modifier | shift_modifier | super_modifier);
-
-  if (! CHARACTERP (make_number (c2)))
-    {
-      /* KEY_DESCRIPTION_SIZE is large enough for this.  */
-      p += sprintf (p, "[%d]", c);
-      return p;
-    }
-
-  tab_as_ci = (c2 == '\t' && (c & meta_modifier));
-
-  if (c & alt_modifier)
-    {
-      *p++ = 'A';
-      *p++ = '-';
-      c -= alt_modifier;
-    }
-  if ((c & ctrl_modifier) != 0
-      || (c2 < ' ' && c2 != 27 && c2 != '\t' && c2 != Ctl ('M'))
-      || tab_as_ci)
-    {
-      *p++ = 'C';
-      *p++ = '-';
-      c &= ~ctrl_modifier;
-    }
-  if (c & hyper_modifier)
-    {
-      *p++ = 'H';
-      *p++ = '-';
-      c -= hyper_modifier;
-    }
-  if (c & meta_modifier)
-    {
-      *p++ = 'M';
-      *p++ = '-';
-      c -= meta_modifier;
-    }
-  if (c & shift_modifier)
-    {
-      *p++ = 'S';
-      *p++ = '-';
-      c -= shift_modifier;
-    }
-  if (c & super_modifier)
-    {
-      *p++ = 's';
-      *p++ = '-';
-      c -= super_modifier;
-    }
-  if (c < 040)
-    {
-      if (c == 033)
-	{
-	  *p++ = 'E';
-	  *p++ = 'S';
-	  *p++ = 'C';
-	}
-      else if (tab_as_ci)
-	{
-	  *p++ = 'i';
-	}
-      else if (c == '\t')
-	{
-	  *p++ = 'T';
-	  *p++ = 'A';
-	  *p++ = 'B';
-	}
-      else if (c == Ctl ('M'))
-	{
-	  *p++ = 'R';
-	  *p++ = 'E';
-	  *p++ = 'T';
-	}
-      else
-	{
-	  /* `C-' already added above.  */
-	  if (c > 0 && c <= Ctl ('Z'))
-	    *p++ = c + 0140;
-	  else
-	    *p++ = c + 0100;
-	}
-    }
-  else if (c == 0177)
-    {
-      *p++ = 'D';
-      *p++ = 'E';
-      *p++ = 'L';
-    }
-  else if (c == ' ')
-   {
-      *p++ = 'S';
-      *p++ = 'P';
-      *p++ = 'C';
-    }
-  else if (c < 128)
-    *p++ = c;
-  else
-    {
-      /* Now we are sure that C is a valid character code.  */
-      p += CHAR_STRING (c, (unsigned char *) p);
-    }
-
-  return p;
-}
-
-/* This function cannot GC.  */
-
-DEFUN ("single-key-description", Fsingle_key_description,
-       Ssingle_key_description, 1, 2, 0,
-       doc: /* Return a pretty description of command character KEY.
-Control characters turn into C-whatever, etc.
-Optional argument NO-ANGLES non-nil means don't put angle brackets
-around function keys and event symbols.  */)
-  (Lisp_Object key, Lisp_Object no_angles)
-{
-  USE_SAFE_ALLOCA;
-
-  if (CONSP (key) && lucid_event_type_list_p (key))
-    key = Fevent_convert_list (key);
-
-  if (CONSP (key) && INTEGERP (XCAR (key)) && INTEGERP (XCDR (key)))
-    /* An interval from a map-char-table.  */
-    {
-      AUTO_STRING (dot_dot, "..");
-      return concat3 (Fsingle_key_description (XCAR (key), no_angles),
-		      dot_dot,
-		      Fsingle_key_description (XCDR (key), no_angles));
-    }
-
-  key = EVENT_HEAD (key);
-
-  if (INTEGERP (key))		/* Normal character.  */
-    {
-      char tem[KEY_DESCRIPTION_SIZE];
-      char *p = push_key_description (XINT (key), tem);
-      *p = 0;
-      return make_specified_string (tem, -1, p - tem, 1);
-    }
-  else if (SYMBOLP (key))	/* Function key or event-symbol.  */
-    {
-      if (NILP (no_angles))
-	{
-	  Lisp_Object result;
-	  char *buffer = SAFE_ALLOCA (sizeof "<>"
-				      + SBYTES (SYMBOL_NAME (key)));
-	  esprintf (buffer, "<%s>", SDATA (SYMBOL_NAME (key)));
-	  result = build_string (buffer);
-	  SAFE_FREE ();
-	  return result;
-	}
-      else
-	return Fsymbol_name (key);
-    }
-  else if (STRINGP (key))	/* Buffer names in the menubar.  */
-    return Fcopy_sequence (key);
-  else
-    error ("KEY must be an integer, cons, symbol, or string");
-}
-
 static char *
 push_text_char_description (register unsigned int c, register char *p)
 {
@@ -3042,16 +2591,9 @@ be preferred.  */);
   DEFSYM (Qremap, "remap");
   DEFSYM (QCadvertised_binding, ":advertised-binding");
 
-  command_remapping_vector = Fmake_vector (make_number (2), Qremap);
-  staticpro (&command_remapping_vector);
-
-  defsubr (&Scommand_remapping);
   defsubr (&Sminor_mode_key_binding);
-  defsubr (&Sdefine_key);
   defsubr (&Scurrent_active_maps);
   defsubr (&Saccessible_keymaps);
-  defsubr (&Skey_description);
-  defsubr (&Ssingle_key_description);
   defsubr (&Stext_char_description);
   defsubr (&Swhere_is_internal);
   defsubr (&Sdescribe_buffer_bindings);
@@ -39,6 +39,8 @@ extern void set_where_is_cache_keymaps(Lisp_Object);
 extern Lisp_Object get_where_is_cache_keymaps(void);
 extern char *push_key_description (EMACS_INT, char *);
 extern Lisp_Object access_keymap (Lisp_Object, Lisp_Object, bool, bool, bool);
+extern Lisp_Object store_in_keymap (Lisp_Object, Lisp_Object, Lisp_Object, bool);
+extern void silly_event_symbol_error (Lisp_Object);
 extern Lisp_Object get_keymap (Lisp_Object, bool, bool);
 extern bool keymap_memberp(Lisp_Object, Lisp_Object);
 extern Lisp_Object keymap_parent (Lisp_Object, bool);
@@ -969,6 +969,7 @@ enum pvec_type
   PVEC_MUTEX,
   PVEC_CONDVAR,
   PVEC_MODULE_FUNCTION,
+  PVEC_SYMBOL_WITH_POS,
 
   /* These should be last, check internal_equal to see why.  */
   PVEC_COMPILED,
@@ -1941,6 +1942,29 @@ XSUBR (Lisp_Object a)
   return XUNTAG (a, Lisp_Vectorlike);
 }
 
+/* A symbol annotated with a buffer position, as made by
+   `position-symbol'.  */
+
+struct Lisp_Symbol_With_Pos
+  {
+    union vectorlike_header header;
+    Lisp_Object sym;		/* A bare symbol.  */
+    Lisp_Object pos;		/* A fixnum.  */
+  };
+
+INLINE bool
+SYMBOL_WITH_POS_P (Lisp_Object a)
+{
+  return PSEUDOVECTORP (a, PVEC_SYMBOL_WITH_POS);
+}
+
+INLINE struct Lisp_Symbol_With_Pos *
+XSYMBOL_WITH_POS (Lisp_Object a)
+{
+  eassert (SYMBOL_WITH_POS_P (a));
+  return XUNTAG (a, Lisp_Vectorlike);
+}
+
 enum char_table_specials
   {
     /* This is the number of slots that every char table must have.  This
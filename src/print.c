@@ -1603,6 +1603,14 @@ print_vectorlike (Lisp_Object obj, Lisp_Object printcharfun, bool escapeflag,
       printchar ('>', printcharfun);
       break;
 
+    case PVEC_SYMBOL_WITH_POS:
+      print_c_string ("#<symbol ", printcharfun);
+      print_object (XSYMBOL_WITH_POS (obj)->sym, printcharfun, escapeflag);
+      print_c_string (" at ", printcharfun);
+      print_object (XSYMBOL_WITH_POS (obj)->pos, printcharfun, escapeflag);
+      printchar ('>', printcharfun);
+      break;
+
     case PVEC_RECORD:
       {
 	ptrdiff_t size = PVSIZE (obj);
@@ -79,11 +79,6 @@ SYNTAX_FLAGS_COMEND_SECOND (int flags)
   return (flags >> 19) & 1;
 }
 static bool
-SYNTAX_FLAGS_COMSTARTEND_FIRST (int flags)
-{
-  return (flags & 0x50000) != 0;
-}
-static bool
 SYNTAX_FLAGS_PREFIX (int flags)
 {
   return (flags >> 20) & 1;
@@ -127,41 +122,6 @@ SYNTAX_COMEND_FIRST (int c)
   return SYNTAX_FLAGS_COMEND_FIRST (SYNTAX_WITH_FLAGS (c));
 }
 
-/* We use these constants in place for comment-style and
-   string-ender-char to distinguish comments/strings started by
-   comment_fence and string_fence codes.  */
-
-enum
-  {
-    ST_COMMENT_STYLE = 256 + 1,
-    ST_STRING_STYLE = 256 + 2
-  };
-
-/* This is the internal form of the parse state used in parse-partial-sexp.  */
-
-struct lisp_parse_state
-  {
-    EMACS_INT depth;	/* Depth at end of parsing.  */
-    int instring;  /* -1 if not within string, else desired terminator.  */
-    EMACS_INT incomment; /* -1 if in unnestable comment else comment nesting */
-    int comstyle;  /* comment style a=0, or b=1, or ST_COMMENT_STYLE.  */
-    bool quoted;   /* True if just after an escape char at end of parsing.  */
-    EMACS_INT mindepth;	/* Minimum depth seen while scanning.  */
-    /* Char number of most recent start-of-expression at current level */
-    ptrdiff_t thislevelstart;
-    /* Char number of start of containing expression */
-    ptrdiff_t prevlevelstart;
-    ptrdiff_t location;	     /* Char number at which parsing stopped.  */
-    ptrdiff_t location_byte; /* Corresponding byte position.  */
-    ptrdiff_t comstr_start;  /* Position of last comment/string starter.  */
-    Lisp_Object levelstarts; /* Char numbers of starts-of-expression
-				of levels (starting from outermost).  */
-    int prev_syntax; /* Syntax of previous position scanned, when
-                        that position (potentially) holds the first char
-                        of a 2-char construct, i.e. comment delimiter
-                        or Sescape, etc.  Smax otherwise. */
-  };
-
 /* These variables are a cache for finding the start of a defun.
    find_start_pos is the place for which the defun start was found.
    find_start_value is the defun start position found for it.
@@ -178,11 +138,6 @@ static ptrdiff_t find_start_begv;
 static EMACS_INT find_start_modiff;
 
 
-Lisp_Object scan_lists (EMACS_INT, EMACS_INT, EMACS_INT, bool);
-static void scan_sexps_forward (struct lisp_parse_state *,
-                                ptrdiff_t, ptrdiff_t, ptrdiff_t, EMACS_INT,
-                                bool, int);
-static void internalize_parse_state (Lisp_Object, struct lisp_parse_state *);
 static bool in_classes (int, Lisp_Object);
 static void parse_sexp_propertize (ptrdiff_t charpos);
 
@@ -234,7 +189,7 @@ SYNTAX_MATCH (int c)
    Sign of COUNT gives the direction of the search.
  */
 
-static void
+void
 SETUP_SYNTAX_TABLE (ptrdiff_t from, ptrdiff_t count)
 {
   SETUP_BUFFER_SYNTAX_TABLE ();
@@ -527,7 +482,7 @@ update_syntax_table_forward (ptrdiff_t charpos, bool init,
    Global syntax-table data should be set up already to be good at CHARPOS
    or after.  On return global syntax data is good for lookup at CHARPOS.  */
 
-static bool
+bool
 char_quoted (ptrdiff_t charpos, ptrdiff_t bytepos)
 {
   enum syntaxcode code;
@@ -664,7 +619,7 @@ find_defun_start (ptrdiff_t pos, ptrdiff_t pos_byte)
 
 /* Return the SYNTAX_COMEND_FIRST of the character before POS, POS_BYTE.  */
 
-static bool
+bool
 prev_char_comend_first (ptrdiff_t pos, ptrdiff_t pos_byte)
 {
   int c;
@@ -690,7 +645,7 @@ prev_char_comend_first (ptrdiff_t pos, ptrdiff_t pos_byte)
    Global syntax data remains valid for backward search starting at
    the returned value (or at FROM, if the search was not successful).  */
 
-static bool
+bool
 back_comment (ptrdiff_t from, ptrdiff_t from_byte, ptrdiff_t stop,
 	      bool comnested, int comstyle, ptrdiff_t *charpos_ptr,
 	      ptrdiff_t *bytepos_ptr)
@@ -1038,21 +993,6 @@ this is probably the wrong function to use, because it can't take
   return make_number (syntax_code_spec[SYNTAX (char_int)]);
 }
 
-DEFUN ("matching-paren", Fmatching_paren, Smatching_paren, 1, 1, 0,
-       doc: /* Return the matching parenthesis of CHARACTER, or nil if none.  */)
-  (Lisp_Object character)
-{
-  int char_int;
-  enum syntaxcode code;
-  CHECK_CHARACTER (character);
-  char_int = XINT (character);
-  SETUP_BUFFER_SYNTAX_TABLE ();
-  code = SYNTAX (char_int);
-  if (code == Sopen || code == Sclose)
-    return SYNTAX_MATCH (char_int);
-  return Qnil;
-}
-
 DEFUN ("string-to-syntax", Fstring_to_syntax, Sstring_to_syntax, 1, 1, 0,
        doc: /* Convert a syntax descriptor STRING into a raw syntax descriptor.
 STRING should be a string of the form allowed as argument of
@@ -1132,75 +1072,6 @@ the value of a `syntax-table' text property.  */)
 
 /* I really don't know why this is interactive
    help-form should at least be made useful whilst reading the second arg.  */
-DEFUN ("modify-syntax-entry", Fmodify_syntax_entry, Smodify_syntax_entry, 2, 3,
-  "cSet syntax for character: \nsSet syntax for %s to: ",
-       doc: /* Set syntax for character CHAR according to string NEWENTRY.
-The syntax is changed only for table SYNTAX-TABLE, which defaults to
- the current buffer's syntax table.
-CHAR may be a cons (MIN . MAX), in which case, syntaxes of all characters
-in the range MIN to MAX are changed.
-The first character of NEWENTRY should be one of the following:
-  Space or -  whitespace syntax.    w   word constituent.
-  _           symbol constituent.   .   punctuation.
-  (           open-parenthesis.     )   close-parenthesis.
-  "           string quote.         \\   escape.
-  $           paired delimiter.     \\='   expression quote or prefix operator.
-  <           comment starter.      >   comment ender.
-  /           character-quote.      @   inherit from parent table.
-  |           generic string fence. !   generic comment fence.
-
-Only single-character comment start and end sequences are represented thus.
-Two-character sequences are represented as described below.
-The second character of NEWENTRY is the matching parenthesis,
- used only if the first character is `(' or `)'.
-Any additional characters are flags.
-Defined flags are the characters 1, 2, 3, 4, b, p, and n.
- 1 means CHAR is the start of a two-char comment start sequence.
- 2 means CHAR is the second character of such a sequence.
- 3 means CHAR is the start of a two-char comment end sequence.
- 4 means CHAR is the second character of such a sequence.
-
-There can be several orthogonal comment sequences.  This is to support
-language modes such as C++.  By default, all comment sequences are of style
-a, but you can set the comment sequence style to b (on the second character
-of a comment-start, and the first character of a comment-end sequence) and/or
-c (on any of its chars) using this flag:
- b means CHAR is part of comment sequence b.
- c means CHAR is part of comment sequence c.
- n means CHAR is part of a nestable comment sequence.
-
- p means CHAR is a prefix character for `backward-prefix-chars';
-   such characters are treated as whitespace when they occur
-   between expressions.
-usage: (modify-syntax-entry CHAR NEWENTRY &optional SYNTAX-TABLE)  */)
-  (Lisp_Object c, Lisp_Object newentry, Lisp_Object syntax_table)
-{
-  if (CONSP (c))
-    {
-      CHECK_CHARACTER_CAR (c);
-      CHECK_CHARACTER_CDR (c);
-    }
-  else
-    CHECK_CHARACTER (c);
-
-  if (NILP (syntax_table))
-    syntax_table = BVAR (current_buffer, syntax_table);
-  else
-    check_syntax_table (syntax_table);
-
-  newentry = Fstring_to_syntax (newentry);
-  if (CONSP (c))
-    SET_RAW_SYNTAX_ENTRY_RANGE (syntax_table, c, newentry);
-  else
-    SET_RAW_SYNTAX_ENTRY (syntax_table, XINT (c), newentry);
-
-  /* We clear the regexp cache, since character classes can now have
-     different values from those in the compiled regexps.*/
-  clear_regexp_cache ();
-
-  return Qnil;
-}
-
 /* Dump syntax table to buffer in human-readable format */
 
 DEFUN ("internal-describe-syntax-value", Finternal_describe_syntax_value,
@@ -2141,7 +2012,7 @@ in_classes (int c, Lisp_Object iso_classes)
    Global syntax data is assumed to initially be valid for FROM and
    remains valid for forward search starting at the returned position. */
 
-static bool
+bool
 forw_comment (ptrdiff_t from, ptrdiff_t from_byte, ptrdiff_t stop,
 	      EMACS_INT nesting, int style, int prev_syntax,
 	      ptrdiff_t *charpos_ptr, ptrdiff_t *bytepos_ptr,
@@ -2459,410 +2330,6 @@ between them, return t; otherwise return nil.  */)
   return Qt;
 }
 
-/* Return syntax code of character C if C is an ASCII character
-   or if MULTIBYTE_SYMBOL_P is false.  Otherwise, return Ssymbol.  */
-
-static enum syntaxcode
-syntax_multibyte (int c, bool multibyte_symbol_p)
-{
-  return ASCII_CHAR_P (c) || !multibyte_symbol_p ? SYNTAX (c) : Ssymbol;
-}
-
-Lisp_Object
-scan_lists (EMACS_INT from, EMACS_INT count, EMACS_INT depth, bool sexpflag)
-{
-  Lisp_Object val;
-  ptrdiff_t stop = count > 0 ? ZV : BEGV;
-  int c, c1;
-  int stringterm;
-  bool quoted;
-  bool mathexit = 0;
-  enum syntaxcode code;
-  EMACS_INT min_depth = depth;  /* Err out if depth gets less than this.  */
-  int comstyle = 0;		/* Style of comment encountered.  */
-  bool comnested = 0;		/* Whether the comment is nestable or not.  */
-  ptrdiff_t temp_pos;
-  EMACS_INT last_good = from;
-  bool found;
-  ptrdiff_t from_byte;
-  ptrdiff_t out_bytepos, out_charpos;
-  EMACS_INT dummy;
-  int dummy2;
-  bool multibyte_symbol_p = sexpflag && multibyte_syntax_as_symbol;
-  unsigned short int quit_count = 0;
-
-  if (depth > 0) min_depth = 0;
-
-  if (from > ZV) from = ZV;
-  if (from < BEGV) from = BEGV;
-
-  from_byte = CHAR_TO_BYTE (from);
-
-  maybe_quit ();
-
-  SETUP_SYNTAX_TABLE (from, count);
-  while (count > 0)
-    {
-      while (from < stop)
-	{
-	  rarely_quit (++quit_count);
-	  bool comstart_first, prefix;
-	  int syntax, other_syntax;
-	  UPDATE_SYNTAX_TABLE_FORWARD (from);
-	  c = FETCH_CHAR_AS_MULTIBYTE (from_byte);
-	  syntax = SYNTAX_WITH_FLAGS (c);
-	  code = syntax_multibyte (c, multibyte_symbol_p);
-	  comstart_first = SYNTAX_FLAGS_COMSTART_FIRST (syntax);
-	  comnested = SYNTAX_FLAGS_COMMENT_NESTED (syntax);
-	  comstyle = SYNTAX_FLAGS_COMMENT_STYLE (syntax, 0);
-	  prefix = SYNTAX_FLAGS_PREFIX (syntax);
-	  if (depth == min_depth)
-	    last_good = from;
-	  INC_BOTH (from, from_byte);
-	  UPDATE_SYNTAX_TABLE_FORWARD (from);
-	  if (from < stop && comstart_first
-	      && (c = FETCH_CHAR_AS_MULTIBYTE (from_byte),
-		  other_syntax = SYNTAX_WITH_FLAGS (c),
-		  SYNTAX_FLAGS_COMSTART_SECOND (other_syntax))
-	      && parse_sexp_ignore_comments)
-	    {
-	      /* We have encountered a comment start sequence and we
-		 are ignoring all text inside comments.  We must record
-		 the comment style this sequence begins so that later,
-		 only a comment end of the same style actually ends
-		 the comment section.  */
-	      code = Scomment;
-	      comstyle = SYNTAX_FLAGS_COMMENT_STYLE (other_syntax, syntax);
-	      comnested |= SYNTAX_FLAGS_COMMENT_NESTED (other_syntax);
-	      INC_BOTH (from, from_byte);
-	      UPDATE_SYNTAX_TABLE_FORWARD (from);
-	    }
-
-	  if (prefix)
-	    continue;
-
-	  switch (code)
-	    {
-	    case Sescape:
-	    case Scharquote:
-	      if (from == stop)
-		goto lose;
-	      INC_BOTH (from, from_byte);
-	      /* Treat following character as a word constituent.  */
-	      FALLTHROUGH;
-	    case Sword:
-	    case Ssymbol:
-	      if (depth || !sexpflag) break;
-	      /* This word counts as a sexp; return at end of it.  */
-	      while (from < stop)
-		{
-		  UPDATE_SYNTAX_TABLE_FORWARD (from);
-
-		  c = FETCH_CHAR_AS_MULTIBYTE (from_byte);
-		  switch (syntax_multibyte (c, multibyte_symbol_p))
-		    {
-		    case Scharquote:
-		    case Sescape:
-		      INC_BOTH (from, from_byte);
-		      if (from == stop)
-			goto lose;
-		      break;
-		    case Sword:
-		    case Ssymbol:
-		    case Squote:
-		      break;
-		    default:
-		      goto done;
-		    }
-		  INC_BOTH (from, from_byte);
-		  rarely_quit (++quit_count);
-		}
-	      goto done;
-
-	    case Scomment_fence:
-	      comstyle = ST_COMMENT_STYLE;
-	      FALLTHROUGH;
-	    case Scomment:
-	      if (!parse_sexp_ignore_comments) break;
-	      UPDATE_SYNTAX_TABLE_FORWARD (from);
-	      found = forw_comment (from, from_byte, stop,
-				    comnested, comstyle, 0,
-				    &out_charpos, &out_bytepos, &dummy,
-                                    &dummy2);
-	      from = out_charpos, from_byte = out_bytepos;
-	      if (!found)
-		{
-		  if (depth == 0)
-		    goto done;
-		  goto lose;
-		}
-	      INC_BOTH (from, from_byte);
-	      UPDATE_SYNTAX_TABLE_FORWARD (from);
-	      break;
-
-	    case Smath:
-	      if (!sexpflag)
-		break;
-	      if (from != stop && c == FETCH_CHAR_AS_MULTIBYTE (from_byte))
-		{
-		  INC_BOTH (from, from_byte);
-		}
-	      if (mathexit)
-		{
-		  mathexit = 0;
-		  goto close1;
-		}
-	      mathexit = 1;
-	      FALLTHROUGH;
-	    case Sopen:
-	      if (!++depth) goto done;
-	      break;
-
-	    case Sclose:
-	    close1:
-	      if (!--depth) goto done;
-	      if (depth < min_depth)
-		xsignal3 (Qscan_error,
-			  build_string ("Containing expression ends prematurely"),
-			  make_number (last_good), make_number (from));
-	      break;
-
-	    case Sstring:
-	    case Sstring_fence:
-	      temp_pos = dec_bytepos (from_byte);
-	      stringterm = FETCH_CHAR_AS_MULTIBYTE (temp_pos);
-	      while (1)
-		{
-		  enum syntaxcode c_code;
-		  if (from >= stop)
-		    goto lose;
-		  UPDATE_SYNTAX_TABLE_FORWARD (from);
-		  c = FETCH_CHAR_AS_MULTIBYTE (from_byte);
-		  c_code = syntax_multibyte (c, multibyte_symbol_p);
-		  if (code == Sstring
-		      ? c == stringterm && c_code == Sstring
-		      : c_code == Sstring_fence)
-		    break;
-
-		  if (c_code == Scharquote || c_code == Sescape)
-		    INC_BOTH (from, from_byte);
-		  INC_BOTH (from, from_byte);
-		  rarely_quit (++quit_count);
-		}
-	      INC_BOTH (from, from_byte);
-	      if (!depth && sexpflag) goto done;
-	      break;
-	    default:
-	      /* Ignore whitespace, punctuation, quote, endcomment.  */
-	      break;
-	    }
-	}
-
-      /* Reached end of buffer.  Error if within object, return nil if between */
-      if (depth)
-	goto lose;
-
-      return Qnil;
-
-      /* End of object reached */
-    done:
-      count--;
-    }
-
-  while (count < 0)
-    {
-      while (from > stop)
-	{
-	  rarely_quit (++quit_count);
-	  DEC_BOTH (from, from_byte);
-	  UPDATE_SYNTAX_TABLE_BACKWARD (from);
-	  c = FETCH_CHAR_AS_MULTIBYTE (from_byte);
-	  int syntax = SYNTAX_WITH_FLAGS (c);
-	  code = syntax_multibyte (c, multibyte_symbol_p);
-	  if (depth == min_depth)
-	    last_good = from;
-	  comstyle = 0;
-	  comnested = SYNTAX_FLAGS_COMMENT_NESTED (syntax);
-	  if (code == Sendcomment)
-	    comstyle = SYNTAX_FLAGS_COMMENT_STYLE (syntax, 0);
-	  if (from > stop && SYNTAX_FLAGS_COMEND_SECOND (syntax)
-	      && prev_char_comend_first (from, from_byte)
-	      && parse_sexp_ignore_comments)
-	    {
-	      /* We must record the comment style encountered so that
-		 later, we can match only the proper comment begin
-		 sequence of the same style.  */
-	      int c2, other_syntax;
-	      DEC_BOTH (from, from_byte);
-	      UPDATE_SYNTAX_TABLE_BACKWARD (from);
-	      code = Sendcomment;
-	      c2 = FETCH_CHAR_AS_MULTIBYTE (from_byte);
-	      other_syntax = SYNTAX_WITH_FLAGS (c2);
-	      comstyle = SYNTAX_FLAGS_COMMENT_STYLE (other_syntax, syntax);
-	      comnested |= SYNTAX_FLAGS_COMMENT_NESTED (other_syntax);
-	    }
-
-	  /* Quoting turns anything except a comment-ender
-	     into a word character.  Note that this cannot be true
-	     if we decremented FROM in the if-statement above.  */
-	  if (code != Sendcomment && char_quoted (from, from_byte))
-	    {
-	      DEC_BOTH (from, from_byte);
-	      code = Sword;
-	    }
-	  else if (SYNTAX_FLAGS_PREFIX (syntax))
-	    continue;
-
-	  switch (code)
-	    {
-	    case Sword:
-	    case Ssymbol:
-	    case Sescape:
-	    case Scharquote:
-	      if (depth || !sexpflag) break;
-	      /* This word counts as a sexp; count object finished
-		 after passing it.  */
-	      while (from > stop)
-		{
-		  temp_pos = from_byte;
-		  if (! NILP (BVAR (current_buffer, enable_multibyte_characters)))
-		    DEC_POS (temp_pos);
-		  else
-		    temp_pos--;
-		  UPDATE_SYNTAX_TABLE_BACKWARD (from - 1);
-		  c1 = FETCH_CHAR_AS_MULTIBYTE (temp_pos);
-		  /* Don't allow comment-end to be quoted.  */
-		  if (syntax_multibyte (c1, multibyte_symbol_p) == Sendcomment)
-		    goto done2;
-		  quoted = char_quoted (from - 1, temp_pos);
-		  if (quoted)
-		    {
-		      DEC_BOTH (from, from_byte);
-		      temp_pos = dec_bytepos (temp_pos);
-		      UPDATE_SYNTAX_TABLE_BACKWARD (from - 1);
-		    }
-		  c1 = FETCH_CHAR_AS_MULTIBYTE (temp_pos);
-		  if (! quoted)
-		    switch (syntax_multibyte (c1, multibyte_symbol_p))
-		      {
-		      case Sword: case Ssymbol: case Squote: break;
-		      default: goto done2;
-		      }
-		  DEC_BOTH (from, from_byte);
-		  rarely_quit (++quit_count);
-		}
-	      goto done2;
-
-	    case Smath:
-	      if (!sexpflag)
-		break;
-	      if (from > BEGV)
-		{
-		  temp_pos = dec_bytepos (from_byte);
-		  UPDATE_SYNTAX_TABLE_BACKWARD (from - 1);
-		  if (from != stop && c == FETCH_CHAR_AS_MULTIBYTE (temp_pos))
-		    DEC_BOTH (from, from_byte);
-		}
-	      if (mathexit)
-		{
-		  mathexit = 0;
-		  goto open2;
-		}
-	      mathexit = 1;
-	      FALLTHROUGH;
-	    case Sclose:
-	      if (!++depth) goto done2;
-	      break;
-
-	    case Sopen:
-	    open2:
-	      if (!--depth) goto done2;
-	      if (depth < min_depth)
-		xsignal3 (Qscan_error,
-			  build_string ("Containing expression ends prematurely"),
-			  make_number (last_good), make_number (from));
-	      break;
-
-	    case Sendcomment:
-	      if (!parse_sexp_ignore_comments)
-		break;
-	      found = back_comment (from, from_byte, stop, comnested, comstyle,
-				    &out_charpos, &out_bytepos);
-	      /* FIXME:  if !found, it really wasn't a comment-end.
-		 For single-char Sendcomment, we can't do much about it apart
-		 from skipping the char.
-		 For 2-char endcomments, we could try again, taking both
-		 chars as separate entities, but it's a lot of trouble
-		 for very little gain, so we don't bother either.  -sm */
-	      if (found)
-		from = out_charpos, from_byte = out_bytepos;
-	      break;
-
-	    case Scomment_fence:
-	    case Sstring_fence:
-	      while (1)
-		{
-		  if (from == stop)
-		    goto lose;
-		  DEC_BOTH (from, from_byte);
-		  UPDATE_SYNTAX_TABLE_BACKWARD (from);
-		  if (!char_quoted (from, from_byte))
-		    {
-		      c = FETCH_CHAR_AS_MULTIBYTE (from_byte);
-		      if (syntax_multibyte (c, multibyte_symbol_p) == code)
-			break;
-		    }
-		  rarely_quit (++quit_count);
-		}
-	      if (code == Sstring_fence && !depth && sexpflag) goto done2;
-	      break;
-
-	    case Sstring:
-	      stringterm = FETCH_CHAR_AS_MULTIBYTE (from_byte);
-	      while (true)
-		{
-		  if (from == stop)
-		    goto lose;
-		  DEC_BOTH (from, from_byte);
-		  UPDATE_SYNTAX_TABLE_BACKWARD (from);
-		  if (!char_quoted (from, from_byte))
-		    {
-		      c = FETCH_CHAR_AS_MULTIBYTE (from_byte);
-		      if (c == stringterm
-			  && (syntax_multibyte (c, multibyte_symbol_p)
-			      == Sstring))
-			break;
-		    }
-		  rarely_quit (++quit_count);
-		}
-	      if (!depth && sexpflag) goto done2;
-	      break;
-	    default:
-	      /* Ignore whitespace, punctuation, quote, endcomment.  */
-	      break;
-	    }
-	}
-
-      /* Reached start of buffer.  Error if within object, return nil if between */
-      if (depth)
-	goto lose;
-
-      return Qnil;
-
-    done2:
-      count++;
-    }
-
-
-  XSETFASTINT (val, from);
-  return val;
-
- lose:
-  xsignal3 (Qscan_error,
-	    build_string ("Unbalanced parentheses"),
-	    make_number (last_good), make_number (from));
-}
-
 DEFUN ("backward-prefix-chars", Fbackward_prefix_chars, Sbackward_prefix_chars,
        0, 0, 0,
        doc: /* Move point backward over any number of chars with prefix syntax.
@@ -2908,511 +2375,6 @@ the prefix syntax flag (p).  */)
 }
 
 
-/* If the character at FROM_BYTE is the second part of a 2-character
-   comment opener based on PREV_FROM_SYNTAX, update STATE and return
-   true.  */
-static bool
-in_2char_comment_start (struct lisp_parse_state *state,
-                        int prev_from_syntax,
-                        ptrdiff_t prev_from,
-                        ptrdiff_t from_byte)
-{
-  int c1, syntax;
-  if (SYNTAX_FLAGS_COMSTART_FIRST (prev_from_syntax)
-      && (c1 = FETCH_CHAR_AS_MULTIBYTE (from_byte),
-          syntax = SYNTAX_WITH_FLAGS (c1),
-          SYNTAX_FLAGS_COMSTART_SECOND (syntax)))
-    {
-      /* Record the comment style we have entered so that only
-         the comment-end sequence of the same style actually
-         terminates the comment section.  */
-      state->comstyle
-        = SYNTAX_FLAGS_COMMENT_STYLE (syntax, prev_from_syntax);
-      bool comnested = (SYNTAX_FLAGS_COMMENT_NESTED (prev_from_syntax)
-                        | SYNTAX_FLAGS_COMMENT_NESTED (syntax));
-      state->incomment = comnested ? 1 : -1;
-      state->comstr_start = prev_from;
-      return true;
-    }
-  return false;
-}
-
-/* Parse forward from FROM / FROM_BYTE to END,
-   assuming that FROM has state STATE,
-   and return a description of the state of the parse at END.
-   If STOPBEFORE, stop at the start of an atom.
-   If COMMENTSTOP is 1, stop at the start of a comment.
-   If COMMENTSTOP is -1, stop at the start or end of a comment,
-   after the beginning of a string, or after the end of a string.  */
-
-static void
-scan_sexps_forward (struct lisp_parse_state *state,
-		    ptrdiff_t from, ptrdiff_t from_byte, ptrdiff_t end,
-		    EMACS_INT targetdepth, bool stopbefore,
-		    int commentstop)
-{
-  enum syntaxcode code;
-  struct level { ptrdiff_t last, prev; };
-  struct level levelstart[100];
-  struct level *curlevel = levelstart;
-  struct level *endlevel = levelstart + 100;
-  EMACS_INT depth;      /* Paren depth of current scanning location.
-			   level - levelstart equals this except
-			   when the depth becomes negative.  */
-  EMACS_INT mindepth;		/* Lowest DEPTH value seen.  */
-  bool start_quoted = 0;	/* True means starting after a char quote.  */
-  Lisp_Object tem;
-  ptrdiff_t prev_from;		/* Keep one character before FROM.  */
-  ptrdiff_t prev_from_byte;
-  int prev_from_syntax, prev_prev_from_syntax;
-  bool boundary_stop = commentstop == -1;
-  bool nofence;
-  bool found;
-  ptrdiff_t out_bytepos, out_charpos;
-  int temp;
-  unsigned short int quit_count = 0;
-
-  prev_from = from;
-  prev_from_byte = from_byte;
-  if (from != BEGV)
-    DEC_BOTH (prev_from, prev_from_byte);
-
-  /* Use this macro instead of `from++'.  */
-#define INC_FROM				\
-do { prev_from = from;				\
-     prev_from_byte = from_byte; 		\
-     temp = FETCH_CHAR_AS_MULTIBYTE (prev_from_byte);	\
-     prev_prev_from_syntax = prev_from_syntax;  \
-     prev_from_syntax = SYNTAX_WITH_FLAGS (temp); \
-     INC_BOTH (from, from_byte);		\
-     if (from < end)				\
-       UPDATE_SYNTAX_TABLE_FORWARD (from);	\
-  } while (0)
-
-  maybe_quit ();
-
-  depth = state->depth;
-  start_quoted = state->quoted;
-  prev_prev_from_syntax = Smax;
-  prev_from_syntax = state->prev_syntax;
-
-  tem = state->levelstarts;
-  while (!NILP (tem))		/* >= second enclosing sexps.  */
-    {
-      Lisp_Object temhd = Fcar (tem);
-      if (RANGED_INTEGERP (PTRDIFF_MIN, temhd, PTRDIFF_MAX))
-        curlevel->last = XINT (temhd);
-      if (++curlevel == endlevel)
-        curlevel--; /* error ("Nesting too deep for parser"); */
-      curlevel->prev = -1;
-      curlevel->last = -1;
-      tem = Fcdr (tem);
-    }
-  curlevel->prev = -1;
-  curlevel->last = -1;
-
-  state->quoted = 0;
-  mindepth = depth;
-
-  SETUP_SYNTAX_TABLE (from, 1);
-
-  /* Enter the loop at a place appropriate for initial state.  */
-
-  if (state->incomment)
-    goto startincomment;
-  if (state->instring >= 0)
-    {
-      nofence = state->instring != ST_STRING_STYLE;
-      if (start_quoted)
-	goto startquotedinstring;
-      goto startinstring;
-    }
-  else if (start_quoted)
-    goto startquoted;
-  else if ((from < end)
-           && (in_2char_comment_start (state, prev_from_syntax,
-                                       prev_from, from_byte)))
-    {
-      INC_FROM;
-      prev_from_syntax = Smax; /* the syntax has already been "used up". */
-      goto atcomment;
-    }
-
-  while (from < end)
-    {
-      rarely_quit (++quit_count);
-      INC_FROM;
-
-      if ((from < end)
-          && (in_2char_comment_start (state, prev_from_syntax,
-                                      prev_from, from_byte)))
-        {
-          INC_FROM;
-          prev_from_syntax = Smax; /* the syntax has already been "used up". */
-          goto atcomment;
-        }
-
-      if (SYNTAX_FLAGS_PREFIX (prev_from_syntax))
-	continue;
-      code = prev_from_syntax & 0xff;
-      switch (code)
-	{
-	case Sescape:
-	case Scharquote:
-	  if (stopbefore) goto stop;  /* this arg means stop at sexp start */
-	  curlevel->last = prev_from;
-	startquoted:
-	  if (from == end) goto endquoted;
-	  INC_FROM;
-	  goto symstarted;
-	  /* treat following character as a word constituent */
-	case Sword:
-	case Ssymbol:
-	  if (stopbefore) goto stop;  /* this arg means stop at sexp start */
-	  curlevel->last = prev_from;
-	symstarted:
-	  while (from < end)
-	    {
-              if (in_2char_comment_start (state, prev_from_syntax,
-                                          prev_from, from_byte))
-                {
-                  INC_FROM;
-                  prev_from_syntax = Smax; /* the syntax has already been "used up". */
-                  goto atcomment;
-                }
-
-	      int symchar = FETCH_CHAR_AS_MULTIBYTE (from_byte);
-              switch (SYNTAX (symchar))
-		{
-		case Scharquote:
-		case Sescape:
-		  INC_FROM;
-		  if (from == end) goto endquoted;
-		  break;
-		case Sword:
-		case Ssymbol:
-		case Squote:
-		  break;
-		default:
-		  goto symdone;
-		}
-	      INC_FROM;
-	      rarely_quit (++quit_count);
-	    }
-	symdone:
-	  curlevel->prev = curlevel->last;
-	  break;
-
-	case Scomment_fence:
-          /* Record the comment style we have entered so that only
-             the comment-end sequence of the same style actually
-             terminates the comment section.  */
-          state->comstyle = ST_COMMENT_STYLE;
-          state->incomment = -1;
-          state->comstr_start = prev_from;
-          goto atcomment;
-	case Scomment:
-          state->comstyle = SYNTAX_FLAGS_COMMENT_STYLE (prev_from_syntax, 0);
-          state->incomment = (SYNTAX_FLAGS_COMMENT_NESTED (prev_from_syntax) ?
-                              1 : -1);
-          state->comstr_start = prev_from;
-        atcomment:
-          if (commentstop || boundary_stop) goto done;
-	startincomment:
-	  /* The (from == BEGV) test was to enter the loop in the middle so
-	     that we find a 2-char comment ender even if we start in the
-	     middle of it.  We don't want to do that if we're just at the
-	     beginning of the comment (think of (*) ... (*)).  */
-	  found = forw_comment (from, from_byte, end,
-				state->incomment, state->comstyle,
-				from == BEGV ? 0 : prev_from_syntax,
-				&out_charpos, &out_bytepos, &state->incomment,
-                                &prev_from_syntax);
-	  from = out_charpos; from_byte = out_bytepos;
-	  /* Beware!  prev_from and friends (except prev_from_syntax)
-	     are invalid now.  Luckily, the `done' doesn't use them
-	     and the INC_FROM sets them to a sane value without
-	     looking at them. */
-	  if (!found) goto done;
-	  INC_FROM;
-	  state->incomment = 0;
-	  state->comstyle = 0;	/* reset the comment style */
-	  prev_from_syntax = Smax; /* For the comment closer */
-          if (boundary_stop) goto done;
-	  break;
-
-	case Sopen:
-	  if (stopbefore) goto stop;  /* this arg means stop at sexp start */
-	  depth++;
-	  /* curlevel++->last ran into compiler bug on Apollo */
-	  curlevel->last = prev_from;
-	  if (++curlevel == endlevel)
-	    curlevel--; /* error ("Nesting too deep for parser"); */
-	  curlevel->prev = -1;
-	  curlevel->last = -1;
-	  if (targetdepth == depth) goto done;
-	  break;
-
-	case Sclose:
-	  depth--;
-	  if (depth < mindepth)
-	    mindepth = depth;
-	  if (curlevel != levelstart)
-	    curlevel--;
-	  curlevel->prev = curlevel->last;
-	  if (targetdepth == depth) goto done;
-	  break;
-
-	case Sstring:
-	case Sstring_fence:
-	  state->comstr_start = from - 1;
-	  if (stopbefore) goto stop;  /* this arg means stop at sexp start */
-	  curlevel->last = prev_from;
-	  state->instring = (code == Sstring
-			    ? (FETCH_CHAR_AS_MULTIBYTE (prev_from_byte))
-			    : ST_STRING_STYLE);
-	  if (boundary_stop) goto done;
-	startinstring:
-	  {
-	    nofence = state->instring != ST_STRING_STYLE;
-
-	    while (1)
-	      {
-		int c;
-		enum syntaxcode c_code;
-
-		if (from >= end) goto done;
-		c = FETCH_CHAR_AS_MULTIBYTE (from_byte);
-		c_code = SYNTAX (c);
-
-		/* Check C_CODE here so that if the char has
-		   a syntax-table property which says it is NOT
-		   a string character, it does not end the string.  */
-		if (nofence && c == state->instring && c_code == Sstring)
-		  break;
-
-		switch (c_code)
-		  {
-		  case Sstring_fence:
-		    if (!nofence) goto string_end;
-		    break;
-
-		  case Scharquote:
-		  case Sescape:
-		    INC_FROM;
-		  startquotedinstring:
-		    if (from >= end) goto endquoted;
-		    break;
-
-		  default:
-		    break;
-		  }
-		INC_FROM;
-		rarely_quit (++quit_count);
-	      }
-	  }
-	string_end:
-	  state->instring = -1;
-	  curlevel->prev = curlevel->last;
-	  INC_FROM;
-	  if (boundary_stop) goto done;
-	  break;
-
-	case Smath:
-	  /* FIXME: We should do something with it.  */
-	  break;
-	default:
-	  /* Ignore whitespace, punctuation, quote, endcomment.  */
-	  break;
-	}
-    }
-  goto done;
-
- stop:   /* Here if stopping before start of sexp. */
-  from = prev_from;    /* We have just fetched the char that starts it; */
-  from_byte = prev_from_byte;
-  prev_from_syntax = prev_prev_from_syntax;
-  goto done; /* but return the position before it. */
-
- endquoted:
-  state->quoted = 1;
- done:
-  state->depth = depth;
-  state->mindepth = mindepth;
-  state->thislevelstart = curlevel->prev;
-  state->prevlevelstart
-    = (curlevel == levelstart) ? -1 : (curlevel - 1)->last;
-  state->location = from;
-  state->location_byte = from_byte;
-  state->levelstarts = Qnil;
-  while (curlevel > levelstart)
-    state->levelstarts = Fcons (make_number ((--curlevel)->last),
-                                state->levelstarts);
-  state->prev_syntax = (SYNTAX_FLAGS_COMSTARTEND_FIRST (prev_from_syntax)
-                        || state->quoted) ? prev_from_syntax : Smax;
-}
-
-/* Convert a (lisp) parse state to the internal form used in
-   scan_sexps_forward.  */
-static void
-internalize_parse_state (Lisp_Object external, struct lisp_parse_state *state)
-{
-  Lisp_Object tem;
-
-  if (NILP (external))
-    {
-      state->depth = 0;
-      state->instring = -1;
-      state->incomment = 0;
-      state->quoted = 0;
-      state->comstyle = 0;	/* comment style a by default.  */
-      state->comstr_start = -1;	/* no comment/string seen.  */
-      state->levelstarts = Qnil;
-      state->prev_syntax = Smax;
-    }
-  else
-    {
-      tem = Fcar (external);
-      if (!NILP (tem))
-	state->depth = XINT (tem);
-      else
-	state->depth = 0;
-
-      external = Fcdr (external);
-      external = Fcdr (external);
-      external = Fcdr (external);
-      tem = Fcar (external);
-      /* Check whether we are inside string_fence-style string: */
-      state->instring = (!NILP (tem)
-                         ? (CHARACTERP (tem) ? XFASTINT (tem) : ST_STRING_STYLE)
-                         : -1);
-
-      external = Fcdr (external);
-      tem = Fcar (external);
-      state->incomment = (!NILP (tem)
-                          ? (INTEGERP (tem) ? XINT (tem) : -1)
-                          : 0);
-
-      external = Fcdr (external);
-      tem = Fcar (external);
-      state->quoted = !NILP (tem);
-
-      /* if the eighth element of the list is nil, we are in comment
-	 style a.  If it is non-nil, we are in comment style b */
-      external = Fcdr (external);
-      external = Fcdr (external);
-      tem = Fcar (external);
-      state->comstyle = (NILP (tem)
-                         ? 0
-                         : (RANGED_INTEGERP (0, tem, ST_COMMENT_STYLE)
-                            ? XINT (tem)
-                            : ST_COMMENT_STYLE));
-
-      external = Fcdr (external);
-      tem = Fcar (external);
-      state->comstr_start =
-	RANGED_INTEGERP (PTRDIFF_MIN, tem, PTRDIFF_MAX) ? XINT (tem) : -1;
-      external = Fcdr (external);
-      tem = Fcar (external);
-      state->levelstarts = tem;
-
-      external = Fcdr (external);
-      tem = Fcar (external);
-      state->prev_syntax = NILP (tem) ? Smax : XINT (tem);
-    }
-}
-
-DEFUN ("parse-partial-sexp", Fparse_partial_sexp, Sparse_partial_sexp, 2, 6, 0,
-       doc: /* Parse Lisp syntax starting at FROM until TO; return status of parse at TO.
-Parsing stops at TO or when certain criteria are met;
- point is set to where parsing stops.
-If fifth arg OLDSTATE is omitted or nil,
- parsing assumes that FROM is the beginning of a function.
-
-Value is a list of elements describing final state of parsing:
- 0. depth in parens.
- 1. character address of start of innermost containing list; nil if none.
- 2. character address of start of last complete sexp terminated.
- 3. non-nil if inside a string.
-    (it is the character that will terminate the string,
-     or t if the string should be terminated by a generic string delimiter.)
- 4. nil if outside a comment, t if inside a non-nestable comment,
-    else an integer (the current comment nesting).
- 5. t if following a quote character.
- 6. the minimum paren-depth encountered during this scan.
- 7. style of comment, if any.
- 8. character address of start of comment or string; nil if not in one.
- 9. List of positions of currently open parens, outermost first.
-10. When the last position scanned holds the first character of a
-    (potential) two character construct, the syntax of that position,
-    otherwise nil.  That construct can be a two character comment
-    delimiter or an Escaped or Char-quoted character.
-11..... Possible further internal information used by `parse-partial-sexp'.
-
-If third arg TARGETDEPTH is non-nil, parsing stops if the depth
-in parentheses becomes equal to TARGETDEPTH.
-Fourth arg STOPBEFORE non-nil means stop when we come to
- any character that starts a sexp.
-Fifth arg OLDSTATE is a list like what this function returns.
- It is used to initialize the state of the parse.  Elements number 1, 2, 6
- are ignored.
-Sixth arg COMMENTSTOP non-nil means stop after the start of a comment.
- If it is the symbol `syntax-table', stop after the start of a comment or a
- string, or after end of a comment or a string.  */)
-  (Lisp_Object from, Lisp_Object to, Lisp_Object targetdepth,
-   Lisp_Object stopbefore, Lisp_Object oldstate, Lisp_Object commentstop)
-{
-  struct lisp_parse_state state;
-  EMACS_INT target;
-
-  if (!NILP (targetdepth))
-    {
-      CHECK_NUMBER (targetdepth);
-      target = XINT (targetdepth);
-    }
-  else
-    target = TYPE_MINIMUM (EMACS_INT);	/* We won't reach this depth.  */
-
-  validate_region (&from, &to);
-  internalize_parse_state (oldstate, &state);
-  scan_sexps_forward (&state, XINT (from), CHAR_TO_BYTE (XINT (from)),
-		      XINT (to),
-		      target, !NILP (stopbefore),
-		      (NILP (commentstop)
-		       ? 0 : (EQ (commentstop, Qsyntax_table) ? -1 : 1)));
-
-  SET_PT_BOTH (state.location, state.location_byte);
-
-  return
-    Fcons (make_number (state.depth),
-	   Fcons (state.prevlevelstart < 0
-		  ? Qnil : make_number (state.prevlevelstart),
-	     Fcons (state.thislevelstart < 0
-		    ? Qnil : make_number (state.thislevelstart),
-	       Fcons (state.instring >= 0
-		      ? (state.instring == ST_STRING_STYLE
-			 ? Qt : make_number (state.instring)) : Qnil,
-		 Fcons (state.incomment < 0 ? Qt :
-			(state.incomment == 0 ? Qnil :
-			 make_number (state.incomment)),
-		   Fcons (state.quoted ? Qt : Qnil,
-		     Fcons (make_number (state.mindepth),
-		       Fcons ((state.comstyle
-			       ? (state.comstyle == ST_COMMENT_STYLE
-				  ? Qsyntax_table
-				  : make_number (state.comstyle))
-			       : Qnil),
-		         Fcons (((state.incomment
-                                  || (state.instring >= 0))
-                                 ? make_number (state.comstr_start)
-                                 : Qnil),
-			   Fcons (state.levelstarts,
-                             Fcons (state.prev_syntax == Smax
-                                    ? Qnil
-                                    : make_number (state.prev_syntax),
-                                Qnil)))))))))));
-}
-
 void
 init_syntax_once (void)
 {
@@ -3575,12 +2537,9 @@ In both cases, LIMIT bounds the search. */);
   Fmake_variable_buffer_local (Qcomment_end_can_be_escaped);
 
   defsubr (&Schar_syntax);
-  defsubr (&Smatching_paren);
   defsubr (&Sstring_to_syntax);
-  defsubr (&Smodify_syntax_entry);
   defsubr (&Sinternal_describe_syntax_value);
 
   defsubr (&Sforward_comment);
   defsubr (&Sbackward_prefix_chars);
-  defsubr (&Sparse_partial_sexp);
 }
@@ -229,7 +229,53 @@ SETUP_BUFFER_SYNTAX_TABLE (void)
   gl_state.current_syntax_table = BVAR (current_buffer, syntax_table);
 }
 
+/* We use these constants in place for comment-style and
+   string-ender-char to distinguish comments/strings started by
+   comment_fence and string_fence codes.  */
+
+enum parse_state_style
+  {
+    ST_COMMENT_STYLE = 256 + 1,
+    ST_STRING_STYLE = 256 + 2
+  };
+
+/* This is the internal form of the parse state used in parse-partial-sexp.  */
+
+struct lisp_parse_state
+  {
+    EMACS_INT depth;	/* Depth at end of parsing.  */
+    int instring;  /* -1 if not within string, else desired terminator.  */
+    EMACS_INT incomment; /* -1 if in unnestable comment else comment nesting */
+    int comstyle;  /* comment style a=0, or b=1, or ST_COMMENT_STYLE.  */
+    bool quoted;   /* True if just after an escape char at end of parsing.  */
+    EMACS_INT mindepth;	/* Minimum depth seen while scanning.  */
+    /* Char number of most recent start-of-expression at current level */
+    ptrdiff_t thislevelstart;
+    /* Char number of start of containing expression */
+    ptrdiff_t prevlevelstart;
+    ptrdiff_t location;	     /* Char number at which parsing stopped.  */
+    ptrdiff_t location_byte; /* Corresponding byte position.  */
+    ptrdiff_t comstr_start;  /* Position of last comment/string starter.  */
+    Lisp_Object levelstarts; /* Char numbers of starts-of-expression
+				of levels (starting from outermost).  */
+    int prev_syntax; /* Syntax of previous position scanned, when
+                        that position (potentially) holds the first char
+                        of a 2-char construct, i.e. comment delimiter
+                        or Sescape, etc.  Smax otherwise. */
+  };
+
 extern ptrdiff_t scan_words (ptrdiff_t, EMACS_INT);
+extern void SETUP_SYNTAX_TABLE (ptrdiff_t, ptrdiff_t);
+extern bool char_quoted (ptrdiff_t, ptrdiff_t);
+extern bool prev_char_comend_first (ptrdiff_t, ptrdiff_t);
+extern bool forw_comment (ptrdiff_t, ptrdiff_t, ptrdiff_t, EMACS_INT, int, int,
+                          ptrdiff_t *, ptrdiff_t *, EMACS_INT *, int *);
+extern bool back_comment (ptrdiff_t, ptrdiff_t, ptrdiff_t, bool, int,
+                          ptrdiff_t *, ptrdiff_t *);
+extern void scan_sexps_forward (struct lisp_parse_state *,
+                                ptrdiff_t, ptrdiff_t, ptrdiff_t, EMACS_INT,
+                                bool, int);
+extern void internalize_parse_state (Lisp_Object, struct lisp_parse_state *);
 extern void SETUP_SYNTAX_TABLE_FOR_OBJECT (Lisp_Object, ptrdiff_t, ptrdiff_t);
 extern void check_syntax_table (Lisp_Object obj);
 
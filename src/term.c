@@ -1361,7 +1361,8 @@ term_get_fkeys_1 (void)
       if (sequence)
 	Fdefine_key (KVAR (kboard, Vinput_decode_map), build_string (sequence),
 		     Fmake_vector (make_number (1),
-				   intern (keys[i].name)));
+				   intern (keys[i].name)),
+		     Qnil);
     }
 
   /* The uses of the "k0" capability are inconsistent; sometimes it
@@ -1380,13 +1381,13 @@ term_get_fkeys_1 (void)
 	  /* Define f0 first, so that f10 takes precedence in case the
 	     key sequences happens to be the same.  */
 	  Fdefine_key (KVAR (kboard, Vinput_decode_map), build_string (k0),
-		       Fmake_vector (make_number (1), intern ("f0")));
+		       Fmake_vector (make_number (1), intern ("f0")), Qnil);
 	Fdefine_key (KVAR (kboard, Vinput_decode_map), build_string (k_semi),
-		     Fmake_vector (make_number (1), intern ("f10")));
+		     Fmake_vector (make_number (1), intern ("f10")), Qnil);
       }
     else if (k0)
       Fdefine_key (KVAR (kboard, Vinput_decode_map), build_string (k0),
-		   Fmake_vector (make_number (1), intern (k0_name)));
+		   Fmake_vector (make_number (1), intern (k0_name)), Qnil);
   }
 
   /* Set up cookies for numbered function keys above f10. */
@@ -1410,7 +1411,8 @@ term_get_fkeys_1 (void)
 	      sprintf (fkey, "f%d", i);
 	      Fdefine_key (KVAR (kboard, Vinput_decode_map), build_string (sequence),
 			   Fmake_vector (make_number (1),
-					 intern (fkey)));
+					 intern (fkey)),
+			   Qnil);
 	    }
 	}
       }
@@ -1427,7 +1429,8 @@ term_get_fkeys_1 (void)
 	  if (sequence)                                                 \
 	    Fdefine_key (KVAR (kboard, Vinput_decode_map), build_string (sequence), \
 			 Fmake_vector (make_number (1),                 \
-				       intern (sym)));                  \
+				       intern (sym)),                   \
+			 Qnil);                                         \
 	}
 
       /* if there's no key_next keycap, map key_npage to `next' keysym */
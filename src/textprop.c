@@ -902,120 +902,6 @@ first valid position in OBJECT.  */)
   return position;
 }
 
-DEFUN ("next-property-change", Fnext_property_change,
-       Snext_property_change, 1, 3, 0,
-       doc: /* Return the position of next property change.
-Scans characters forward from POSITION in OBJECT till it finds
-a change in some text property, then returns the position of the change.
-If the optional second argument OBJECT is a buffer (or nil, which means
-the current buffer), POSITION is a buffer position (integer or marker).
-If OBJECT is a string, POSITION is a 0-based index into it.
-Return nil if LIMIT is nil or omitted, and the property is constant all
-the way to the end of OBJECT; if the value is non-nil, it is a position
-greater than POSITION, never equal.
-
-If the optional third argument LIMIT is non-nil, don't search
-past position LIMIT; return LIMIT if nothing is found before LIMIT.  */)
-  (Lisp_Object position, Lisp_Object object, Lisp_Object limit)
-{
-  register INTERVAL i, next;
-
-  if (NILP (object))
-    XSETBUFFER (object, current_buffer);
-
-  if (!NILP (limit) && !EQ (limit, Qt))
-    CHECK_NUMBER_COERCE_MARKER (limit);
-
-  i = validate_interval_range (object, &position, &position, soft);
-
-  /* If LIMIT is t, return start of next interval--don't
-     bother checking further intervals.  */
-  if (EQ (limit, Qt))
-    {
-      if (!i)
-	next = i;
-      else
-	next = next_interval (i);
-
-      if (!next)
-	XSETFASTINT (position, (STRINGP (object)
-				? SCHARS (object)
-				: BUF_ZV (XBUFFER (object))));
-      else
-	XSETFASTINT (position, next->position);
-      return position;
-    }
-
-  if (!i)
-    return limit;
-
-  next = next_interval (i);
-
-  while (next && intervals_equal (i, next)
-	 && (NILP (limit) || next->position < XFASTINT (limit)))
-    next = next_interval (next);
-
-  if (!next
-      || (next->position
-	  >= (INTEGERP (limit)
-	      ? XFASTINT (limit)
-	      : (STRINGP (object)
-		 ? SCHARS (object)
-		 : BUF_ZV (XBUFFER (object))))))
-    return limit;
-  else
-    return make_number (next->position);
-}
-
-DEFUN ("next-single-property-change", Fnext_single_property_change,
-       Snext_single_property_change, 2, 4, 0,
-       doc: /* Return the position of next property change for a specific property.
-Scans characters forward from POSITION till it finds
-a change in the PROP property, then returns the position of the change.
-If the optional third argument OBJECT is a buffer (or nil, which means
-the current buffer), POSITION is a buffer position (integer or marker).
-If OBJECT is a string, POSITION is a 0-based index into it.
-The property values are compared with `eq'.
-Return nil if LIMIT is nil or omitted, and the property is constant all
-the way to the end of OBJECT; if the value is non-nil, it is a position
-greater than POSITION, never equal.
-
-If the optional fourth argument LIMIT is non-nil, don't search
-past position LIMIT; return LIMIT if nothing is found before LIMIT.  */)
-  (Lisp_Object position, Lisp_Object prop, Lisp_Object object, Lisp_Object limit)
-{
-  register INTERVAL i, next;
-  register Lisp_Object here_val;
-
-  if (NILP (object))
-    XSETBUFFER (object, current_buffer);
-
-  if (!NILP (limit))
-    CHECK_NUMBER_COERCE_MARKER (limit);
-
-  i = validate_interval_range (object, &position, &position, soft);
-  if (!i)
-    return limit;
-
-  here_val = textget (i->plist, prop);
-  next = next_interval (i);
-  while (next
-	 && EQ (here_val, textget (next->plist, prop))
-	 && (NILP (limit) || next->position < XFASTINT (limit)))
-    next = next_interval (next);
-
-  if (!next
-      || (next->position
-	  >= (INTEGERP (limit)
-	      ? XFASTINT (limit)
-	      : (STRINGP (object)
-		 ? SCHARS (object)
-		 : BUF_ZV (XBUFFER (object))))))
-    return limit;
-  else
-    return make_number (next->position);
-}
-
 DEFUN ("previous-property-change", Fprevious_property_change,
        Sprevious_property_change, 1, 3, 0,
        doc: /* Return the position of previous property change.
@@ -1248,25 +1134,6 @@ Return t if any property value actually changed, nil otherwise.  */)
 				TEXT_PROPERTY_REPLACE);
 }
 
-/* Callers note, this can GC when OBJECT is a buffer (or nil).  */
-
-DEFUN ("put-text-property", Fput_text_property,
-       Sput_text_property, 4, 5, 0,
-       doc: /* Set one property of the text from START to END.
-The third and fourth arguments PROPERTY and VALUE
-specify the property to add.
-If the optional fifth argument OBJECT is a buffer (or nil, which means
-the current buffer), START and END are buffer positions (integers or
-markers).  If OBJECT is a string, START and END are 0-based indices into it.  */)
-  (Lisp_Object start, Lisp_Object end, Lisp_Object property,
-   Lisp_Object value, Lisp_Object object)
-{
-  AUTO_LIST2 (properties, property, value);
-  Fadd_text_properties (start, end, properties, object);
-  return Qnil;
-}
-
-
 DEFUN ("add-face-text-property", Fadd_face_text_property,
        Sadd_face_text_property, 3, 5, 0,
        doc: /* Add the face property to the text from START to END.
@@ -1450,28 +1317,19 @@ set_text_properties_1 (Lisp_Object start, Lisp_Object end, Lisp_Object propertie
   while (len > 0);
 }
 
-DEFUN ("remove-text-properties", Fremove_text_properties,
-       Sremove_text_properties, 3, 4, 0,
-       doc: /* Remove some properties from text from START to END.
-The third argument PROPERTIES is a property list
-whose property names specify the properties to remove.
-\(The values stored in PROPERTIES are ignored.)
-If the optional fourth argument OBJECT is a buffer (or nil, which means
-the current buffer), START and END are buffer positions (integers or
-markers).  If OBJECT is a string, START and END are 0-based indices into it.
-Return t if any property was actually removed, nil otherwise.
+/* Remove the properties named in the property list PROPERTIES from
+   the text of OBJECT, a buffer or a string, between START and END.
+   Return t if any property was actually removed, nil otherwise.  */
 
-Use `set-text-properties' if you want to remove all text properties.  */)
-  (Lisp_Object start, Lisp_Object end, Lisp_Object properties, Lisp_Object object)
+Lisp_Object
+remove_text_properties (Lisp_Object start, Lisp_Object end,
+			Lisp_Object properties, Lisp_Object object)
 {
   INTERVAL i, unchanged;
   ptrdiff_t s, len;
   bool modified = false;
   bool first_time = true;
 
-  if (NILP (object))
-    XSETBUFFER (object, current_buffer);
-
  retry:
   i = validate_interval_range (object, &start, &end, soft);
   if (!i)
@@ -1566,15 +1424,14 @@ Use `set-text-properties' if you want to remove all text properties.  */)
     }
 }
 
-DEFUN ("remove-list-of-text-properties", Fremove_list_of_text_properties,
-       Sremove_list_of_text_properties, 3, 4, 0,
-       doc: /* Remove some properties from text from START to END.
-The third argument LIST-OF-PROPERTIES is a list of property names to remove.
-If the optional fourth argument OBJECT is a buffer (or nil, which means
-the current buffer), START and END are buffer positions (integers or
-markers).  If OBJECT is a string, START and END are 0-based indices into it.
-Return t if any property was actually removed, nil otherwise.  */)
-  (Lisp_Object start, Lisp_Object end, Lisp_Object list_of_properties, Lisp_Object object)
+/* Remove the properties named in LIST_OF_PROPERTIES from the text of
+   OBJECT, a buffer or a string, between START and END.  Return t if
+   any property was actually removed, nil otherwise.  */
+
+Lisp_Object
+remove_list_of_text_properties (Lisp_Object start, Lisp_Object end,
+				Lisp_Object list_of_properties,
+				Lisp_Object object)
 {
   INTERVAL i, unchanged;
   ptrdiff_t s, len;
@@ -1582,9 +1439,6 @@ Return t if any property was actually removed, nil otherwise.  */)
   Lisp_Object properties;
   properties = list_of_properties;
 
-  if (NILP (object))
-    XSETBUFFER (object, current_buffer);
-
   i = validate_interval_range (object, &start, &end, soft);
   if (!i)
     return Qnil;
@@ -2333,15 +2187,10 @@ inherits it if NONSTICKINESS is nil.  The `front-sticky' and
   defsubr (&Sprevious_char_property_change);
   defsubr (&Snext_single_char_property_change);
   defsubr (&Sprevious_single_char_property_change);
-  defsubr (&Snext_property_change);
-  defsubr (&Snext_single_property_change);
   defsubr (&Sprevious_property_change);
   defsubr (&Sprevious_single_property_change);
   defsubr (&Sadd_text_properties);
-  defsubr (&Sput_text_property);
   defsubr (&Sadd_face_text_property);
-  defsubr (&Sremove_text_properties);
-  defsubr (&Sremove_list_of_text_properties);
   defsubr (&Stext_property_any);
   defsubr (&Stext_property_not_all);
 }
@@ -146,6 +146,25 @@
       (should-not (string= random-name buf-name))
       (should (< 0 random-number 999999)))))
 
+(ert-deftest buffers-test--hash-region-around-gap ()
+  "Region hashes read the text on both sides of the gap."
+  (with-temp-buffer
+    (insert "hello world, and some more text")
+    (let ((expected (secure-hash 'sha1 (buffer-string)))
+          (len (buffer-size)))
+      (dolist (gap (list (point-min) 4 (point-max)))
+        ;; Inserting and deleting a character leaves the gap at GAP.
+        (goto-char gap)
+        (insert "x")
+        (delete-char -1)
+        (should (equal (buffer-hash) expected))
+        ;; Entirely before, entirely after, and straddling the gap.
+        (dolist (region (list (cons 1 3) (cons (- len 3) (1+ len))
+                              (cons 2 (1- len))))
+          (should (equal (buffer-hash-region (car region) (cdr region))
+                         (secure-hash 'sha1 (current-buffer)
+                                      (car region) (cdr region)))))))))
+
 (provide 'buffers-tests)
 
 ;;; buffers-tests.el ends here
@@ -8,5 +8,40 @@
     (should (equal (char-resolve-modifiers 42) 42))
     (should (equal (char-resolve-modifiers 500000) 500000)))
 
+(ert-deftest get-byte ()
+  (let ((s (string-to-unibyte "a\377b")))
+    (should (= (get-byte 0 s) ?a))
+    (should (= (get-byte 1 s) 255))
+    (should (= (get-byte nil s) ?a))
+    (should-error (get-byte 3 s) :type 'args-out-of-range))
+  (should (= (get-byte 1 (string-to-multibyte "a\377")) 255))
+  (should-error (get-byte 0 "é"))
+  (with-temp-buffer
+    (insert "xyz")
+    (should (= (get-byte 2) ?y))
+    (goto-char (point-min))
+    (should (= (get-byte) ?x))))
+
+(ert-deftest unibyte-char-to-multibyte ()
+  (should (= (unibyte-char-to-multibyte ?a) ?a))
+  (should (= (unibyte-char-to-multibyte 200) #x3fffc8))
+  (should-error (unibyte-char-to-multibyte 256)))
+
+(ert-deftest multibyte-char-to-unibyte ()
+  (should (= (multibyte-char-to-unibyte ?a) ?a))
+  (should (= (multibyte-char-to-unibyte #x3fffc8) 200))
+  (should (= (multibyte-char-to-unibyte (unibyte-char-to-multibyte 255)) 255))
+  (should (= (multibyte-char-to-unibyte ?中) -1)))
+
+(ert-deftest char-from-name ()
+  (should (= (char-from-name "LATIN SMALL LETTER A") ?a))
+  (should (= (char-from-name "latin small letter a" t) ?a))
+  (should-not (char-from-name "latin small letter a"))
+  (should-not (char-from-name "NO SUCH CHARACTER NAME"))
+  (should (= (char-from-name "U+00E9") ?\u00E9))
+  (should-not (char-from-name "U+XYZ"))
+  (should (= (char-from-name "VARIATION SELECTOR-17") #xE0100))
+  (should (= (char-from-name "CJK COMPATIBILITY IDEOGRAPH-F900") #xF900)))
+
 (provide 'character-tests)
 ;;; character-tests.el ends here
@@ -0,0 +1,40 @@
+;;; charset-tests.el --- Tests for charset.rs
+
+;;; Commentary:
+
+;;; Code:
+
+(require 'ert)
+
+(ert-deftest charset-test--split-char ()
+  (should (equal (split-char ?a) '(ascii 97)))
+  (should-error (split-char "a") :type 'wrong-type-argument))
+
+(ert-deftest charset-test--make-char ()
+  (should (= (make-char 'ascii 97) ?a))
+  (should (= (apply #'make-char (split-char ?z)) ?z))
+  (should-error (make-char 'ascii 256) :type 'args-out-of-range)
+  (should (equal (condition-case err
+                     (make-char 'ascii 256)
+                   (args-out-of-range (cdr err)))
+                 '(255 256)))
+  (should-error (make-char 'no-such-charset 97) :type 'wrong-type-argument))
+
+(ert-deftest charset-test--char-charset ()
+  (should (eq (char-charset ?a) 'ascii))
+  (should (eq (char-charset ?a '(latin-iso8859-1 ascii)) 'ascii))
+  (should (eq (char-charset ?é '(latin-iso8859-1)) 'latin-iso8859-1))
+  (should-not (char-charset ?é '(ascii)))
+  (should (eq (char-charset ?é 'iso-latin-1) 'iso-8859-1))
+  (should-error (char-charset "a") :type 'wrong-type-argument))
+
+(ert-deftest charset-test--decode-encode-char ()
+  (should (= (decode-char 'unicode #x263A) #x263A))
+  (should (= (encode-char (decode-char 'unicode #x263A) 'unicode) #x263A))
+  (should (= (encode-char ?é 'latin-iso8859-1) #x69))
+  (should-not (encode-char ?中 'ascii))
+  (should-not (decode-char 'ascii 200))
+  (should-error (decode-char 'no-such-charset 65) :type 'wrong-type-argument))
+
+(provide 'charset-tests)
+;;; charset-tests.el ends here
@@ -50,5 +50,55 @@
   (let ((last-command-event ?a))
     (should-error (self-insert-command -1))))
 
+(ert-deftest cmds-test--post-self-insert-hook-variable ()
+  "`post-self-insert-hook' is defined from Rust with `defvar_lisp!'."
+  (should (special-variable-p 'post-self-insert-hook))
+  (should (boundp 'post-self-insert-hook))
+  (should (string-prefix-p
+           "Hook run at the end of `self-insert-command'."
+           (documentation-property 'post-self-insert-hook
+                                   'variable-documentation t)))
+  (let ((ran nil))
+    (with-temp-buffer
+      (let ((post-self-insert-hook (list (lambda () (setq ran t)))))
+        (let ((last-command-event ?a))
+          (self-insert-command 1))))
+    (should ran)))
+
+(ert-deftest cmds-tests--end-of-line-multibyte ()
+  (with-temp-buffer
+    (insert "héllo wörld €\nsecond\n")
+    (goto-char 3)
+    (end-of-line)
+    (should (= (point) 14))
+    (should (eolp))
+    (should (eq (char-before) ?€))
+    (beginning-of-line)
+    (should (= (point) 1))
+    (end-of-line 2)
+    (should (= (point) 21))
+    (beginning-of-line 0)
+    (should (= (point) 1))
+    (end-of-line 3)
+    (should (eobp))))
+
+(ert-deftest cmds-tests--goto-line-internal ()
+  (with-temp-buffer
+    (insert "one\ntwo\nthree\nfour")
+    (should (= (goto-line-internal 1) 1))
+    (should (bobp))
+    (should (= (goto-line-internal 3) 3))
+    (should (= (point) 9))
+    (should (looking-at "three"))
+    ;; Past the last line we stop at its beginning.
+    (should (= (goto-line-internal 10) 4))
+    (should (looking-at "four"))
+    (should (= (goto-line-internal 0) 1))
+    (should (bobp))
+    ;; Lines are counted from the start of the accessible portion.
+    (narrow-to-region 5 (point-max))
+    (should (= (goto-line-internal 2) 2))
+    (should (looking-at "three"))))
+
 (provide 'cmds-tests)
 ;;; cmds-tests.el ends here
@@ -0,0 +1,45 @@
+;;; crypto-tests.el --- tests for crypto/mod.rs -*- lexical-binding: t -*-
+
+;;; Code:
+
+(require 'ert)
+
+(ert-deftest crypto-test--secure-hash-algorithms ()
+  (let ((algorithms (secure-hash-algorithms)))
+    (dolist (alg '(md5 sha1 sha224 sha256 sha384 sha512))
+      (should (memq alg algorithms)))))
+
+(ert-deftest crypto-test--secure-hash-binary-length ()
+  (dolist (expected '((md5 . 16) (sha1 . 20) (sha224 . 28)
+                      (sha256 . 32) (sha384 . 48) (sha512 . 64)))
+    (let ((alg (car expected))
+          (len (cdr expected)))
+      (should (= (length (secure-hash alg "abc" nil nil t)) len))
+      (should (= (length (secure-hash alg "abc")) (* 2 len))))))
+
+(ert-deftest crypto-test--secure-hash-bytes ()
+  (let ((input (string-to-unibyte "abc\377\0xyz")))
+    (dolist (alg (secure-hash-algorithms))
+      (should (equal (secure-hash-bytes alg input)
+                     (secure-hash alg input)))))
+  (should (equal (secure-hash-bytes 'md5 "")
+                 "d41d8cd98f00b204e9800998ecf8427e"))
+  (should-error (secure-hash-bytes 'md5 "wörld"))
+  (should-error (secure-hash-bytes 'md4 "abc")))
+
+;; Make sure the region hash does not depend on where the gap is.
+(ert-deftest crypto-test--buffer-hash-region ()
+  (with-temp-buffer
+    (insert "hello, wörld")
+    (goto-char 4)
+    (insert "xyz")
+    (should (equal (buffer-hash-region) (buffer-hash)))
+    (should (equal (buffer-hash-region (point-min) (point-max) 'sha1)
+                   (buffer-hash)))
+    (should (equal (buffer-hash-region 1 4 'md5) (md5 "hel")))
+    (should (equal (buffer-hash-region 4 1 'md5) (md5 "hel")))
+    (should-error (buffer-hash-region 1 100)
+                  :type 'args-out-of-range)))
+
+(provide 'crypto-tests)
+;;; crypto-tests.el ends here
@@ -146,5 +146,109 @@
   (should (= (string-to-number "1111" 2) 15))
   (should (= (string-to-number "FF" 16) 255)))
 
+(ert-deftest data-test--string-to-number-types ()
+  (should (eql (string-to-number "42") 42))
+  (should (eql (string-to-number " -3.5x") -3.5))
+  (should (eql (string-to-number "ff" 16) 255))
+  (should (eql (string-to-number "1.5" 16) 1))
+  (should-error (string-to-number "1" 17) :type 'args-out-of-range))
+
+(ert-deftest data-test--number-to-string ()
+  (should (equal (number-to-string 42) "42"))
+  (should (equal (number-to-string -7) "-7"))
+  (should (equal (number-to-string most-negative-fixnum)
+                 (format "%d" most-negative-fixnum)))
+  (should (equal (number-to-string 1.0) "1.0"))
+  (should (equal (number-to-string -0.5) "-0.5"))
+  (should-not (multibyte-string-p (number-to-string 1)))
+  (let ((f 3.0e-7))
+    (should (eql (string-to-number (number-to-string f)) f)))
+  (should-error (number-to-string "1") :type 'wrong-type-argument)
+  (with-temp-buffer
+    (should-error (number-to-string (point-marker))
+                  :type 'wrong-type-argument)))
+
+(ert-deftest data-test--bool-vector-subsetp ()
+  (should (bool-vector-subsetp (bool-vector t nil nil t)
+                               (bool-vector t nil t t)))
+  (should (bool-vector-subsetp (bool-vector t nil t t)
+                               (bool-vector t nil t t)))
+  (should (bool-vector-subsetp (make-bool-vector 0 nil)
+                               (make-bool-vector 0 nil)))
+  (let ((a (make-bool-vector 100 nil))
+        (b (make-bool-vector 100 t)))
+    (aset a 99 t)
+    (should (bool-vector-subsetp a b))
+    (aset b 99 nil)
+    (should-not (bool-vector-subsetp a b)))
+  (should-error (bool-vector-subsetp (bool-vector t) (bool-vector t nil))
+                :type 'wrong-length-argument)
+  (should-error (bool-vector-subsetp [t] (bool-vector t))
+                :type 'wrong-type-argument))
+
+(defvar data-test--local-var 'default)
+(defvar data-test--auto-local-var 'default)
+
+(ert-deftest data-test--make-local-variable ()
+  (with-temp-buffer
+    (let ((first (current-buffer)))
+      (should (eq (make-local-variable 'data-test--local-var)
+                  'data-test--local-var))
+      (should (local-variable-p 'data-test--local-var))
+      (setq data-test--local-var 'first)
+      (with-temp-buffer
+        (should-not (local-variable-p 'data-test--local-var))
+        (should (eq data-test--local-var 'default))
+        (setq data-test--local-var 'second)
+        (with-current-buffer first
+          (should (eq data-test--local-var 'first))))
+      (should (eq (default-value 'data-test--local-var) 'second))
+      (kill-local-variable 'data-test--local-var)
+      (setq-default data-test--local-var 'default)))
+  (should-error (make-local-variable nil) :type 'setting-constant)
+  (should-error (make-local-variable t) :type 'setting-constant))
+
+(ert-deftest data-test--make-variable-buffer-local ()
+  (should (eq (make-variable-buffer-local 'data-test--auto-local-var)
+              'data-test--auto-local-var))
+  (should (local-variable-if-set-p 'data-test--auto-local-var))
+  (with-temp-buffer
+    (setq data-test--auto-local-var 'first)
+    (should (local-variable-p 'data-test--auto-local-var))
+    (with-temp-buffer
+      (should (eq data-test--auto-local-var 'default))))
+  (should (eq (default-value 'data-test--auto-local-var) 'default))
+  (should-error (make-variable-buffer-local nil) :type 'setting-constant)
+  (should-error (make-variable-buffer-local t) :type 'setting-constant))
+
+(defvar data-test--default-var 'default)
+
+(ert-deftest data-test--set-default ()
+  (with-temp-buffer
+    (setq-local data-test--default-var 'local)
+    (should (eq (set-default 'data-test--default-var 'new) 'new))
+    ;; The local binding is untouched...
+    (should (eq data-test--default-var 'local))
+    (should (eq (default-value 'data-test--default-var) 'new))
+    ;; ...while a buffer without one sees the new default.
+    (with-temp-buffer
+      (should (eq data-test--default-var 'new))))
+  (set-default 'data-test--default-var 'default)
+  (should-error (set-default nil t) :type 'setting-constant)
+  (should (eq (set-default :data-test--keyword :data-test--keyword)
+              :data-test--keyword)))
+
+(ert-deftest data-test--set-default-forwarded ()
+  ;; `fill-column' lives in a per-buffer slot.
+  (let ((old (default-value 'fill-column)))
+    (unwind-protect
+        (with-temp-buffer
+          (setq fill-column 12)
+          (set-default 'fill-column 34)
+          (should (= fill-column 12))
+          (with-temp-buffer
+            (should (= fill-column 34))))
+      (set-default 'fill-column old))))
+
 (provide 'data-tests)
 ;;; data-tests.el ends here
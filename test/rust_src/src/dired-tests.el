@@ -46,3 +46,19 @@
       (should system-groups)
       (should (listp system-groups))
       (should (< 0 (length system-groups))))))
+
+(ert-deftest test-file-attributes ()
+  (let* ((dir (make-temp-file "dired" t))
+         (file (expand-file-name "file" dir)))
+    (unwind-protect
+        (progn
+          (write-region "0123456789" nil file nil 'silent)
+          (let ((attrs (file-attributes file)))
+            (should-not (file-attribute-type attrs))
+            (should (= (file-attribute-size attrs) 10))
+            (should (time-less-p 0 (file-attribute-modification-time attrs))))
+          (should (eq (file-attribute-type (file-attributes dir)) t))
+          (should (integerp (file-attribute-user-id (file-attributes file))))
+          (should (stringp (file-attribute-user-id (file-attributes file 'string))))
+          (should-not (file-attributes (expand-file-name "missing" dir))))
+      (delete-directory dir t))))
@@ -96,3 +96,99 @@
     (should (equal fixed-time (time-subtract fixed-time 0)))
     (should (equal more-time (time-add fixed-time '(0 10))))
     (should (equal less-time (time-subtract fixed-time '(0 20))))))
+
+(ert-deftest editfns-tests--following-preceding-char ()
+  (dolist (multibyte '(t nil))
+    (with-temp-buffer
+      (set-buffer-multibyte multibyte)
+      (insert "aé€\n\U0001D11Eb")
+      (let ((gap (+ (point-min) 2)))
+        ;; Move the gap into the middle of the text.
+        (goto-char gap)
+        (insert "x")
+        (delete-char -1))
+      (narrow-to-region (1+ (point-min)) (1- (point-max)))
+      (goto-char (point-min))
+      (should (= (preceding-char) 0))
+      (while (< (point) (point-max))
+        (should (= (following-char) (char-after)))
+        (forward-char)
+        (should (= (preceding-char) (char-before))))
+      (should (= (following-char) 0)))))
+
+(ert-deftest editfns-tests--pos-bol-eol ()
+  (with-temp-buffer
+    (insert "first\nsecond line\nthird")
+    (goto-char 10)
+    (dolist (n '(nil 0 1 2 3 -1 5))
+      (should (= (pos-bol n) (line-beginning-position n)))
+      (should (= (pos-eol n) (line-end-position n))))
+    (should (= (pos-bol) 7))
+    (should (= (pos-eol) 18))
+    (should (= (point) 10))))
+
+(ert-deftest editfns-tests--pos-bol-ignores-fields ()
+  (with-temp-buffer
+    (insert "abc")
+    (insert (propertize "def" 'field 'prompt))
+    (insert "ghi")
+    (goto-char 8)
+    (should (= (line-beginning-position) 7))
+    (should (= (pos-bol) 1))
+    (goto-char 5)
+    (should (= (line-end-position) 7))
+    (should (= (pos-eol) 10))))
+
+(ert-deftest editfns-tests--line-number-at-pos ()
+  (with-temp-buffer
+    (insert "one\ntwo\nthree\nfour")
+    (should (= (line-number-at-pos) 4))
+    (should (= (line-number-at-pos 1) 1))
+    (should (= (line-number-at-pos 4) 1))
+    (should (= (line-number-at-pos 5) 2))
+    (should (= (line-number-at-pos (copy-marker 10)) 3))
+    (narrow-to-region 5 (point-max))
+    (goto-char 10)
+    (should (= (line-number-at-pos) 2))
+    (should (= (line-number-at-pos nil t) 3))
+    (should (= (line-number-at-pos 5) 1))
+    (should (= (line-number-at-pos 5 t) 2))
+    ;; Positions outside the accessible portion are clipped to it.
+    (should (= (line-number-at-pos 1) 1))
+    (should (= (line-number-at-pos 1 t) 1))))
+
+(ert-deftest editfns-tests--get-pos-property ()
+  (with-temp-buffer
+    (insert "abcdef")
+    (put-text-property 2 5 'face 'bold)
+    ;; Text properties are rear-sticky by default.
+    (should (eq (get-pos-property 5 'face) 'bold))
+    (should-not (get-pos-property 2 'face))
+    (should (eq (get-pos-property 3 'face (current-buffer)) 'bold))
+    (let ((ol (make-overlay 3 5)))
+      (overlay-put ol 'face 'italic)
+      ;; The overlay shadows the text property inside it and at its
+      ;; start, but text inserted at its end stays outside it.
+      (should (eq (get-pos-property 3 'face) 'italic))
+      (should (eq (get-pos-property 4 'face) 'italic))
+      (should (eq (get-pos-property 5 'face) 'bold))
+      (let ((front (make-overlay 3 5 nil t)))
+        (overlay-put front 'face 'underline)
+        (overlay-put front 'priority 10)
+        (should (eq (get-pos-property 4 'face) 'underline))
+        (should (eq (get-pos-property 3 'face) 'italic)))))
+  (should (eq (get-pos-property 1 'face (propertize "ab" 'face 'bold))
+              'bold)))
+
+(ert-deftest editfns-tests--insert-and-inherit ()
+  (with-temp-buffer
+    (insert (propertize "abcd" 'face 'bold))
+    (goto-char 3)
+    (insert-and-inherit "XY" ?Z)
+    (should (equal (buffer-string) "abXYZcd"))
+    (should (eq (get-text-property 3 'face) 'bold))
+    (should (eq (get-text-property 5 'face) 'bold))
+    (should (= (point) 6))
+    ;; Plain `insert' does not inherit.
+    (insert "Q")
+    (should-not (get-text-property 6 'face))))
@@ -249,6 +249,116 @@
 
   (should-error (run-hook-with-args)))
 
+(ert-deftest eval-tests--run-hooks-all-functions ()
+  (setq eval-tests--hook-calls nil)
+  (setq eval-tests--hook (list (lambda () (push 'first eval-tests--hook-calls))
+                               (lambda () (push 'second eval-tests--hook-calls))))
+  (run-hooks 'eval-tests--hook)
+  (should (equal eval-tests--hook-calls '(second first)))
+
+  ;; A t element runs the global value as well.
+  (setq eval-tests--hook-calls nil)
+  (with-temp-buffer
+    (set (make-local-variable 'eval-tests--hook)
+         (list (lambda () (push 'local eval-tests--hook-calls)) t))
+    (run-hooks 'eval-tests--hook))
+  (should (equal eval-tests--hook-calls '(second first local)))
+  (makunbound 'eval-tests--hook)
+  (makunbound 'eval-tests--hook-calls))
+
+(ert-deftest eval-tests--add-hook-depth ()
+  (setq eval-tests--hook nil)
+  (add-hook 'eval-tests--hook 'a)
+  (add-hook 'eval-tests--hook 'b 90)
+  (add-hook 'eval-tests--hook 'c -50)
+  (add-hook 'eval-tests--hook 'd)
+  (add-hook 'eval-tests--hook 'e t)
+  (add-hook 'eval-tests--hook 'a 10)
+  (should (equal eval-tests--hook '(c d a b e)))
+  (remove-hook 'eval-tests--hook 'b)
+  (should (equal eval-tests--hook '(c d a e)))
+  (should-not (assq 'b (get 'eval-tests--hook 'hook--depth-alist)))
+  (makunbound 'eval-tests--hook)
+  (put 'eval-tests--hook 'hook--depth-alist nil))
+
+(ert-deftest eval-tests--add-hook-local ()
+  (setq eval-tests--hook '(global))
+  (with-temp-buffer
+    (add-hook 'eval-tests--hook 'local nil t)
+    (should (equal eval-tests--hook '(local t)))
+    (should (equal (default-value 'eval-tests--hook) '(global)))
+    (add-hook 'eval-tests--hook 'other)
+    (should (equal (default-value 'eval-tests--hook) '(other global)))
+    (remove-hook 'eval-tests--hook 'local t)
+    (should-not (local-variable-p 'eval-tests--hook))
+    (remove-hook 'eval-tests--hook 'other)
+    (should (equal eval-tests--hook '(global))))
+  (makunbound 'eval-tests--hook))
+
+(ert-deftest eval-tests--run-hook-with-args ()
+  (setq eval-tests--hook-calls nil)
+  (setq eval-tests--hook (lambda (&rest args) (push args eval-tests--hook-calls)))
+  (should-not (run-hook-with-args 'eval-tests--hook 1 2))
+  (should (equal eval-tests--hook-calls '((1 2))))
+  (makunbound 'eval-tests--hook)
+  (makunbound 'eval-tests--hook-calls))
+
+(ert-deftest eval-tests--run-hook-with-args-until-success ()
+  (setq eval-tests--hook-calls nil)
+  (setq eval-tests--hook
+        (list (lambda (x) (push 'first eval-tests--hook-calls) nil)
+              (lambda (x) (push 'second eval-tests--hook-calls) (* x 2))
+              (lambda (x) (push 'third eval-tests--hook-calls) x)))
+  (should (equal (run-hook-with-args-until-success 'eval-tests--hook 21) 42))
+  (should (equal eval-tests--hook-calls '(second first)))
+  (setq eval-tests--hook (lambda (x) nil))
+  (should-not (run-hook-with-args-until-success 'eval-tests--hook 21))
+  (makunbound 'eval-tests--hook)
+  (makunbound 'eval-tests--hook-calls))
+
+(ert-deftest eval-tests--run-hook-with-args-until-failure ()
+  (setq eval-tests--hook-calls nil)
+  (setq eval-tests--hook
+        (list (lambda (x) (push 'first eval-tests--hook-calls) x)
+              (lambda (x) (push 'second eval-tests--hook-calls) nil)
+              (lambda (x) (push 'third eval-tests--hook-calls) x)))
+  (should-not (run-hook-with-args-until-failure 'eval-tests--hook 1))
+  (should (equal eval-tests--hook-calls '(second first)))
+  (setq eval-tests--hook (lambda (x) x))
+  (should (eq (run-hook-with-args-until-failure 'eval-tests--hook 1) t))
+  (makunbound 'eval-tests--hook)
+  (makunbound 'eval-tests--hook-calls))
+
+(ert-deftest eval-tests--define-error ()
+  (define-error 'eval-tests--parent-error "Parent error")
+  (define-error 'eval-tests--child-error "Child error" 'eval-tests--parent-error)
+  (should (equal (get 'eval-tests--child-error 'error-conditions)
+                 '(eval-tests--child-error eval-tests--parent-error error)))
+  (should (equal (get 'eval-tests--child-error 'error-message) "Child error"))
+  (define-error 'eval-tests--multi-error nil
+    '(eval-tests--child-error arith-error))
+  (should (equal (get 'eval-tests--multi-error 'error-conditions)
+                 '(eval-tests--multi-error eval-tests--child-error
+                   eval-tests--parent-error error arith-error)))
+  (should-not (get 'eval-tests--multi-error 'error-message))
+  (should (equal (condition-case err
+                     (signal 'eval-tests--child-error '(42))
+                   (eval-tests--parent-error (error-message-string err)))
+                 "Child error: 42"))
+  (should (equal (cadr (should-error
+                        (define-error 'eval-tests--bad-error "Bad"
+                          '(eval-tests--no-such-error))))
+                 "Unknown signal `eval-tests--no-such-error'")))
+
+(ert-deftest eval-tests--internal-ignore-errors ()
+  (should (eq (internal--ignore-errors (lambda () 'value)) 'value))
+  (should-not (internal--ignore-errors (lambda () (error "Boom"))))
+  (should-not (internal--ignore-errors (lambda () (car 1))))
+  ;; Non-local exits other than errors pass through.
+  (should (eq (catch 'eval-tests--tag
+                (internal--ignore-errors (lambda () (throw 'eval-tests--tag 1))))
+              1)))
+
 (ert-deftest eval-tests--funcall()
   (let ((f (lambda () 1)))
     (should (eq (funcall f) 1)))
@@ -39,3 +39,165 @@
       (should-not (file-name-case-insensitive-p file)))
     (when (eq system-type 'darwin)
       (should (file-name-case-insensitive-p file)))))
+
+(ert-deftest test-insert-file-contents-literally ()
+  (let ((file (make-temp-file "fileio"))
+        (bytes (unibyte-string 0 1 127 128 200 255 ?\n ?a)))
+    (unwind-protect
+        (progn
+          (let ((coding-system-for-write 'no-conversion))
+            (write-region bytes nil file nil 'silent))
+          (with-temp-buffer
+            (set-buffer-multibyte nil)
+            (should (equal (insert-file-contents-literally file)
+                           (list file (length bytes))))
+            (should (= (point) (point-min)))
+            (should (equal (buffer-string) bytes)))
+          (with-temp-buffer
+            (set-buffer-multibyte nil)
+            (should (equal (insert-file-contents-literally file nil 2 5)
+                           (list file 3)))
+            (should (equal (buffer-string) (unibyte-string 127 128 200))))
+          (with-temp-buffer
+            (let ((last-coding-system-used nil))
+              (insert-file-contents-literally file)
+              (should (eq last-coding-system-used 'no-conversion)))
+            (should (equal (encode-coding-string (buffer-string) 'raw-text)
+                           bytes))))
+      (delete-file file))))
+
+(ert-deftest test-write-region-literally ()
+  (let ((file (make-temp-file "fileio"))
+        (text "abc\nλ→ü\n"))
+    (unwind-protect
+        (with-temp-buffer
+          (insert "xx" text "yy")
+          ;; Move the gap into the middle of the region.
+          (goto-char 6)
+          (insert "q")
+          (delete-char -1)
+          (write-region-literally 3 (+ 3 (length text)) file)
+          (with-temp-buffer
+            (set-buffer-multibyte nil)
+            (insert-file-contents-literally file)
+            (should (equal (buffer-string)
+                           (encode-coding-string text 'utf-8-emacs))))
+          (write-region-literally "zz" nil file t)
+          (should (equal (nth 1 (insert-file-contents-literally file))
+                         (+ 2 (string-bytes text))))
+          (should-error (write-region-literally 1 2 file nil nil nil 'excl)
+                        :type 'file-already-exists))
+      (delete-file file))))
+
+(ert-deftest test-file-access-predicates ()
+  (let* ((dir (make-temp-file "fileio" t))
+         (file (expand-file-name "file" dir))
+         (missing (expand-file-name "missing" dir)))
+    (unwind-protect
+        (progn
+          (write-region "" nil file nil 'silent)
+          (should (file-exists-p file))
+          (should (file-readable-p file))
+          (should (file-writable-p file))
+          (should-not (file-directory-p file))
+          (should (file-exists-p dir))
+          (should (file-readable-p dir))
+          (should (file-writable-p dir))
+          (should (file-directory-p dir))
+          (should-not (file-exists-p missing))
+          (should-not (file-readable-p missing))
+          (should (file-writable-p missing))
+          (should-not (file-directory-p missing))
+          (should-not (file-writable-p (expand-file-name "missing/file" dir))))
+      (delete-directory dir t))))
+
+(ert-deftest test-file-name-absolute-p ()
+  (should (file-name-absolute-p "/abs"))
+  (should (file-name-absolute-p "~/rel"))
+  (should (file-name-absolute-p "~user/rel"))
+  (should-not (file-name-absolute-p "rel/path"))
+  (should-not (file-name-absolute-p ""))
+  (should-error (file-name-absolute-p 'abs) :type 'wrong-type-argument)
+  (when (memq system-type '(ms-dos windows-nt))
+    (should (file-name-absolute-p "C:\\path"))
+    (should (file-name-absolute-p "\\\\server\\share"))
+    (should-not (file-name-absolute-p "C:path"))))
+
+(ert-deftest test-file-name-as-directory ()
+  (should (equal (file-name-as-directory "/tmp") "/tmp/"))
+  (let ((dir "/tmp/"))
+    (should (eq (file-name-as-directory dir) dir)))
+  (should (equal (file-name-as-directory "/") "/"))
+  (should (equal (file-name-as-directory "") "./"))
+  (should (multibyte-string-p (file-name-as-directory "/tmp/λ")))
+  (should-error (file-name-as-directory nil) :type 'wrong-type-argument))
+
+(ert-deftest test-directory-file-name ()
+  (should (equal (directory-file-name "/tmp/") "/tmp"))
+  (should (equal (directory-file-name "/tmp///") "/tmp"))
+  (let ((file "/tmp"))
+    (should (eq (directory-file-name file) file)))
+  (should (equal (directory-file-name "/") "/"))
+  (should (equal (directory-file-name "//") "//"))
+  (should (equal (directory-file-name "///") "/"))
+  (should (equal (directory-file-name "") ""))
+  (should (multibyte-string-p (directory-file-name "/tmp/λ/")))
+  (should-error (directory-file-name nil) :type 'wrong-type-argument))
+
+(ert-deftest test-abbreviate-file-name ()
+  (let* ((home (make-temp-file "home" t))
+         (process-environment (cons (format "HOME=%s/" home)
+                                    process-environment))
+         (directory-abbrev-alist nil)
+         (abbreviated-home-dir nil))
+    (unwind-protect
+        (progn
+          (should (equal (abbreviate-file-name (expand-file-name "a/b" home))
+                         "~/a/b"))
+          (should (string-match-p abbreviated-home-dir home))
+          (should (equal (abbreviate-file-name home) "~"))
+          (should (equal (abbreviate-file-name (concat home "x/y"))
+                         (concat home "x/y")))
+          (should (equal (abbreviate-file-name "/elsewhere/file")
+                         "/elsewhere/file"))
+          (let ((directory-abbrev-alist
+                 `((,(concat "\\`" (regexp-quote home) "/short") . "/s"))))
+            (should (equal (abbreviate-file-name (concat home "/short/f"))
+                           "/s/f"))))
+      (delete-directory home t))))
+
+(ert-deftest test-make-temp-name ()
+  (let* ((prefix (expand-file-name "fileio-λ" temporary-file-directory))
+         (first (make-temp-name prefix))
+         (second (make-temp-name prefix)))
+    (should (string-prefix-p prefix first))
+    (should (string-prefix-p prefix second))
+    (should (= (length first) (+ (length prefix) 6)))
+    (should (string-match-p "\\`[0-9a-zA-Z]\\{6\\}\\'"
+                            (substring first (length prefix))))
+    (should-not (equal first second))
+    (should-not (file-exists-p first))))
+
+(ert-deftest test-make-temp-file ()
+  (let ((file (make-temp-file "fileio" nil ".txt" "initial text")))
+    (unwind-protect
+        (progn
+          (should (file-name-absolute-p file))
+          (should (string-prefix-p (expand-file-name "fileio" temporary-file-directory)
+                                   file))
+          (should (string-suffix-p ".txt" file))
+          (should (file-regular-p file))
+          (should (equal (with-temp-buffer
+                           (insert-file-contents file)
+                           (buffer-string))
+                         "initial text")))
+      (delete-file file)))
+  (let ((dir (make-temp-file "fileio" t)))
+    (unwind-protect
+        (should (file-directory-p dir))
+      (delete-directory dir)))
+  (let ((file (make-temp-file "")))
+    (unwind-protect
+        (should (equal (file-name-directory file)
+                       (file-name-as-directory temporary-file-directory)))
+      (delete-file file))))
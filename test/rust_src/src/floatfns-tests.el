@@ -37,4 +37,71 @@
 (ert-deftest float-divide-by-zero ()
   (should (isnan (/ 0.0 0))))
 
+(ert-deftest floatfns-tests--abs ()
+  (should (= (abs -5) 5))
+  (should (integerp (abs -5)))
+  (should (= (abs -2.5) 2.5))
+  (let ((a (abs most-negative-fixnum)))
+    (should (floatp a))
+    (should (= a (- (float most-negative-fixnum)))))
+  (with-temp-buffer
+    (insert "abc")
+    (should (= (abs (point-marker)) 4))))
+
+(ert-deftest floatfns-tests--float ()
+  (should (eql (float 3) 3.0))
+  (let ((f 1.5))
+    (should (eq (float f) f)))
+  (with-temp-buffer
+    (should (eql (float (point-marker)) 1.0)))
+  (should-error (float "3") :type 'wrong-type-argument))
+
+(ert-deftest floatfns-tests--truncate ()
+  (should (= (truncate 7 2) 3))
+  (should (= (truncate -7 2) -3))
+  (should (= (truncate 7.5) 7))
+  (should (= (truncate 7 2.0) 3))
+  (with-temp-buffer
+    (insert "abcdef")
+    (should (= (truncate (point-marker) 2) 3)))
+  (should-error (truncate 7 0) :type 'arith-error))
+
+(ert-deftest floatfns-tests--floor-ceiling-round ()
+  (should (= (floor 7 2) 3))
+  (should (= (floor -7 2) -4))
+  (should (= (floor 7.5) 7))
+  (should (= (ceiling 7 2) 4))
+  (should (= (ceiling -7 2) -3))
+  (should (= (ceiling 7.5) 8))
+  (should (= (round 2.5) 2))
+  (should (= (round 3.5) 4))
+  (should (= (round 5 2) 2))
+  (should (= (round 7 2) 4))
+  (should (= (round 7 2.0) 4))
+  ;; Two fixnums divide exactly, without going through floats.
+  (should (= (floor most-positive-fixnum 1) most-positive-fixnum))
+  (should-error (floor 7 0) :type 'arith-error)
+  (should-error (ceiling 7 0) :type 'arith-error)
+  (should-error (round 7 0) :type 'arith-error))
+
+(ert-deftest floatfns-tests--expt ()
+  (should (eql (expt 2 10) 1024))
+  (should (eql (expt -3 3) -27))
+  (should (eql (expt 2 -1) 0.5))
+  (should (eql (expt 0 0) 1))
+  (should (eql (expt 0 5) 0))
+  (should (eql (expt -1 most-positive-fixnum) -1))
+  (should (eql (expt 2.0 3) 8.0))
+  ;; Too big for a fixnum, so the result is a float.
+  (should (floatp (expt 2 100)))
+  (should (= (expt 2 100) (expt 2.0 100))))
+
+(ert-deftest floatfns-tests--sqrt-exp-log ()
+  (should (= (sqrt 16) 4.0))
+  (should (isnan (sqrt -1)))
+  (should (= (exp 0) 1.0))
+  (should (= (log 8 2) 3.0))
+  (should (= (log 100 10) 2.0))
+  (should (= (log 1) 0.0)))
+
 (provide 'floatfns-tests)
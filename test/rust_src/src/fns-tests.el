@@ -60,6 +60,29 @@
   (should (equal (copy-sequence (bool-vector nil t t nil t)) (bool-vector nil t t nil t)))
   (should (equal (copy-sequence (list 1 2 3)) (list 1 2 3))))
 
+(ert-deftest test-copy-sequence-independent ()
+  (let* ((l (list 1 2 3))
+         (c (copy-sequence l)))
+    (setcar c 'x)
+    (should (equal l '(1 2 3)))
+    (should (equal c '(x 2 3))))
+  (let* ((v (vector 1 2 3))
+         (c (copy-sequence v)))
+    (aset c 0 'x)
+    (should (equal v [1 2 3]))
+    (should (equal c [x 2 3])))
+  (should (equal (copy-sequence '(1 2 . 3)) '(1 2 . 3)))
+  (let* ((s (propertize "h\u00e9llo" 'face 'bold))
+         (c (copy-sequence s)))
+    (should-not (eq c s))
+    (should (equal c s))
+    (should (multibyte-string-p c))
+    (should (eq (get-text-property 1 'face c) 'bold))
+    (aset c 0 ?j)
+    (should (equal s "h\u00e9llo")))
+  (should-not (multibyte-string-p (copy-sequence (string-to-unibyte "abc"))))
+  (should-error (copy-sequence 'foo) :type 'wrong-type-argument))
+
 (ert-deftest test-vconcat ()
   (should-error (vconcat t))
   (should (equal (vconcat) []))
@@ -79,6 +102,26 @@
   (should (equal "xyzzy" (reverse (reverse "xyzzy"))))
   (should (equal "こんにちは / ｺﾝﾆﾁﾊ" (reverse (reverse "こんにちは / ｺﾝﾆﾁﾊ")))))
 
+(ert-deftest test-reverse-fresh-copy ()
+  (should (eq (reverse nil) nil))
+  (let* ((l (list 1 2 3))
+         (r (reverse l)))
+    (should (equal r '(3 2 1)))
+    (should (equal l '(1 2 3))))
+  (let ((v (vector 1 2 3)))
+    (should (equal (reverse v) [3 2 1]))
+    (should-not (eq (reverse v) v))
+    (should (equal v [1 2 3])))
+  ;; Multibyte strings are reversed by character, not by byte.
+  (let* ((s "a\u20acb")
+         (r (reverse s)))
+    (should (equal r "b\u20aca"))
+    (should (multibyte-string-p r))
+    (should (= (string-bytes r) (string-bytes s)))
+    (should (equal s "a\u20acb")))
+  (should-not (multibyte-string-p (reverse (string-to-unibyte "abc"))))
+  (should-error (reverse 'foo) :type 'wrong-type-argument))
+
 (ert-deftest test-reverse-bool-vector ()
   (let ((A (make-bool-vector 10 nil)))
     (dotimes (i 5) (aset A i t))
@@ -112,6 +155,20 @@
     (nreverse (nreverse A))
     (should (equal A '(one two three)))))
 
+(ert-deftest test-nreverse-list-relinks ()
+  (let* ((A (list 1 2 3))
+         (B (nreverse A)))
+    (should (equal B '(3 2 1)))
+    ;; The old head is now the last cell.
+    (should (eq (cdr A) nil))
+    (should (eq (nthcdr 2 B) A)))
+  (should (equal (nreverse (list 'a)) '(a)))
+  (should (eq (nreverse nil) nil))
+  (let ((v (vector 1 2 3)))
+    (should (eq (nreverse v) v))
+    (should (equal v [3 2 1])))
+  (should-error (nreverse 'foo) :type 'wrong-type-argument))
+
 (ert-deftest test-nreverse-bool-vector ()
   (let ((A (make-bool-vector 10 nil)))
     (dotimes (i 5) (aset A i t))
@@ -119,6 +176,38 @@
     (should (equal [nil nil nil nil nil t t t t t] (vconcat A)))
     (should (equal [t t t t t nil nil nil nil nil] (vconcat (nreverse A))))))
 
+(ert-deftest test-append ()
+  (should (eq (append) nil))
+  (should (eq (append nil) nil))
+  (should (equal (append '(1) 2) '(1 . 2)))
+  (should (equal (append [1 2] "ab" nil) '(1 2 ?a ?b)))
+  (should (equal (append "a\u20ac" [x] '(y) '(z)) '(?a ?\u20ac x y z)))
+  (should (equal (append (bool-vector t nil) nil) '(t nil)))
+  (let ((tail (list 3)))
+    (should (eq (append nil "" [] tail) tail))
+    (should (eq (nthcdr 2 (append '(1 2) tail)) tail)))
+  (let ((l (list 1 2)))
+    (should-not (eq (append l nil) l))
+    (should (equal (append l nil) l)))
+  (should-error (append 1 nil) :type 'wrong-type-argument)
+  (should-error (append '(1 . 2) nil) :type 'wrong-type-argument))
+
+(ert-deftest test-nconc ()
+  (should (eq (nconc) nil))
+  (should (eq (nconc nil nil) nil))
+  (should (equal (nconc nil (list 1)) '(1)))
+  (let* ((a (list 1 2))
+         (b (list 3 4))
+         (r (nconc a nil b 5)))
+    (should (eq r a))
+    (should (equal r '(1 2 3 4 . 5)))
+    (should (eq (nthcdr 2 r) b)))
+  (should (eq (nconc nil 'foo) 'foo))
+  (should-error (nconc 1 (list 2)) :type 'wrong-type-argument)
+  (should (equal (condition-case err (nconc (list 1) 2 (list 3))
+                   (wrong-type-argument (cadr err)))
+                 'listp)))
+
 ;; Test handling of cyclic and dotted lists.
 
 (defun cyc1 (a)
@@ -151,3 +240,34 @@
   (should-error (reverse (cyc2 1 2)) :type 'circular-list)
   (should-error (reverse (dot1 1)) :type 'wrong-type-argument)
   (should-error (reverse (dot2 1 2)) :type 'wrong-type-argument))
+
+(ert-deftest fns-tests--compare-strings ()
+  (should (eq (compare-strings "foobar" nil nil "foobar" nil nil) t))
+  (should (eq (compare-strings "foobar" 0 3 "foo" nil nil) t))
+  (should (= (compare-strings "foo" nil nil "foobar" nil nil) -4))
+  (should (= (compare-strings "foobar" nil nil "foo" nil nil) 4))
+  (should (= (compare-strings "abc" nil nil "abd" nil nil) -3))
+  (should (eq (compare-strings "FooBar" nil nil "fOObAR" nil nil t) t))
+  (should (= (compare-strings "FooBar" nil nil "fOObAR" nil nil) -1))
+  (should (eq (compare-strings "xyzzy" 0 10 "xyzzy" nil nil) t))
+  (should (eq (compare-strings "\300bc" nil nil (string-to-multibyte "\300bc")
+                               nil nil)
+              t))
+  (should-error (compare-strings "xyzzy" 3 2 "xyzzy" nil nil)
+                :type 'args-out-of-range))
+
+(ert-deftest fns-tests--provide-featurep ()
+  (let ((features features))
+    (should-not (featurep 'fns-tests--feature))
+    (should (eq (provide 'fns-tests--feature '(sub1 sub2))
+                'fns-tests--feature))
+    (should (featurep 'fns-tests--feature))
+    (should (featurep 'fns-tests--feature 'sub2))
+    (should-not (featurep 'fns-tests--feature 'sub3))
+    ;; Providing a feature twice doesn't duplicate it.
+    (provide 'fns-tests--feature)
+    (should-not (memq 'fns-tests--feature
+                      (cdr (memq 'fns-tests--feature features))))
+    (should-error (provide 'fns-tests--feature 'sub1)
+                  :type 'wrong-type-argument))
+  (put 'fns-tests--feature 'subfeatures nil))
@@ -0,0 +1,35 @@
+;;; hashtable-tests.el --- tests for hashtable.rs functions -*- lexical-binding: t -*-
+
+;;; Code:
+
+(require 'ert)
+
+(ert-deftest hashtable-tests--sxhash-equal ()
+  (should (= (sxhash-equal (list 1 "two" [3.0 four]))
+             (sxhash-equal (list 1 "two" [3.0 four]))))
+  (should (= (sxhash-equal "string") (sxhash-equal (copy-sequence "string"))))
+  (should (integerp (sxhash-equal (let ((l (list 1 2))) (nconc l l))))))
+
+(ert-deftest hashtable-tests--sxhash-eq ()
+  (let ((cons (list 1 2)))
+    (should (= (sxhash-eq cons) (sxhash-eq cons))))
+  (should (= (sxhash-eq 'symbol) (sxhash-eq 'symbol))))
+
+(ert-deftest hashtable-tests--sxhash-eql ()
+  (should (= (sxhash-eql 1.5) (sxhash-eql (/ 3.0 2))))
+  (should (= (sxhash-eql 42) (sxhash-eq 42))))
+
+(ert-deftest hashtable-tests--define-hash-table-test ()
+  (define-hash-table-test 'hashtable-tests--case-fold
+    (lambda (a b) (string= (downcase a) (downcase b)))
+    (lambda (key) (sxhash-equal (downcase key))))
+  (should (= (length (get 'hashtable-tests--case-fold 'hash-table-test)) 2))
+  (let ((table (make-hash-table :test 'hashtable-tests--case-fold)))
+    (puthash "Key" 'value table)
+    (should (eq (gethash "KEY" table) 'value))
+    (should (eq (gethash "key" table) 'value))
+    (should-not (gethash "other" table))
+    (should (eq (hash-table-test table) 'hashtable-tests--case-fold))))
+
+(provide 'hashtable-tests)
+;;; hashtable-tests.el ends here
@@ -227,6 +227,19 @@
   (should-not (keymapp '(test-map)))
   (should-not (keymapp nil)))
 
+(ert-deftest keymap-tests--keymapp-constructors ()
+  (should (keymapp (make-sparse-keymap)))
+  (should (keymapp (make-sparse-keymap "prompt")))
+  (should (equal (make-sparse-keymap) '(keymap)))
+  (should (equal (make-sparse-keymap "prompt") '(keymap "prompt")))
+  (should (keymapp (make-keymap "prompt")))
+  (should (char-table-p (cadr (make-keymap))))
+  ;; A symbol whose function definition is a keymap is a keymap too.
+  (fset 'keymap-tests--prefix (make-sparse-keymap))
+  (should (keymapp 'keymap-tests--prefix))
+  (fmakunbound 'keymap-tests--prefix)
+  (should-not (keymapp 'keymap-tests--prefix)))
+
 (ert-deftest keymap-tests--use-local-map ()
   (let ((sample-keymap '(keymap
                          (3 keymap
@@ -253,6 +266,131 @@
     (should (equal (current-global-map) '(keymap (3 keymap (26 . emacs-version)))))
     (use-global-map backup-keymap)))
 
+(ert-deftest keymap-tests--command-remapping ()
+  (let ((map (make-sparse-keymap)))
+    (define-key map [remap kill-line] 'kill-whole-line)
+    (should (eq (command-remapping 'kill-line nil (list map))
+                'kill-whole-line))
+    (should-not (command-remapping 'kill-word nil (list map)))
+    (should-not (command-remapping "kill-line" nil (list map)))
+    (with-temp-buffer
+      (use-local-map map)
+      (should (eq (command-remapping 'kill-line) 'kill-whole-line))
+      (should (eq (key-binding [remap kill-line]) 'kill-whole-line)))))
+
+(ert-deftest keymap-tests--define-key ()
+  (let ((map (make-sparse-keymap)))
+    (define-key map (kbd "C-c C-z") 'emacs-version)
+    (should (eq (lookup-key map (kbd "C-c C-z")) 'emacs-version))
+    (should (keymapp (lookup-key map (kbd "C-c"))))
+    (should (= (lookup-key map (kbd "C-c C-z C-a")) 2))
+    (should-error (define-key map (kbd "C-c C-z C-a") 'ignore))
+    (should-not (define-key map "" 'ignore))))
+
+(ert-deftest keymap-tests--define-key-remove ()
+  (let ((parent (make-sparse-keymap))
+        (map (make-sparse-keymap)))
+    (set-keymap-parent map parent)
+    (define-key parent "a" 'parent-command)
+    (define-key map "a" 'child-command)
+    (should (eq (lookup-key map "a") 'child-command))
+    (define-key map "a" nil)
+    (should-not (lookup-key map "a"))
+    (define-key map "a" nil t)
+    (should (eq (lookup-key map "a") 'parent-command))))
+
+(ert-deftest keymap-tests--lookup-key ()
+  (let ((map '(keymap (3 keymap (26 . emacs-version)) (24 . ignore))))
+    (should (eq (lookup-key map [24]) 'ignore))
+    (should (eq (lookup-key map "\C-c\C-z") 'emacs-version))
+    (should (equal (lookup-key map [3]) '(keymap (26 . emacs-version))))
+    (should (= (lookup-key map [24 1]) 1))
+    (should (= (lookup-key map [3 26 1]) 2))
+    (should-not (lookup-key map [1]))
+    (should-not (lookup-key map [3 1]))
+    (should (eq (lookup-key map []) map))
+    (should-error (lookup-key map 'a) :type 'wrong-type-argument)))
+
+(ert-deftest keymap-tests--lookup-key-full-keymap ()
+  (let ((map (make-keymap)))
+    (define-key map "a" 'self-insert-command)
+    (should (char-table-p (cadr map)))
+    (should (eq (lookup-key map "a") 'self-insert-command))
+    (should-not (lookup-key map "b"))))
+
+(ert-deftest keymap-tests--lookup-key-parent-and-default ()
+  (let ((parent (make-sparse-keymap))
+        (map (make-sparse-keymap)))
+    (set-keymap-parent map parent)
+    (define-key parent "a" 'parent-command)
+    (define-key map [t] 'default-command)
+    (should (eq (lookup-key map "a") 'parent-command))
+    (should-not (lookup-key map "b"))
+    (should (eq (lookup-key map "b" t) 'default-command))))
+
+(ert-deftest keymap-tests--single-key-description ()
+  (should (equal (single-key-description ?\C-c) "C-c"))
+  (should (equal (single-key-description ?\M-x) "M-x"))
+  (should (equal (single-key-description ?\C-\M-\S-a) "C-M-S-a"))
+  (should (equal (single-key-description ?\r) "RET"))
+  (should (equal (single-key-description ?\t) "TAB"))
+  (should (equal (single-key-description ?\M-\t) "C-M-i"))
+  (should (equal (single-key-description ?\e) "ESC"))
+  (should (equal (single-key-description ?\s) "SPC"))
+  (should (equal (single-key-description ?\d) "DEL"))
+  (should (equal (single-key-description ?é) "é"))
+  (should (equal (single-key-description 'f1) "<f1>"))
+  (should (equal (single-key-description 'C-f1 t) "C-f1"))
+  (should (equal (single-key-description '(mouse-1 (nil))) "<mouse-1>"))
+  (should (equal (single-key-description '(?a . ?z)) "a..z"))
+  (should (equal (single-key-description "buffer") "buffer"))
+  (should-error (single-key-description 1.0)))
+
+(ert-deftest keymap-tests--key-description ()
+  (should (equal (key-description [?\C-x ?l]) "C-x l"))
+  (should (equal (key-description "\C-c\C-x") "C-c C-x"))
+  (should (equal (key-description [f1 ?a]) "<f1> a"))
+  (should (equal (key-description '(?a ?b)) "a b"))
+  (should (equal (key-description [?b] [?\C-x]) "C-x b"))
+  (should (equal (key-description [27 ?x]) "M-x"))
+  (should (equal (key-description [27 f1]) "ESC <f1>"))
+  (should (equal (key-description [?a 27]) "a ESC"))
+  (should (equal (key-description "\M-x") "M-x"))
+  (should (equal (key-description []) ""))
+  (should (equal (key-description nil) ""))
+  (should-error (key-description 'a) :type 'wrong-type-argument))
+
+(ert-deftest keymap-tests--key-valid-p ()
+  (should (key-valid-p "f"))
+  (should (key-valid-p "C-c"))
+  (should (key-valid-p "C-x C-f"))
+  (should (key-valid-p "S o m"))
+  (should (key-valid-p "<f1>"))
+  (should (key-valid-p "H-<left>"))
+  (should (key-valid-p "C-M-<return>"))
+  (should (key-valid-p "A-C-H-M-S-s-a"))
+  (should (key-valid-p "C--"))
+  (should (key-valid-p "é"))
+  (dolist (name '("NUL" "RET" "LFD" "TAB" "ESC" "SPC" "DEL"))
+    (should (key-valid-p name))
+    (should (key-valid-p (concat "M-" name))))
+  (should-not (key-valid-p ""))
+  (should-not (key-valid-p "C-"))
+  (should-not (key-valid-p "C-x C-"))
+  (should-not (key-valid-p "M-C-a"))
+  (should-not (key-valid-p "<M-C-down>"))
+  (should-not (key-valid-p "<>"))
+  (should-not (key-valid-p "<f 1>"))
+  (should-not (key-valid-p "ab"))
+  (should-not (key-valid-p "tab"))
+  (should-not (key-valid-p "C-x  C-f"))
+  (should-not (key-valid-p " C-x"))
+  (should-not (key-valid-p "C-x "))
+  (should-not (key-valid-p "\C-a"))
+  (should-not (key-valid-p "\M-a"))
+  (should-not (key-valid-p [?a]))
+  (should-not (key-valid-p nil)))
+
 (provide 'rust-keymap-tests)
 
 ;;; keymap-tests.el ends here
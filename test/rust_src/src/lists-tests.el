@@ -164,5 +164,174 @@
     (should-error (lax-plist-put d1 3 3) :type 'wrong-type-argument)
     (should-error (lax-plist-put d2 3 3) :type 'wrong-type-argument)))
 
+(defvar lists-tests--add-to-list-var nil)
+
+(ert-deftest lists-tests--add-to-list ()
+  ;; Go through `funcall' so the compiler macro doesn't open-code the call.
+  (setq lists-tests--add-to-list-var (list 'b))
+  (should (equal (funcall #'add-to-list 'lists-tests--add-to-list-var 'a)
+                 '(a b)))
+  (should (equal (funcall #'add-to-list 'lists-tests--add-to-list-var 'c t)
+                 '(a b c)))
+  (should (equal lists-tests--add-to-list-var '(a b c)))
+  ;; Nothing happens when the element is already present.
+  (let ((old lists-tests--add-to-list-var))
+    (should (eq (funcall #'add-to-list 'lists-tests--add-to-list-var 'b) old))
+    (should (eq (funcall #'add-to-list 'lists-tests--add-to-list-var 'c t) old)))
+  (setq lists-tests--add-to-list-var (list "x"))
+  (should (equal (funcall #'add-to-list 'lists-tests--add-to-list-var "x")
+                 '("x")))
+  (should (equal (funcall #'add-to-list 'lists-tests--add-to-list-var
+                          (copy-sequence "x") nil #'eq)
+                 '("x" "x")))
+  (should (equal (funcall #'add-to-list 'lists-tests--add-to-list-var "X" nil
+                          (lambda (a b) (string= (downcase a) (downcase b))))
+                 '("x" "x")))
+  (setq lists-tests--add-to-list-var 'not-a-list)
+  (should-error (funcall #'add-to-list 'lists-tests--add-to-list-var 'a)
+                :type 'wrong-type-argument))
+
+(ert-deftest lists-tests--last ()
+  (should (equal (last '(1 2 3)) '(3)))
+  (should (equal (last '(1 2 3) 2) '(2 3)))
+  (should (equal (last '(1 2 3) 5) '(1 2 3)))
+  (should (eq (last '(1 2 3) 0) nil))
+  (should (eq (last '(1 2 3) -1) nil))
+  (should (eq (last nil) nil))
+  (should (equal (last '(1 2 . 3)) '(2 . 3)))
+  (let ((l (list 1 2)))
+    (setcdr (cdr l) l)
+    (should-error (last l) :type 'circular-list)))
+
+(ert-deftest lists-tests--number-sequence ()
+  (should (equal (number-sequence 1 5) '(1 2 3 4 5)))
+  (should (equal (number-sequence 5 1 -2) '(5 3 1)))
+  (should (equal (number-sequence 1 6 2) '(1 3 5)))
+  (should (equal (number-sequence 0 1 0.5) '(0 0.5 1.0)))
+  (should (equal (number-sequence 3) '(3)))
+  (should (equal (number-sequence 3 3.0) '(3)))
+  (should (eq (number-sequence 5 1) nil))
+  (should (eq (number-sequence 1 5 -1) nil))
+  (should-error (number-sequence 1 5 0))
+  (should (equal (number-sequence (- most-positive-fixnum 1)
+                                  most-positive-fixnum)
+                 (list (- most-positive-fixnum 1) most-positive-fixnum))))
+
+(ert-deftest lists-tests--assq-delete-all ()
+  (let ((alist (list (cons 'a 1) (cons 'b 2) 'stray (cons 'a 3) (cons 'c 1))))
+    (should (equal (assq-delete-all 'a alist) '((b . 2) stray (c . 1)))))
+  (let ((alist (list (cons 'a 1) 'a (cons 'b 2))))
+    (should (equal (assq-delete-all 'a alist) '(a (b . 2)))))
+  (should-not (assq-delete-all 'a (list (cons 'a 1) (cons 'a 2))))
+  (should-not (assq-delete-all 'a nil))
+  (should-error (assq-delete-all 'a '((a . 1) . b)) :type 'wrong-type-argument))
+
+(ert-deftest lists-tests--rassq-delete-all ()
+  (let ((alist (list (cons 'a 1) (cons 'b 2) 'stray (cons 'c 1) (cons 'd 3))))
+    (should (equal (rassq-delete-all 1 alist) '((b . 2) stray (d . 3)))))
+  (let ((alist (list 'x (cons 'a 'x))))
+    (should (equal (rassq-delete-all 'x alist) '(x))))
+  (should-not (rassq-delete-all 1 nil)))
+
+(ert-deftest lists-tests--flatten-tree ()
+  (should (equal (flatten-tree '(1 (2 (3 4)) 5)) '(1 2 3 4 5)))
+  (should (equal (flatten-tree '(1 . 2)) '(1 2)))
+  (should (equal (flatten-tree '(1 (2 . 3) nil (4 5 (6)) 7))
+                 '(1 2 3 4 5 6 7)))
+  (should (equal (flatten-tree 'a) '(a)))
+  (should-not (flatten-tree nil))
+  (should-not (flatten-tree '(nil (nil))))
+  (let ((deep nil))
+    (dotimes (i 10000)
+      (setq deep (list deep i)))
+    (should (= (length (flatten-tree deep)) 10000))))
+
+(ert-deftest lists-tests--length-comparisons ()
+  (let ((long (number-sequence 1 100000)))
+    (should (length< long 100001))
+    (should-not (length< long 100000))
+    (should (length> long 99999))
+    (should-not (length> long 100000))
+    (should (length= long 100000))
+    (should-not (length= long 10)))
+  (should (length= nil 0))
+  (should-not (length= '(1) -1))
+  (should (length> '(1 2 . 3) 1))
+  (should (length= "€uro" 4))
+  (should (length< [1 2] 3))
+  (should (length> [1 2] -1))
+  (let ((circ (list 1 2)))
+    (setcdr (cdr circ) circ)
+    (should-not (length< circ 3))
+    (should (length> circ 100))
+    (should-not (length= circ 50))
+    (should-error (length< circ most-positive-fixnum) :type 'circular-list))
+  (should-error (length< '(1 2) 'a) :type 'wrong-type-argument))
+
+(ert-deftest lists-tests--take ()
+  (should (equal (take 2 '(a b c)) '(a b)))
+  (should (equal (take 5 '(a b)) '(a b)))
+  (should-not (take 0 '(a b)))
+  (should-not (take -1 '(a b)))
+  (should-not (take 3 nil))
+  (let* ((list (list 'a 'b 'c))
+         (copy (take 3 list)))
+    (should (equal copy list))
+    (should-not (eq copy list)))
+  (let ((circ (list 1 2)))
+    (setcdr (cdr circ) circ)
+    (should (equal (take 5 circ) '(1 2 1 2 1))))
+  (should (equal (take 1 '(a . b)) '(a)))
+  (should-error (take 2 '(a . b)) :type 'wrong-type-argument))
+
+(ert-deftest lists-tests--ntake ()
+  (let ((list (list 'a 'b 'c)))
+    (should (eq (ntake 2 list) list))
+    (should (equal list '(a b))))
+  (let ((list (list 'a 'b)))
+    (should (eq (ntake 5 list) list))
+    (should (equal list '(a b))))
+  (should-not (ntake 0 (list 'a)))
+  (should-not (ntake 2 nil))
+  (should-error (ntake 1 'a) :type 'wrong-type-argument))
+
+(ert-deftest lists-tests--cxr ()
+  (should (= (caddr '(1 2 3)) 3))
+  (should-not (cadr '(1)))
+  (should (eq (caar '((a) b)) 'a))
+  (should (eq (cdar '((a . b))) 'b))
+  (should (equal (cddr '(1 2 3)) '(3)))
+  (should (eq (caadr '(1 (2) 3)) 2))
+  (should (eq (cadar '((1 2))) 2))
+  (should (equal (cdadr '(1 (2 3))) '(3)))
+  (should (equal (cddar '((1 2 3))) '(3)))
+  (should-not (cdddr '(1 2 3)))
+  (should-not (caaar nil))
+  (should-error (cadr '(1 . 2)) :type 'wrong-type-argument)
+  (should-error (caar '(1)) :type 'wrong-type-argument))
+
+(ert-deftest lists-tests--copy-tree ()
+  (let* ((orig (list 1 (list 2 (list 3)) (cons 4 5)))
+         (copy (copy-tree orig)))
+    (should (equal copy orig))
+    (should-not (eq copy orig))
+    (should-not (eq (cadr copy) (cadr orig)))
+    (setcar (cadr (cadr copy)) 'changed)
+    (setcdr (nth 2 copy) 'changed)
+    (should (equal orig '(1 (2 (3)) (4 . 5)))))
+  (let* ((vec (vector (list 1 2)))
+         (orig (list vec))
+         (shared (copy-tree orig))
+         (deep (copy-tree orig t)))
+    (should (eq (car shared) vec))
+    (should (equal (car deep) vec))
+    (should-not (eq (car deep) vec))
+    (setcar (aref (car deep) 0) 'changed)
+    (should (equal vec [(1 2)])))
+  (should (equal (copy-tree '(1 . [2])) '(1 . [2])))
+  (should (eq (copy-tree 'a) 'a))
+  (let ((long (number-sequence 1 100000)))
+    (should (equal (copy-tree long) long))))
+
 (provide 'rust-lists-tests)
 ;;; lists-tests.el ends here
@@ -224,4 +224,19 @@ literals (Bug#20852)."
     (should (eq (unintern "b" obarray) t))
     (should (equal obarray [0]))))
 
+(ert-deftest unintern-intern-soft-tests ()
+  (let* ((ob (obarray-make))
+         (sym (intern "lread-tests--gone" ob)))
+    (should (eq (intern-soft "lread-tests--gone" ob) sym))
+    (should (eq (unintern "lread-tests--gone" ob) t))
+    (should-not (intern-soft "lread-tests--gone" ob))
+    ;; The symbol itself survives, it is only unlinked.
+    (should (equal (symbol-name sym) "lread-tests--gone"))
+    (should-not (unintern "lread-tests--gone" ob)))
+  ;; Passing a symbol only removes that exact symbol.
+  (let ((ob (obarray-make)))
+    (intern "lread-tests--other" ob)
+    (should-not (unintern (make-symbol "lread-tests--other") ob))
+    (should (intern-soft "lread-tests--other" ob))))
+
 ;;; lread-tests.el ends here
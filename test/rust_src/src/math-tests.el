@@ -0,0 +1,77 @@
+;;; math-tests.el --- tests for math.rs functions -*- lexical-binding: t -*-
+
+;;; Code:
+
+(require 'ert)
+
+(ert-deftest math-tests--less ()
+  (should (< 1 2 3))
+  (should-not (< 1 3 2))
+  (should (< 1))
+  (should (< 1 1.5 2))
+  (should-not (< 2 2.0)))
+
+(ert-deftest math-tests--greater ()
+  (should (> 3 2 1))
+  (should-not (> 3 1 2))
+  (should (>= 3 3.0 2))
+  (should (<= 1 1.0 2)))
+
+(ert-deftest math-tests--equal ()
+  (should (= 1 1.0))
+  (should (= 1 1.0 1))
+  (should-not (= 1 1.0 2))
+  (should-not (= 0.0e+NaN 0.0e+NaN))
+  (should (/= 1 2))
+  (should-not (/= 1 1.0))
+  (should (/= 0.0e+NaN 0.0e+NaN)))
+
+(ert-deftest math-tests--compare-markers ()
+  (with-temp-buffer
+    (insert "abc")
+    (should (= (point-marker) 4))
+    (should (< 1 (point-marker) 5))))
+
+(ert-deftest math-tests--compare-errors ()
+  (should-error (< 1 'a) :type 'wrong-type-argument)
+  (should-error (= "1" 1) :type 'wrong-type-argument)
+  (should-error (<) :type 'wrong-number-of-arguments))
+
+(ert-deftest math-tests--bitwise ()
+  (should (= (logand 12 10) 8))
+  (should (= (logand) -1))
+  (should (= (logior 1 2 4) 7))
+  (should (= (logior) 0))
+  (should (= (logxor 5 3) 6))
+  (should (= (logxor) 0))
+  (should (= (lognot 0) -1))
+  (should (= (lognot 5) -6))
+  (with-temp-buffer
+    (insert "abcdef")
+    (should (= (logand (point-marker) 3) 3)))
+  (should-error (logand 1 1.0) :type 'wrong-type-argument)
+  (should-error (logior 'a) :type 'wrong-type-argument)
+  (should-error (lognot 1.0) :type 'wrong-type-argument))
+
+(ert-deftest math-tests--ash ()
+  (should (= (ash 1 4) 16))
+  (should (= (ash -3 2) -12))
+  (should (= (ash 16 -2) 4))
+  (should (= (ash -16 -2) -4))
+  (should (= (ash -1 -1) -1))
+  (should (= (ash 5 -100) 0))
+  (should (= (ash -5 -100) -1))
+  (should (= (ash most-positive-fixnum 1) -2))
+  (should-error (ash 1.0 1) :type 'wrong-type-argument))
+
+(ert-deftest math-tests--lsh ()
+  (should (= (lsh 1 4) 16))
+  (should (= (lsh -3 2) -12))
+  (should (= (lsh 16 -2) 4))
+  (should (= (lsh -1 -1) most-positive-fixnum))
+  (should (> (lsh -16 -2) 0))
+  (should (= (lsh -5 -100) 0))
+  (should-error (lsh 1 'a) :type 'wrong-type-argument))
+
+(provide 'math-tests)
+;;; math-tests.el ends here
@@ -405,4 +405,36 @@
       (insert "test")
       (should (string= (minibuffer-contents) "test")))))
 
+(defvar minibuf-tests--history nil)
+
+(ert-deftest minibuf-tests--add-to-history-dedup ()
+  (let ((history-delete-duplicates t))
+    (setq minibuf-tests--history (list "b" "a" "c"))
+    (should (equal (add-to-history 'minibuf-tests--history "a")
+                   '("a" "b" "c")))
+    (should (equal minibuf-tests--history '("a" "b" "c")))
+    ;; Empty strings and repeats of the head are ignored...
+    (should (equal (add-to-history 'minibuf-tests--history "")
+                   '("a" "b" "c")))
+    (should (equal (add-to-history 'minibuf-tests--history "a")
+                   '("a" "b" "c")))
+    ;; ...unless KEEP-ALL is given.
+    (should (equal (add-to-history 'minibuf-tests--history "" nil t)
+                   '("" "a" "b" "c")))))
+
+(ert-deftest minibuf-tests--add-to-history-truncate ()
+  (let ((history-delete-duplicates nil)
+        (history-length 3))
+    (setq minibuf-tests--history (list "c" "b" "a"))
+    (should (equal (add-to-history 'minibuf-tests--history "d")
+                   '("d" "c" "b")))
+    (should (equal (add-to-history 'minibuf-tests--history "e" 2)
+                   '("e" "d")))
+    (should-not (add-to-history 'minibuf-tests--history "f" 0))
+    (put 'minibuf-tests--history 'history-length 1)
+    (unwind-protect
+        (should (equal (add-to-history 'minibuf-tests--history "g")
+                       '("g")))
+      (put 'minibuf-tests--history 'history-length nil))))
+
 ;;; minibuf-tests.el ends here
@@ -0,0 +1,73 @@
+;;; multibyte-tests.el --- tests for multibyte.rs functions -*- lexical-binding: t -*-
+
+;;; Code:
+
+(require 'ert)
+
+(ert-deftest multibyte-tests--string-search ()
+  (should (equal (string-search "world" "hello world") 6))
+  (should (equal (string-search "o" "hello world" 5) 7))
+  (should-not (string-search "xyz" "hello world"))
+  (should-not (string-search "o" "hello world" 8))
+  (should (equal (string-search "" "abc") 0))
+  (should (equal (string-search "" "abc" 2) 2))
+  (should (equal (string-search "" "abc" 3) 3))
+  (should-error (string-search "a" "abc" 4) :type 'args-out-of-range)
+  (should-error (string-search "a" "abc" -1) :type 'args-out-of-range)
+  (should-error (string-search 'a "abc") :type 'wrong-type-argument))
+
+(ert-deftest multibyte-tests--string-search-multibyte ()
+  ;; Byte and character offsets differ after the first two characters.
+  (should (equal (string-search "b" "aé€b") 3))
+  (should (equal (string-search "€" "aé€b€" 3) 4))
+  (should-not (string-search "ö" "aé€b"))
+  ;; A unibyte needle can match a multibyte haystack, and vice versa.
+  (should (equal (string-search (string-to-unibyte "b") "aé€b") 3))
+  (should (equal (string-search "\351" "a\351") 1))
+  (should (equal (string-search (string-to-multibyte "\351") "a\351") 1))
+  (should-not (string-search "é" "a\351")))
+
+(ert-deftest multibyte-tests--string-replace ()
+  (should (equal (string-replace "foo" "bar" "foo, foofoo") "bar, barbar"))
+  (should (equal (string-replace "aa" "b" "aaa") "ba"))
+  (should (equal (string-replace "x" "y" "abc") "abc"))
+  (should (equal (string-replace "b" "" "abcb") "ac"))
+  (should-not (multibyte-string-p (string-replace "a" "b" "abc")))
+  (let ((result (string-replace "b" "€é" "abcb")))
+    (should (equal result "a€éc€é"))
+    (should (multibyte-string-p result))
+    (should (= (length result) 6)))
+  (let ((result (string-replace "€" "e" "a€b€")))
+    (should (equal result "aebe"))
+    (should (= (length result) 4)))
+  (should (equal (string-replace "b" "é" (string-to-unibyte "abc")) "aéc"))
+  (should-error (string-replace "" "x" "abc") :type 'wrong-length-argument))
+
+(ert-deftest multibyte-tests--string-prefix-p ()
+  (should (string-prefix-p "foo" "foobar"))
+  (should (string-prefix-p "" "foobar"))
+  (should-not (string-prefix-p "bar" "foobar"))
+  (should-not (string-prefix-p "FOO" "foobar"))
+  (should (string-prefix-p "FOO" "foobar" t))
+  (should (string-prefix-p "ÉCO" "école" t))
+  (should-not (string-prefix-p "foobarbaz" "foobar"))
+  ;; A unibyte prefix can match the ASCII prefix of a multibyte string.
+  (should (string-prefix-p (string-to-unibyte "ab") "abé"))
+  (should (string-prefix-p "a" (string-to-multibyte "abc")))
+  (should-not (string-prefix-p "\351" "é"))
+  (should-error (string-prefix-p 'a "abc") :type 'wrong-type-argument))
+
+(ert-deftest multibyte-tests--string-suffix-p ()
+  (should (string-suffix-p "bar" "foobar"))
+  (should (string-suffix-p "" "foobar"))
+  (should-not (string-suffix-p "foo" "foobar"))
+  (should (string-suffix-p "BAR" "foobar" t))
+  (should (string-suffix-p "le" "école"))
+  (should (string-suffix-p "COLE" "école" t))
+  (should-not (string-suffix-p "xfoobar" "foobar"))
+  (should-not (string-suffix-p "long suffix" "fix"))
+  (should (string-suffix-p (string-to-unibyte "ar") "éfoobar"))
+  (should-error (string-suffix-p "a" nil) :type 'wrong-type-argument))
+
+(provide 'multibyte-tests)
+;;; multibyte-tests.el ends here
@@ -21,5 +21,16 @@
 (ert-deftest test-random-range ()
   (should (< 0 (random 10000) 10000)))
 
+(ert-deftest test-random-bounded ()
+  (dotimes (_ 1000)
+    (let ((value (random 3)))
+      (should (<= 0 value 2)))))
+
+(ert-deftest test-random-seed-sequence ()
+  (let ((draw (lambda ()
+                (random "test-seed")
+                (mapcar (lambda (_) (random 1000)) (make-list 10 nil)))))
+    (should (equal (funcall draw) (funcall draw)))))
+
 (provide 'numbers-tests)
 ;;; numbers-tests.el ends here
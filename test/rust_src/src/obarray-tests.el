@@ -31,3 +31,36 @@
   (should-error
    (mapatoms (lambda (s)) 123)
    :type 'wrong-type-argument))
+
+(ert-deftest obarray-tests-obarray-make ()
+  (should (obarrayp (obarray-make)))
+  (should (= (length (obarray-make)) obarray-default-size))
+  (let ((obarray-default-size 13))
+    (should (= (length (obarray-make)) 13)))
+  (should (= (length (obarray-make 7)) 7))
+  (should-error (obarray-make 0) :type 'args-out-of-range)
+  (should-error (obarray-make -1) :type 'wrong-type-argument)
+  (should-error (obarray-make 'foo) :type 'wrong-type-argument))
+
+(ert-deftest obarray-tests-obarrayp ()
+  (should (obarrayp (make-vector 3 0)))
+  (should-not (obarrayp []))
+  (should-not (obarrayp "foo"))
+  (should-not (obarrayp nil)))
+
+(ert-deftest obarray-tests-obarray-make-isolated ()
+  ;; Interning into a fresh obarray must not touch the global one.
+  (let ((ob (obarray-make)))
+    (should (intern "obarray-tests--fresh-symbol" ob))
+    (should (intern-soft "obarray-tests--fresh-symbol" ob))
+    (should-not (intern-soft "obarray-tests--fresh-symbol"))))
+
+(ert-deftest obarray-tests-mapatoms-custom-obarray ()
+  (let ((ob (obarray-make 3))
+        (names '("alpha" "beta" "gamma" "delta" "epsilon"))
+        (seen nil))
+    (dolist (name names)
+      (intern name ob))
+    (should-not (mapatoms (lambda (s) (push (symbol-name s) seen)) ob))
+    (should (= (length seen) (length names)))
+    (should (equal (sort seen #'string<) (sort (copy-sequence names) #'string<)))))
@@ -37,4 +37,29 @@
   ;; Test single unicode character with multiple code-points
   (should (eq (string-width "é") 1)))
 
+(ert-deftest string-distance ()
+  (should (equal 0 (string-distance "abcd" "abcd")))
+  (should (equal 4 (string-distance "abcd" "efgh")))
+  (should (equal 4 (string-distance "abcd" "abcdefgh")))
+  (should (equal 1 (string-distance "ab" "a0")))
+  (should (equal 3 (string-distance "kitten" "sitting")))
+  ;; Multibyte strings compared by byte
+  (should (equal 6 (string-distance "ab" "ab我她" t)))
+  (should (equal 3 (string-distance "ab" "a我b" t)))
+  (should (equal 3 (string-distance "我" "她" t)))
+  ;; Multibyte strings compared by character
+  (should (equal 2 (string-distance "ab" "ab我她")))
+  (should (equal 1 (string-distance "ab" "a我b")))
+  (should (equal 1 (string-distance "我" "她"))))
+
+(ert-deftest string-equal-ignore-case ()
+  (should (string-equal-ignore-case "abc" "ABC"))
+  (should (string-equal-ignore-case "ÉCOLE" "école"))
+  (should (string-equal-ignore-case "Straße" "STRAßE"))
+  (should (string-equal-ignore-case 'Foo "fOO"))
+  (should (string-equal-ignore-case (string-to-unibyte "abc") "ABC"))
+  (should-not (string-equal-ignore-case "abc" "abcd"))
+  (should-not (string-equal-ignore-case "abcd" "ABC"))
+  (should-not (string-equal-ignore-case "é" "e")))
+
 ;;; strings-tests ends here
@@ -0,0 +1,30 @@
+;;; symbols-tests.el --- tests for symbols.rs functions -*- lexical-binding: t -*-
+
+;;; Code:
+
+(require 'ert)
+
+(ert-deftest symbols-tests--position-symbol ()
+  (let ((ls (position-symbol 'foo 42)))
+    (should (symbol-with-pos-p ls))
+    (should (eq (bare-symbol ls) 'foo))
+    (should (= (symbol-with-pos-pos ls) 42))
+    (should (eq (type-of ls) 'symbol-with-pos))
+    (should (equal (prin1-to-string ls) "#<symbol foo at 42>"))
+    ;; Repositioning takes the bare symbol and the position from
+    ;; other positioned symbols.
+    (let ((moved (position-symbol ls (position-symbol 'bar 7))))
+      (should (eq (bare-symbol moved) 'foo))
+      (should (= (symbol-with-pos-pos moved) 7)))))
+
+(ert-deftest symbols-tests--bare-symbol ()
+  (should (eq (bare-symbol 'foo) 'foo))
+  (should (eq (bare-symbol nil) nil))
+  (should-not (symbol-with-pos-p 'foo))
+  (should-error (bare-symbol "foo") :type 'wrong-type-argument)
+  (should-error (symbol-with-pos-pos 'foo) :type 'wrong-type-argument)
+  (should-error (position-symbol "foo" 1) :type 'wrong-type-argument)
+  (should-error (position-symbol 'foo 'bar) :type 'wrong-type-argument))
+
+(provide 'symbols-tests)
+;;; symbols-tests.el ends here
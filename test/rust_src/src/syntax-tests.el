@@ -13,6 +13,22 @@
       (set-syntax-table st)
       (should (eq st (syntax-table))))))
 
+(ert-deftest test-set-syntax-table-copy ()
+  (with-temp-buffer
+    (let ((st (copy-syntax-table)))
+      (should (eq (char-table-parent st) (standard-syntax-table)))
+      (should (eq (char-syntax ?+) ?_))
+      (modify-syntax-entry ?+ "." st)
+      (should (eq (set-syntax-table st) st))
+      (should (eq (syntax-table) st))
+      (should (eq (char-syntax ?+) ?.))
+      ;; The standard table is left alone.
+      (with-syntax-table (standard-syntax-table)
+        (should (eq (char-syntax ?+) ?_)))))
+  (should-error (set-syntax-table (make-char-table 'foo))
+                :type 'wrong-type-argument)
+  (should-error (set-syntax-table 'foo) :type 'wrong-type-argument))
+
 (ert-deftest test-forward-word ()
   (let ((str "test forward-word"))
     (with-temp-buffer
@@ -33,3 +49,220 @@
     (should (eq (scan-sexps 0 3) nil))
     (insert "(+ 1")
     (should-error (scan-sexps 0 3))))
+
+(ert-deftest test-current-word ()
+  (with-temp-buffer
+    (emacs-lisp-mode)
+    (insert "foo-bar   baz")
+    ;; Point in the middle of a symbol.
+    (goto-char 3)
+    (should (equal (current-word) "foo-bar"))
+    (should (equal (current-word nil t) "foo"))
+    ;; Point at a word boundary.
+    (goto-char 8)
+    (should (equal (current-word) "foo-bar"))
+    (goto-char 11)
+    (should (equal (current-word) "baz"))
+    ;; Point between words.
+    (goto-char 9)
+    (should (equal (current-word) "foo-bar"))
+    (should (eq (current-word t) nil))
+    (should (eq (get-text-property 0 'face (current-word)) nil))))
+
+(ert-deftest test-current-word-syntax-property ()
+  (with-temp-buffer
+    (emacs-lisp-mode)
+    (setq-local parse-sexp-lookup-properties t)
+    (insert "foo-bar")
+    ;; Make the hyphen punctuation through a text property.
+    (put-text-property 4 5 'syntax-table (string-to-syntax "."))
+    (goto-char 2)
+    (should (equal (current-word) "foo"))
+    (should (= (point) 2))))
+
+(ert-deftest test-current-word-empty-line ()
+  (with-temp-buffer
+    (insert "   \n  foo")
+    (goto-char 2)
+    (should (eq (current-word) nil))
+    (goto-char (point-max))
+    (should (equal (current-word) "foo"))))
+
+(ert-deftest test-backward-word ()
+  (with-temp-buffer
+    (insert "one, two; three")
+    (should (backward-word))
+    (should (looking-at "three"))
+    (should (backward-word 2))
+    (should (looking-at "one"))
+    (should (eq (backward-word) nil))
+    (should (bobp))
+    ;; A negative count moves forward.
+    (should (backward-word -2))
+    (should (looking-back "two" nil))))
+
+(ert-deftest test-forward-word-punctuation-multibyte ()
+  (with-temp-buffer
+    (insert "héllo, wörld!")
+    (goto-char (point-min))
+    (should (forward-word))
+    (should (= (point) 6))
+    (should (forward-word))
+    (should (looking-back "wörld" nil))
+    (should (eq (forward-word) nil))
+    (should (eobp))))
+
+(ert-deftest test-scan-lists-nested ()
+  (with-temp-buffer
+    (emacs-lisp-mode)
+    (insert "(a (b (c)) d) e")
+    (should (eq (scan-lists 1 1 0) 14))
+    (should (eq (scan-lists 14 -1 0) 1))
+    ;; Positive depth stops once we have left that many lists.
+    (should (eq (scan-lists 8 1 1) 10))
+    (should (eq (scan-lists 8 1 2) 11))
+    (should (eq (scan-lists 14 1 0) nil))
+    (should (eq (scan-sexps 1 2) 16))
+    (should-error (scan-lists 2 2 0) :type 'scan-error)))
+
+(ert-deftest test-scan-lists-string-escape ()
+  (with-temp-buffer
+    (emacs-lisp-mode)
+    (insert "(foo \"a\\)b\" ?\\( bar)")
+    (should (eq (scan-lists 1 1 0) (point-max)))
+    (should (eq (scan-sexps (point-max) -1) 1))
+    (goto-char (point-max))
+    (insert " (")
+    (should-error (scan-lists (1- (point-max)) 1 0) :type 'scan-error)))
+
+(ert-deftest test-up-list ()
+  (with-temp-buffer
+    (emacs-lisp-mode)
+    (insert "(a (b (c) d) e)")
+    (goto-char 8)
+    (up-list)
+    (should (= (point) 10))
+    (up-list 2)
+    (should (= (point) (point-max)))
+    (goto-char 8)
+    (backward-up-list 2)
+    (should (= (point) 4))
+    (backward-up-list)
+    (should (bobp))
+    (should-error (backward-up-list) :type 'scan-error)))
+
+(ert-deftest test-up-list-strings ()
+  (with-temp-buffer
+    (emacs-lisp-mode)
+    (insert "(foo \"bar (baz) qux\")")
+    (goto-char 13)
+    ;; Without NO-SYNTAX-CROSSING, the paren inside the string counts.
+    (up-list)
+    (should (= (point) 16))
+    ;; With it, movement stays within the string and escapes it.
+    (goto-char 13)
+    (up-list 1 t t)
+    (should (= (point) 16))
+    (goto-char 18)
+    (up-list 1 t t)
+    (should (= (point) 21))
+    (goto-char 18)
+    (should-error (up-list 1 nil t) :type 'scan-error)))
+
+(ert-deftest test-parse-partial-sexp-list ()
+  (with-temp-buffer
+    (emacs-lisp-mode)
+    (insert "(a (b (c) d) e)")
+    (let ((state (parse-partial-sexp 1 9)))
+      (should (= (nth 0 state) 3))
+      (should (= (nth 1 state) 7))
+      (should (equal (nth 9 state) '(1 4 7)))
+      (should-not (nth 3 state))
+      (should-not (nth 8 state))
+      (should (= (point) 9)))
+    ;; Stop once we climb back out to depth 1.
+    (let ((state (parse-partial-sexp 8 (point-max) -1)))
+      (should (= (nth 0 state) -1))
+      (should (= (point) 10)))
+    ;; Resuming from a previous state gives the same answer.
+    (let ((state (parse-partial-sexp 6 9 nil nil (parse-partial-sexp 1 6))))
+      (should (= (nth 0 state) 3))
+      (should (= (nth 1 state) 7))
+      (should (equal (nth 9 state) '(1 4 7))))))
+
+(ert-deftest test-parse-partial-sexp-string ()
+  (with-temp-buffer
+    (emacs-lisp-mode)
+    (insert "(foo \"bar (baz\" qux)")
+    (let ((state (parse-partial-sexp 1 12)))
+      (should (eq (nth 3 state) ?\"))
+      (should (= (nth 8 state) 6))
+      (should (= (nth 0 state) 1)))
+    (let ((state (parse-partial-sexp 1 (point-max))))
+      (should-not (nth 3 state))
+      (should (= (nth 0 state) 0)))
+    (should-error (parse-partial-sexp 1 2 'foo) :type 'wrong-type-argument)))
+
+(ert-deftest test-skip-syntax ()
+  (with-temp-buffer
+    (emacs-lisp-mode)
+    (insert "   foo-bar  baz")
+    (goto-char (point-min))
+    (should (= (skip-syntax-forward " ") 3))
+    (should (= (point) 4))
+    (should (= (skip-syntax-forward "w") 3))
+    (should (= (skip-syntax-forward "w_") 4))
+    (should (= (point) 11))
+    (should (= (skip-syntax-forward "^w") 2))
+    (should (looking-at "baz"))
+    ;; LIM bounds the motion.
+    (should (= (skip-syntax-backward "^w" 12) -1))
+    (should (= (point) 12))
+    (goto-char (point-max))
+    (should (= (skip-syntax-backward "w" 14) -2))
+    (should (= (point) 14))
+    (should (= (skip-syntax-backward "w") -1))
+    (should (= (skip-syntax-backward " ") -2))
+    (should (= (point) 11))
+    (should (= (skip-syntax-forward "w") 0))))
+
+(ert-deftest test-modify-syntax-entry ()
+  (with-temp-buffer
+    (set-syntax-table (make-syntax-table))
+    (should (eq (char-syntax ?_) ?_))
+    (modify-syntax-entry ?_ "w")
+    (should (eq (char-syntax ?_) ?w))
+    (insert "foo_bar baz")
+    (goto-char (point-min))
+    (forward-word)
+    (should (looking-at " baz"))
+    ;; Ranges and explicit tables.
+    (let ((st (make-syntax-table)))
+      (modify-syntax-entry '(?0 . ?9) "." st)
+      (should (equal (aref st ?5) (string-to-syntax ".")))
+      (should (eq (char-syntax ?5) ?w)))
+    (modify-syntax-entry ?< "(>")
+    (should (equal (aref (syntax-table) ?<) (string-to-syntax "(>")))
+    (should-error (modify-syntax-entry "a" "w") :type 'wrong-type-argument)
+    (should-error (modify-syntax-entry '(?a . "z") "w")
+                  :type 'wrong-type-argument)
+    (should-error (modify-syntax-entry ?a "w" (make-char-table 'foo))
+                  :type 'wrong-type-argument)))
+
+(ert-deftest test-matching-paren ()
+  (with-temp-buffer
+    (should (eq (matching-paren ?\() ?\)))
+    (should (eq (matching-paren ?\]) ?\[))
+    (should-not (matching-paren ?a))
+    (should-error (matching-paren "(") :type 'wrong-type-argument)))
+
+(ert-deftest test-syntax-after ()
+  (with-temp-buffer
+    (insert "(a)")
+    (should (equal (syntax-after 1) (string-to-syntax "()")))
+    (should (equal (syntax-after 2) (string-to-syntax "w")))
+    (should-not (syntax-after 0))
+    (should-not (syntax-after 4))
+    (let ((parse-sexp-lookup-properties t))
+      (put-text-property 2 3 'syntax-table (string-to-syntax "."))
+      (should (equal (syntax-after 2) (string-to-syntax "."))))))
@@ -73,5 +73,89 @@
     (should (and (equal-including-properties (pop stack) string)
 		 (null stack)))))
 
+(ert-deftest textprop-tests-get-char-property-overlay ()
+  (with-temp-buffer
+    (insert "abcdef")
+    (put-text-property 2 5 'face 'bold)
+    (should (eq (get-char-property 3 'face) 'bold))
+    (let ((low (make-overlay 3 4))
+          (high (make-overlay 3 4)))
+      (overlay-put low 'face 'italic)
+      (should (eq (get-char-property 3 'face) 'italic))
+      (overlay-put high 'face 'underline)
+      (overlay-put high 'priority 10)
+      (should (eq (get-char-property 3 'face) 'underline))
+      (should (eq (get-text-property 3 'face) 'bold))
+      ;; Outside the overlays, the text property shows through.
+      (should (eq (get-char-property 2 'face) 'bold))
+      (should-not (get-char-property 5 'face)))))
+
+(ert-deftest textprop-tests-next-property-change ()
+  (let ((string (concat (propertize "ab" 'face 'bold)
+                        (propertize "cd" 'face 'italic 'help-echo "x")
+                        "ef")))
+    (should (= (next-property-change 0 string) 2))
+    (should (= (next-property-change 2 string) 4))
+    (should-not (next-property-change 4 string))
+    (should (= (next-property-change 0 string 1) 1))
+    (should (= (next-property-change 4 string 6) 6))
+    (should (= (next-property-change 4 string t) 6))
+    (should (= (next-single-property-change 0 'face string) 2))
+    (should (= (next-single-property-change 0 'help-echo string) 2))
+    (should (= (next-single-property-change 2 'help-echo string) 4))
+    (should-not (next-single-property-change 4 'face string))
+    (should (= (next-single-property-change 0 'face string 1) 1))
+    (should-error (next-property-change 7 string) :type 'args-out-of-range)
+    (should-error (next-single-property-change 0 'face 'foo)
+                  :type 'wrong-type-argument))
+  (with-temp-buffer
+    (insert (propertize "ab" 'face 'bold) "cd")
+    (should (= (next-property-change 1) 3))
+    (should (= (next-single-property-change 1 'face) 3))
+    (should-not (next-single-property-change 3 'face))
+    (should (= (next-single-property-change 3 'face nil 4) 4))))
+
+(ert-deftest textprop-tests-put-text-property ()
+  (with-temp-buffer
+    (insert "abcdef")
+    (should-not (put-text-property 2 4 'face 'bold))
+    (should (eq (get-text-property 2 'face) 'bold))
+    (should (eq (get-text-property 3 'face) 'bold))
+    (should-not (get-text-property 4 'face))
+    ;; Markers are accepted, and START and END may be given in any order.
+    (put-text-property (copy-marker 6) (copy-marker 5) 'face 'italic)
+    (should (eq (get-text-property 5 'face) 'italic))
+    (put-text-property 1 2 'face 'underline (current-buffer))
+    (should (eq (get-text-property 1 'face) 'underline))
+    (should-error (put-text-property 1 10 'face 'bold)
+                  :type 'args-out-of-range))
+  (let ((string (copy-sequence "abc")))
+    (put-text-property 1 3 'face 'bold string)
+    (should-not (get-text-property 0 'face string))
+    (should (eq (get-text-property 2 'face string) 'bold))
+    (set-text-properties 0 3 nil string)
+    (should-not (text-properties-at 2 string))))
+
+(ert-deftest textprop-tests-remove-text-properties ()
+  (with-temp-buffer
+    (insert (propertize "abcdef" 'face 'bold 'help-echo "x" 'mouse-face 'hl))
+    (should (remove-text-properties 2 4 '(face nil)))
+    (should-not (get-text-property 2 'face))
+    (should (eq (get-text-property 1 'face) 'bold))
+    (should (eq (get-text-property 4 'face) 'bold))
+    (should (equal (get-text-property 2 'help-echo) "x"))
+    ;; Nothing left to remove.
+    (should-not (remove-text-properties 2 4 '(face nil)))
+    (should (remove-list-of-text-properties 1 7 '(help-echo)))
+    (should-not (get-text-property 1 'help-echo))
+    (should (eq (get-text-property 3 'mouse-face) 'hl))
+    (should-not (remove-list-of-text-properties 1 7 '(help-echo))))
+  (let ((string (propertize "abc" 'face 'bold 'help-echo "x")))
+    (should (remove-list-of-text-properties 0 3 '(face) string))
+    (should-not (get-text-property 0 'face string))
+    (should (equal (get-text-property 0 'help-echo string) "x"))
+    (should-error (remove-text-properties 0 4 '(face nil) string)
+                  :type 'args-out-of-range)))
+
 (provide 'textprop-tests)
 ;; textprop-tests.el ends here.
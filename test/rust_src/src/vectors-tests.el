@@ -1,3 +1,16 @@
 (ert-deftest vector-tests-make ()
   (let ((v (make-vector 10 "asdfghjklqwertyuiopzxcvbnm")))
     (should (= 10 (length v)))))
+
+(ert-deftest vector-tests-length ()
+  (should (= (length "€uro") 4))
+  (should (= (string-bytes "€uro") 6))
+  (should (= (length [1 2 3]) 3))
+  (should (= (length (make-bool-vector 5 t)) 5))
+  (should (= (length '(1 2 3)) 3))
+  (should (= (length nil) 0))
+  (should-error (length '(1 2 . 3)) :type 'wrong-type-argument)
+  (let ((l (list 1 2)))
+    (setcdr (cdr l) l)
+    (should-error (length l) :type 'circular-list))
+  (should-error (length 'foo) :type 'wrong-type-argument))